@@ -0,0 +1,39 @@
+extern crate rsrl;
+#[macro_use]
+extern crate slog;
+
+use rsrl::{
+    bbo::{get_weights, PolicySearch, CEM},
+    domains::{CartPole, Domain},
+    fa::linear::{basis::{Fourier, Projector}, optim::SGD, LFA},
+    logging,
+    policies::Gibbs,
+    spaces::Space,
+    Evaluation,
+};
+
+fn main() {
+    let domain = CartPole::default();
+    let n_actions = domain.action_space().card().into();
+
+    let basis = Fourier::from_space(3, domain.state_space()).with_constant();
+    let policy = Gibbs::standard(LFA::vector(basis, SGD(1.0), n_actions));
+
+    let optimiser = CEM::new(get_weights(&policy), 1.0, 50, 10).with_extra_noise(0.01);
+    let mut agent = PolicySearch::new(optimiser, policy, 1000).with_episodes(3);
+
+    let logger = logging::root(logging::stdout());
+    let domain_builder = Box::new(CartPole::default);
+
+    // Training phase:
+    for generation in 0..50 {
+        let fitness = agent.generation(&domain_builder);
+
+        info!(logger, "generation complete"; "generation" => generation, "fitness" => fitness);
+    }
+
+    // Testing phase:
+    let testing_result = Evaluation::new(&mut agent, domain_builder).next().unwrap();
+
+    info!(logger, "solution"; testing_result);
+}
@@ -0,0 +1,58 @@
+extern crate rsrl;
+#[macro_use]
+extern crate slog;
+
+use rsrl::{
+    control::ac::COPDACQ,
+    domains::{ContinuousMountainCar, Domain},
+    fa::linear::{
+        basis::{Fourier, Projector},
+        optim::SGD,
+        DeterministicCFA,
+        LFA,
+    },
+    logging,
+    make_shared,
+    policies::{gaussian::{self, Gaussian}, Deterministic},
+    run,
+    spaces::Space,
+    Evaluation,
+    SerialExperiment,
+};
+
+fn main() {
+    let domain = ContinuousMountainCar::default();
+
+    let basis = Fourier::from_space(3, domain.state_space()).with_constant();
+    let mu = make_shared(LFA::scalar(basis.clone(), SGD(1.0)));
+
+    // Explore by adding Gaussian noise to the deterministic target policy:
+    let policy = make_shared(Deterministic::new(mu.clone()));
+    let behaviour = Gaussian::new(
+        gaussian::mean::Scalar(mu),
+        gaussian::stddev::Constant(0.5),
+    );
+
+    let mut agent = {
+        let q_func = DeterministicCFA::new(policy.clone(), basis, SGD(1.0));
+
+        COPDACQ::new(q_func, policy, behaviour, 0.001, 0.01, 1.0)
+    };
+
+    let logger = logging::root(logging::stdout());
+    let domain_builder = Box::new(ContinuousMountainCar::default);
+
+    // Training phase:
+    let _training_result = {
+        // Start a serial learning experiment up to 1000 steps per episode.
+        let e = SerialExperiment::new(&mut agent, domain_builder.clone(), 1000);
+
+        // Realise 1000 episodes of the experiment generator.
+        run(e, 1000, Some(logger.clone()))
+    };
+
+    // Testing phase:
+    let testing_result = Evaluation::new(&mut agent, domain_builder).next().unwrap();
+
+    info!(logger, "solution"; testing_result);
+}
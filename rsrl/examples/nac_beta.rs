@@ -30,10 +30,10 @@ fn main() {
     let domain = ContinuousMountainCar::default();
 
     let basis = Chebyshev::from_space(5, domain.state_space()).with_constant();
-    let policy = Beta::new(
+    let policy = make_shared(Beta::new(
         TransformedLFA::scalar(basis.clone(), Softplus),
         TransformedLFA::scalar(basis.clone(), Softplus),
-    );
+    ));
     let critic = {
         let optimiser = SGD(1.0);
         let q_func = StableCFA::new(policy.clone(), basis, optimiser);
@@ -0,0 +1,46 @@
+extern crate rsrl;
+#[macro_use]
+extern crate slog;
+
+use rsrl::{
+    run, make_shared, Evaluation, SerialExperiment,
+    control::td::QLearning,
+    domains::{Domain, MountainCar},
+    fa::linear::{basis::{Fourier, Projector}, BayesianLFA},
+    logging,
+    policies::PosteriorSampling,
+    spaces::Space,
+};
+
+fn main() {
+    let domain = MountainCar::default();
+    let mut agent = {
+        let n_actions = domain.action_space().card().into();
+
+        let basis = Fourier::from_space(3, domain.state_space()).with_constant();
+        let q_func = make_shared(BayesianLFA::new(basis, n_actions, 10.0, 1.0));
+
+        let policy = PosteriorSampling::new(q_func.clone());
+
+        // The Bayesian approximator consumes raw residuals, so use a unit
+        // step size:
+        QLearning::new(q_func, policy, 1.0, 1.0)
+    };
+
+    let logger = logging::root(logging::stdout());
+    let domain_builder = Box::new(MountainCar::default);
+
+    // Training phase:
+    let _training_result = {
+        // Start a serial learning experiment up to 1000 steps per episode.
+        let e = SerialExperiment::new(&mut agent, domain_builder.clone(), 1000);
+
+        // Realise 1000 episodes of the experiment generator.
+        run(e, 1000, Some(logger.clone()))
+    };
+
+    // Testing phase:
+    let testing_result = Evaluation::new(&mut agent, domain_builder).next().unwrap();
+
+    info!(logger, "solution"; testing_result);
+}
@@ -0,0 +1,119 @@
+use crate::{
+    bandits::BanditLearner,
+    policies::{EnumerablePolicy, Policy},
+    utils::argmaxima,
+};
+use rand::Rng;
+use super::{argmax_sample, greedy_probabilities, update_mean};
+
+/// ε-greedy action selection over sample-average (or exponential recency
+/// weighted) reward estimates.
+///
+/// # References
+/// - Sutton, R. S. and Barto, A. G. (2018). Reinforcement Learning: An
+/// Introduction (2nd ed.). MIT Press, Section 2.2.
+#[derive(Clone, Debug)]
+pub struct EpsilonGreedy {
+    pub epsilon: f64,
+    pub step_size: Option<f64>,
+
+    counts: Vec<usize>,
+    estimates: Vec<f64>,
+}
+
+impl EpsilonGreedy {
+    pub fn new(n_arms: usize, epsilon: f64) -> Self {
+        EpsilonGreedy {
+            epsilon,
+            step_size: None,
+
+            counts: vec![0; n_arms],
+            estimates: vec![0.0; n_arms],
+        }
+    }
+
+    /// Construct an agent using a constant step size, suitable for
+    /// non-stationary problems.
+    pub fn with_step_size(n_arms: usize, epsilon: f64, step_size: f64) -> Self {
+        EpsilonGreedy {
+            step_size: Some(step_size),
+
+            ..EpsilonGreedy::new(n_arms, epsilon)
+        }
+    }
+
+    /// Return the current reward estimate of each arm.
+    pub fn estimates(&self) -> &[f64] { &self.estimates }
+}
+
+impl<C> Policy<C> for EpsilonGreedy {
+    type Action = usize;
+
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R, _: &C) -> usize {
+        if rng.gen_bool(self.epsilon) {
+            rng.gen_range(0, self.estimates.len())
+        } else {
+            argmax_sample(rng, &self.estimates)
+        }
+    }
+
+    fn mpa(&self, _: &C) -> usize { argmaxima(&self.estimates).1[0] }
+
+    fn probability(&self, c: &C, a: &usize) -> f64 { self.probabilities(c)[*a] }
+}
+
+impl<C> EnumerablePolicy<C> for EpsilonGreedy {
+    fn n_actions(&self) -> usize { self.estimates.len() }
+
+    fn probabilities(&self, _: &C) -> Vec<f64> {
+        let pr = self.epsilon / self.estimates.len() as f64;
+
+        greedy_probabilities(&self.estimates)
+            .into_iter()
+            .map(|p| pr + p * (1.0 - self.epsilon))
+            .collect()
+    }
+}
+
+impl<C> BanditLearner<C> for EpsilonGreedy {
+    fn handle_pull(&mut self, _: &C, arm: usize, reward: f64) {
+        self.counts[arm] += 1;
+
+        match self.step_size {
+            Some(alpha) => self.estimates[arm] += alpha * (reward - self.estimates[arm]),
+            None => update_mean(&mut self.estimates[arm], self.counts[arm], reward),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        bandits::BanditLearner,
+        policies::EnumerablePolicy,
+        utils::compare_floats,
+    };
+    use super::EpsilonGreedy;
+
+    #[test]
+    fn test_estimates() {
+        let mut agent = EpsilonGreedy::new(2, 0.1);
+
+        agent.handle_pull(&(), 0, 1.0);
+        agent.handle_pull(&(), 0, 0.0);
+        agent.handle_pull(&(), 1, 0.25);
+
+        assert!(compare_floats(agent.estimates(), &[0.5, 0.25], 1e-7));
+        assert!(compare_floats(agent.probabilities(&()), &[0.95, 0.05], 1e-7));
+    }
+
+    #[test]
+    fn test_step_size() {
+        let mut agent = EpsilonGreedy::with_step_size(1, 0.0, 0.5);
+
+        agent.handle_pull(&(), 0, 1.0);
+        agent.handle_pull(&(), 0, 1.0);
+
+        assert!((agent.estimates()[0] - 0.75).abs() < 1e-7);
+    }
+}
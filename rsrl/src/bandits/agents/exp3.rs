@@ -0,0 +1,93 @@
+use crate::{
+    bandits::BanditLearner,
+    policies::{EnumerablePolicy, Policy, sample_probs_with_rng},
+    utils::argmaxima,
+};
+use rand::Rng;
+
+/// Exponential-weight algorithm for exploration and exploitation (EXP3).
+///
+/// Designed for adversarial bandits with rewards in `[0, 1]`. Arms are
+/// sampled from a mixture of the exponential weights distribution and the
+/// uniform distribution, with mixing coefficient `gamma`.
+///
+/// # References
+/// - Auer, P., Cesa-Bianchi, N., Freund, Y., Schapire, R. E. (2002). The
+/// nonstochastic multiarmed bandit problem. SIAM Journal on Computing, 32(1),
+/// 48–77.
+#[derive(Clone, Debug)]
+pub struct EXP3 {
+    pub gamma: f64,
+
+    log_weights: Vec<f64>,
+}
+
+impl EXP3 {
+    pub fn new(n_arms: usize, gamma: f64) -> Self {
+        EXP3 {
+            gamma,
+
+            log_weights: vec![0.0; n_arms],
+        }
+    }
+
+    fn distribution(&self) -> Vec<f64> {
+        let k = self.log_weights.len() as f64;
+        let max_lw = self.log_weights.iter().cloned().fold(::std::f64::MIN, f64::max);
+
+        let ws: Vec<f64> = self.log_weights.iter().map(|lw| (lw - max_lw).exp()).collect();
+        let z: f64 = ws.iter().sum();
+
+        ws.into_iter().map(|w| (1.0 - self.gamma) * w / z + self.gamma / k).collect()
+    }
+}
+
+impl<C> Policy<C> for EXP3 {
+    type Action = usize;
+
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R, _: &C) -> usize {
+        sample_probs_with_rng(rng, &self.distribution())
+    }
+
+    fn mpa(&self, _: &C) -> usize { argmaxima(&self.log_weights).1[0] }
+
+    fn probability(&self, c: &C, a: &usize) -> f64 { self.probabilities(c)[*a] }
+}
+
+impl<C> EnumerablePolicy<C> for EXP3 {
+    fn n_actions(&self) -> usize { self.log_weights.len() }
+
+    fn probabilities(&self, _: &C) -> Vec<f64> { self.distribution() }
+}
+
+impl<C> BanditLearner<C> for EXP3 {
+    fn handle_pull(&mut self, _: &C, arm: usize, reward: f64) {
+        let k = self.log_weights.len() as f64;
+        let p = self.distribution()[arm];
+
+        self.log_weights[arm] += self.gamma * (reward / p) / k;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{bandits::BanditLearner, policies::EnumerablePolicy};
+    use super::EXP3;
+
+    #[test]
+    fn test_probabilities() {
+        let mut agent = EXP3::new(4, 0.2);
+
+        assert!(agent.probabilities(&()).iter().all(|&p| (p - 0.25).abs() < 1e-7));
+
+        for _ in 0..100 {
+            agent.handle_pull(&(), 2, 1.0);
+        }
+
+        let ps = agent.probabilities(&());
+
+        assert!((ps.iter().sum::<f64>() - 1.0).abs() < 1e-7);
+        assert!(ps[2] > 0.5);
+        assert!(ps.iter().all(|&p| p >= 0.05 - 1e-7));
+    }
+}
@@ -0,0 +1,131 @@
+use crate::{
+    bandits::BanditLearner,
+    policies::{EnumerablePolicy, Policy},
+    utils::argmaxima,
+};
+use rand::Rng;
+use super::{argmax_sample, greedy_probabilities, update_mean};
+
+const EPS: f64 = 1e-12;
+const N_BISECTIONS: usize = 50;
+
+/// Kullback-Leibler divergence between Bernoulli distributions.
+fn bernoulli_kl(p: f64, q: f64) -> f64 {
+    let p = clip!(EPS, p, 1.0 - EPS);
+    let q = clip!(EPS, q, 1.0 - EPS);
+
+    p * (p / q).ln() + (1.0 - p) * ((1.0 - p) / (1.0 - q)).ln()
+}
+
+/// KL-UCB action selection for rewards bounded in `[0, 1]`.
+///
+/// The index of each arm is the largest mean `q` satisfying
+/// `n_a KL(μ_a, q) <= ln t + c ln ln t`, computed by bisection.
+///
+/// # References
+/// - Garivier, A., Cappé, O. (2011). The KL-UCB algorithm for bounded
+/// stochastic bandits and beyond. In Proceedings of the 24th Annual Conference
+/// on Learning Theory, pp. 359–376.
+#[derive(Clone, Debug)]
+pub struct KLUCB {
+    pub c: f64,
+
+    t: usize,
+    counts: Vec<usize>,
+    estimates: Vec<f64>,
+}
+
+impl KLUCB {
+    pub fn new(n_arms: usize, c: f64) -> Self {
+        KLUCB {
+            c,
+
+            t: 0,
+            counts: vec![0; n_arms],
+            estimates: vec![0.0; n_arms],
+        }
+    }
+
+    fn upper_bound(&self, mu: f64, n: usize) -> f64 {
+        let t = self.t.max(1) as f64;
+        let budget = (t.ln() + self.c * t.ln().max(1.0).ln()) / n as f64;
+
+        let (mut lb, mut ub) = (mu, 1.0);
+
+        for _ in 0..N_BISECTIONS {
+            let q = (lb + ub) / 2.0;
+
+            if bernoulli_kl(mu, q) > budget {
+                ub = q;
+            } else {
+                lb = q;
+            }
+        }
+
+        lb
+    }
+
+    /// Return the KL upper confidence index of each arm; unpulled arms are
+    /// assigned the largest representable value.
+    pub fn indices(&self) -> Vec<f64> {
+        self.counts.iter().zip(self.estimates.iter()).map(|(&n, &mu)| {
+            if n == 0 { ::std::f64::MAX } else { self.upper_bound(mu, n) }
+        }).collect()
+    }
+}
+
+impl<C> Policy<C> for KLUCB {
+    type Action = usize;
+
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R, _: &C) -> usize {
+        argmax_sample(rng, &self.indices())
+    }
+
+    fn mpa(&self, _: &C) -> usize { argmaxima(&self.indices()).1[0] }
+
+    fn probability(&self, c: &C, a: &usize) -> f64 { self.probabilities(c)[*a] }
+}
+
+impl<C> EnumerablePolicy<C> for KLUCB {
+    fn n_actions(&self) -> usize { self.counts.len() }
+
+    fn probabilities(&self, _: &C) -> Vec<f64> { greedy_probabilities(&self.indices()) }
+}
+
+impl<C> BanditLearner<C> for KLUCB {
+    fn handle_pull(&mut self, _: &C, arm: usize, reward: f64) {
+        self.t += 1;
+        self.counts[arm] += 1;
+
+        update_mean(&mut self.estimates[arm], self.counts[arm], reward);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bandits::BanditLearner;
+    use super::{KLUCB, bernoulli_kl};
+
+    #[test]
+    fn test_kl() {
+        assert!(bernoulli_kl(0.5, 0.5).abs() < 1e-10);
+        assert!(bernoulli_kl(0.1, 0.9) > bernoulli_kl(0.1, 0.5));
+    }
+
+    #[test]
+    fn test_indices() {
+        let mut agent = KLUCB::new(2, 0.0);
+
+        for _ in 0..10 {
+            agent.handle_pull(&(), 0, 0.5);
+        }
+
+        agent.handle_pull(&(), 1, 0.5);
+
+        let indices = agent.indices();
+
+        assert!(indices[0] > 0.5 && indices[0] < 1.0);
+        assert!(indices[1] > indices[0]);
+        assert!(indices.iter().all(|&i| i <= 1.0));
+    }
+}
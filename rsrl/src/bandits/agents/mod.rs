@@ -0,0 +1,35 @@
+//! Bandit agents module.
+use crate::utils::argmaxima;
+use rand::Rng;
+
+import_all!(epsilon_greedy);
+import_all!(ucb1);
+import_all!(kl_ucb);
+import_all!(thompson);
+import_all!(lin_ucb);
+import_all!(exp3);
+
+/// Distribute probability mass uniformly over the maximising entries.
+fn greedy_probabilities(values: &[f64]) -> Vec<f64> {
+    let mut ps = vec![0.0; values.len()];
+    let (_, maxima) = argmaxima(values);
+
+    let p = 1.0 / maxima.len() as f64;
+    for i in maxima {
+        ps[i] = p;
+    }
+
+    ps
+}
+
+/// Sample uniformly from the maximising entries.
+fn argmax_sample<R: Rng + ?Sized>(rng: &mut R, values: &[f64]) -> usize {
+    let (_, maxima) = argmaxima(values);
+
+    maxima[rng.gen_range(0, maxima.len())]
+}
+
+/// Incremental sample-average update of `estimate` given its visit `count`.
+fn update_mean(estimate: &mut f64, count: usize, reward: f64) {
+    *estimate += (reward - *estimate) / count as f64;
+}
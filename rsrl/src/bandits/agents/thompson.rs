@@ -0,0 +1,118 @@
+use crate::{
+    bandits::BanditLearner,
+    policies::{EnumerablePolicy, Policy},
+    utils::argmaxima,
+};
+use rand::{thread_rng, Rng};
+use rstat::{Distribution, univariate::continuous::Beta};
+use super::argmax_sample;
+
+/// Thompson sampling with independent Beta-Bernoulli posteriors.
+///
+/// Rewards are assumed to lie in `[0, 1]`; fractional rewards are handled by
+/// treating them as fractional successes. Since the selection probabilities
+/// are not available in closed form, `probabilities` returns a Monte-Carlo
+/// estimate using `n_samples` posterior draws.
+///
+/// # References
+/// - Thompson, W. R. (1933). On the likelihood that one unknown probability
+/// exceeds another in view of the evidence of two samples. Biometrika,
+/// 25(3/4), 285–294.
+/// - Chapelle, O., Li, L. (2011). An empirical evaluation of Thompson
+/// sampling. In Advances in Neural Information Processing Systems, pp.
+/// 2249–2257.
+#[derive(Clone, Debug)]
+pub struct ThompsonSampling {
+    pub n_samples: usize,
+
+    alphas: Vec<f64>,
+    betas: Vec<f64>,
+}
+
+impl ThompsonSampling {
+    pub fn new(n_arms: usize) -> Self { ThompsonSampling::with_prior(n_arms, 1.0, 1.0) }
+
+    /// Construct an agent with a common `Beta(alpha, beta)` prior on each arm.
+    pub fn with_prior(n_arms: usize, alpha: f64, beta: f64) -> Self {
+        ThompsonSampling {
+            n_samples: 1000,
+
+            alphas: vec![alpha; n_arms],
+            betas: vec![beta; n_arms],
+        }
+    }
+
+    /// Return the posterior mean of each arm.
+    pub fn posterior_means(&self) -> Vec<f64> {
+        self.alphas.iter().zip(self.betas.iter()).map(|(a, b)| a / (a + b)).collect()
+    }
+
+    fn draw<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec<f64> {
+        self.alphas.iter().zip(self.betas.iter()).map(|(&a, &b)| {
+            Beta::new(a, b).sample(rng)
+        }).collect()
+    }
+}
+
+impl<C> Policy<C> for ThompsonSampling {
+    type Action = usize;
+
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R, _: &C) -> usize {
+        let draws = self.draw(rng);
+
+        argmax_sample(rng, &draws)
+    }
+
+    fn mpa(&self, _: &C) -> usize { argmaxima(&self.posterior_means()).1[0] }
+
+    fn probability(&self, c: &C, a: &usize) -> f64 { self.probabilities(c)[*a] }
+}
+
+impl<C> EnumerablePolicy<C> for ThompsonSampling {
+    fn n_actions(&self) -> usize { self.alphas.len() }
+
+    fn probabilities(&self, c: &C) -> Vec<f64> {
+        let mut rng = thread_rng();
+        let mut ps = vec![0.0; self.alphas.len()];
+        let z = 1.0 / self.n_samples as f64;
+
+        for _ in 0..self.n_samples {
+            ps[self.sample(&mut rng, c)] += z;
+        }
+
+        ps
+    }
+}
+
+impl<C> BanditLearner<C> for ThompsonSampling {
+    fn handle_pull(&mut self, _: &C, arm: usize, reward: f64) {
+        self.alphas[arm] += reward;
+        self.betas[arm] += 1.0 - reward;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{bandits::BanditLearner, policies::EnumerablePolicy};
+    use super::ThompsonSampling;
+
+    #[test]
+    fn test_posterior() {
+        let mut agent = ThompsonSampling::new(2);
+
+        for _ in 0..50 {
+            agent.handle_pull(&(), 0, 1.0);
+            agent.handle_pull(&(), 1, 0.0);
+        }
+
+        let means = agent.posterior_means();
+
+        assert!((means[0] - 51.0 / 52.0).abs() < 1e-7);
+        assert!((means[1] - 1.0 / 52.0).abs() < 1e-7);
+
+        let ps = agent.probabilities(&());
+
+        assert!((ps[0] + ps[1] - 1.0).abs() < 1e-7);
+        assert!(ps[0] > 0.99);
+    }
+}
@@ -0,0 +1,111 @@
+use crate::{
+    bandits::BanditLearner,
+    policies::{EnumerablePolicy, Policy},
+    utils::argmaxima,
+};
+use rand::Rng;
+use super::{argmax_sample, greedy_probabilities, update_mean};
+
+/// Upper confidence bound (UCB1) action selection.
+///
+/// Each arm is pulled once, after which the arm maximising
+/// `μ_a + c sqrt(ln t / n_a)` is selected; `c = sqrt(2)` recovers UCB1.
+///
+/// # References
+/// - Auer, P., Cesa-Bianchi, N., Fischer, P. (2002). Finite-time analysis of
+/// the multiarmed bandit problem. Machine Learning, 47(2–3), 235–256.
+#[derive(Clone, Debug)]
+pub struct UCB1 {
+    pub c: f64,
+
+    t: usize,
+    counts: Vec<usize>,
+    estimates: Vec<f64>,
+}
+
+impl UCB1 {
+    pub fn new(n_arms: usize, c: f64) -> Self {
+        UCB1 {
+            c,
+
+            t: 0,
+            counts: vec![0; n_arms],
+            estimates: vec![0.0; n_arms],
+        }
+    }
+
+    /// Return the upper confidence index of each arm; unpulled arms are
+    /// assigned the largest representable value.
+    pub fn indices(&self) -> Vec<f64> {
+        let log_t = (self.t.max(1) as f64).ln();
+
+        self.counts.iter().zip(self.estimates.iter()).map(|(&n, &mu)| {
+            if n == 0 {
+                ::std::f64::MAX
+            } else {
+                mu + self.c * (log_t / n as f64).sqrt()
+            }
+        }).collect()
+    }
+}
+
+impl<C> Policy<C> for UCB1 {
+    type Action = usize;
+
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R, _: &C) -> usize {
+        argmax_sample(rng, &self.indices())
+    }
+
+    fn mpa(&self, _: &C) -> usize { argmaxima(&self.indices()).1[0] }
+
+    fn probability(&self, c: &C, a: &usize) -> f64 { self.probabilities(c)[*a] }
+}
+
+impl<C> EnumerablePolicy<C> for UCB1 {
+    fn n_actions(&self) -> usize { self.counts.len() }
+
+    fn probabilities(&self, _: &C) -> Vec<f64> { greedy_probabilities(&self.indices()) }
+}
+
+impl<C> BanditLearner<C> for UCB1 {
+    fn handle_pull(&mut self, _: &C, arm: usize, reward: f64) {
+        self.t += 1;
+        self.counts[arm] += 1;
+
+        update_mean(&mut self.estimates[arm], self.counts[arm], reward);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bandits::{BanditExperiment, envs::BernoulliBandit};
+    use super::UCB1;
+
+    #[test]
+    fn test_initial_exploration() {
+        let mut agent = UCB1::new(5, 2.0f64.sqrt());
+        let arms: Vec<usize> = {
+            let bandit = BernoulliBandit::new(vec![0.5; 5]);
+
+            BanditExperiment::new(&mut agent, bandit).take(5).map(|r| r.arm).collect()
+        };
+
+        let mut sorted = arms.clone();
+        sorted.sort();
+
+        assert_eq!(sorted, vec![0, 1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_identifies_best_arm() {
+        let mut agent = UCB1::new(3, 2.0f64.sqrt());
+        let bandit = BernoulliBandit::new(vec![0.1, 0.9, 0.2]);
+
+        let n_best = BanditExperiment::new(&mut agent, bandit)
+            .take(1000)
+            .filter(|r| r.arm == 1)
+            .count();
+
+        assert!(n_best > 700);
+    }
+}
@@ -0,0 +1,64 @@
+use crate::bandits::Bandit;
+use rand::{thread_rng, Rng, rngs::ThreadRng};
+
+/// Stationary bandit with Bernoulli-distributed rewards.
+#[derive(Debug)]
+pub struct BernoulliBandit {
+    pub probabilities: Vec<f64>,
+
+    rng: ThreadRng,
+}
+
+impl BernoulliBandit {
+    pub fn new(probabilities: Vec<f64>) -> Self {
+        if probabilities.iter().any(|&p| p < 0.0 || p > 1.0) {
+            panic!("Bernoulli success probabilities must lie in [0, 1].");
+        }
+
+        BernoulliBandit {
+            probabilities,
+
+            rng: thread_rng(),
+        }
+    }
+}
+
+impl Bandit for BernoulliBandit {
+    type Context = ();
+
+    fn n_arms(&self) -> usize { self.probabilities.len() }
+
+    fn context(&self) {}
+
+    fn expected_rewards(&self) -> Vec<f64> { self.probabilities.clone() }
+
+    fn pull(&mut self, arm: usize) -> f64 {
+        if self.rng.gen_bool(self.probabilities[arm]) { 1.0 } else { 0.0 }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bandits::Bandit;
+    use super::BernoulliBandit;
+
+    #[test]
+    fn test_rewards() {
+        let mut bandit = BernoulliBandit::new(vec![0.0, 1.0, 0.5]);
+
+        assert_eq!(bandit.n_arms(), 3);
+
+        for _ in 0..100 {
+            assert_eq!(bandit.pull(0), 0.0);
+            assert_eq!(bandit.pull(1), 1.0);
+
+            let r = bandit.pull(2);
+
+            assert!(r == 0.0 || r == 1.0);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_invalid_probabilities() { BernoulliBandit::new(vec![0.5, 1.5]); }
+}
@@ -0,0 +1,48 @@
+use crate::bandits::Bandit;
+use rand::{thread_rng, rngs::ThreadRng};
+use rstat::{Distribution, univariate::continuous::Normal};
+
+/// Stationary bandit with normally-distributed rewards.
+#[derive(Debug)]
+pub struct GaussianBandit {
+    pub means: Vec<f64>,
+    pub stddevs: Vec<f64>,
+
+    rng: ThreadRng,
+}
+
+impl GaussianBandit {
+    pub fn new(means: Vec<f64>, stddevs: Vec<f64>) -> Self {
+        if means.len() != stddevs.len() {
+            panic!("Expected one standard deviation per arm.");
+        }
+
+        GaussianBandit {
+            means,
+            stddevs,
+
+            rng: thread_rng(),
+        }
+    }
+
+    /// Construct a bandit in which all arms share the same noise level.
+    pub fn homoscedastic(means: Vec<f64>, stddev: f64) -> Self {
+        let stddevs = vec![stddev; means.len()];
+
+        GaussianBandit::new(means, stddevs)
+    }
+}
+
+impl Bandit for GaussianBandit {
+    type Context = ();
+
+    fn n_arms(&self) -> usize { self.means.len() }
+
+    fn context(&self) {}
+
+    fn expected_rewards(&self) -> Vec<f64> { self.means.clone() }
+
+    fn pull(&mut self, arm: usize) -> f64 {
+        Normal::new(self.means[arm], self.stddevs[arm]).sample(&mut self.rng)
+    }
+}
@@ -0,0 +1,83 @@
+use crate::bandits::Bandit;
+use ndarray::{Array1, Array2};
+use rand::{thread_rng, rngs::ThreadRng};
+use rstat::{Distribution, univariate::continuous::Normal};
+
+/// Contextual bandit with rewards linear in a shared context vector.
+///
+/// Each round, a context `x` is drawn uniformly from the unit sphere. The
+/// reward for arm `a` is then `θ_a · x` plus zero-mean Gaussian noise.
+#[derive(Debug)]
+pub struct LinearContextualBandit {
+    pub thetas: Array2<f64>,
+    pub stddev: f64,
+
+    context: Array1<f64>,
+    rng: ThreadRng,
+}
+
+impl LinearContextualBandit {
+    /// Construct a new bandit from an `[n_arms, n_features]` matrix of arm
+    /// parameters.
+    pub fn new(thetas: Array2<f64>, stddev: f64) -> Self {
+        let mut rng = thread_rng();
+        let context = LinearContextualBandit::sample_context(&mut rng, thetas.cols());
+
+        LinearContextualBandit {
+            thetas,
+            stddev,
+
+            context,
+            rng,
+        }
+    }
+
+    fn sample_context(rng: &mut ThreadRng, n_features: usize) -> Array1<f64> {
+        let normal = Normal::new(0.0, 1.0);
+        let x = Array1::from_shape_fn(n_features, |_| normal.sample(rng));
+        let z = x.dot(&x).sqrt();
+
+        x / z
+    }
+}
+
+impl Bandit for LinearContextualBandit {
+    type Context = Array1<f64>;
+
+    fn n_arms(&self) -> usize { self.thetas.rows() }
+
+    fn context(&self) -> Array1<f64> { self.context.clone() }
+
+    fn expected_rewards(&self) -> Vec<f64> { self.thetas.dot(&self.context).into_raw_vec() }
+
+    fn pull(&mut self, arm: usize) -> f64 {
+        let mean = self.thetas.row(arm).dot(&self.context);
+        let reward = Normal::new(mean, self.stddev).sample(&mut self.rng);
+
+        self.context = LinearContextualBandit::sample_context(&mut self.rng, self.thetas.cols());
+
+        reward
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bandits::Bandit;
+    use super::LinearContextualBandit;
+
+    #[test]
+    fn test_context() {
+        let mut bandit = LinearContextualBandit::new(array![[1.0, 0.0], [0.0, 1.0]], 0.1);
+
+        for _ in 0..10 {
+            let x = bandit.context();
+            let er = bandit.expected_rewards();
+
+            assert!((x.dot(&x) - 1.0).abs() < 1e-7);
+            assert!((er[0] - x[0]).abs() < 1e-7);
+            assert!((er[1] - x[1]).abs() < 1e-7);
+
+            bandit.pull(0);
+        }
+    }
+}
@@ -0,0 +1,5 @@
+//! Bandit environments module.
+import_all!(bernoulli);
+import_all!(gaussian);
+import_all!(non_stationary);
+import_all!(linear_contextual);
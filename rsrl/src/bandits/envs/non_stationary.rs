@@ -0,0 +1,73 @@
+use crate::bandits::Bandit;
+use rand::{thread_rng, rngs::ThreadRng};
+use rstat::{Distribution, univariate::continuous::Normal};
+
+/// Non-stationary Gaussian bandit whose arm means follow independent random
+/// walks.
+///
+/// After every pull, each mean is perturbed by zero-mean Gaussian noise with
+/// standard deviation `drift`.
+///
+/// # References
+/// - Sutton, R. S. and Barto, A. G. (2018). Reinforcement Learning: An
+/// Introduction (2nd ed.). MIT Press, Exercise 2.5.
+#[derive(Debug)]
+pub struct NonStationaryBandit {
+    pub means: Vec<f64>,
+
+    pub stddev: f64,
+    pub drift: f64,
+
+    rng: ThreadRng,
+}
+
+impl NonStationaryBandit {
+    pub fn new(means: Vec<f64>, stddev: f64, drift: f64) -> Self {
+        NonStationaryBandit {
+            means,
+
+            stddev,
+            drift,
+
+            rng: thread_rng(),
+        }
+    }
+}
+
+impl Bandit for NonStationaryBandit {
+    type Context = ();
+
+    fn n_arms(&self) -> usize { self.means.len() }
+
+    fn context(&self) {}
+
+    fn expected_rewards(&self) -> Vec<f64> { self.means.clone() }
+
+    fn pull(&mut self, arm: usize) -> f64 {
+        let reward = Normal::new(self.means[arm], self.stddev).sample(&mut self.rng);
+        let walk = Normal::new(0.0, self.drift);
+
+        for m in self.means.iter_mut() {
+            *m += walk.sample(&mut self.rng);
+        }
+
+        reward
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bandits::Bandit;
+    use super::NonStationaryBandit;
+
+    #[test]
+    fn test_drift() {
+        let mut bandit = NonStationaryBandit::new(vec![0.0; 5], 1.0, 0.1);
+
+        for _ in 0..100 {
+            bandit.pull(0);
+        }
+
+        assert!(bandit.expected_rewards().iter().all(|&m| m != 0.0));
+    }
+}
@@ -0,0 +1,139 @@
+//! Multi-armed and contextual bandits module.
+//!
+//! Bandit problems are single-state decision problems in which an agent
+//! repeatedly selects one of a finite number of arms and receives a stochastic
+//! reward. In the contextual setting, a context (or side-information) vector
+//! is revealed before each selection. Agents in this module implement
+//! [`Policy`](../policies/trait.Policy.html) and
+//! [`EnumerablePolicy`](../policies/trait.EnumerablePolicy.html) over the
+//! context type, and [`BanditLearner`](trait.BanditLearner.html) for handling
+//! feedback.
+use crate::policies::Policy;
+use rand::thread_rng;
+use slog::{Logger, Record, Result as LogResult, Serializer, KV};
+
+pub mod agents;
+pub mod envs;
+
+/// An interface for (possibly contextual) multi-armed bandit environments.
+pub trait Bandit {
+    /// Type of the side-information revealed before each round.
+    type Context;
+
+    /// Return the number of arms available.
+    fn n_arms(&self) -> usize;
+
+    /// Return the context of the current round.
+    fn context(&self) -> Self::Context;
+
+    /// Return the expected reward of each arm in the current round.
+    fn expected_rewards(&self) -> Vec<f64>;
+
+    /// Pull an arm, returning the sampled reward and advancing to the next
+    /// round.
+    fn pull(&mut self, arm: usize) -> f64;
+}
+
+/// An interface for agents that learn from bandit feedback.
+pub trait BanditLearner<C> {
+    /// Handle the reward obtained from pulling `arm` in the given `context`.
+    fn handle_pull(&mut self, context: &C, arm: usize, reward: f64);
+}
+
+/// Container for statistics from a single bandit round.
+#[derive(Debug, Clone)]
+pub struct Round {
+    /// The arm that was pulled.
+    pub arm: usize,
+
+    /// The reward received.
+    pub reward: f64,
+
+    /// The expected (pseudo-)regret incurred in this round.
+    pub regret: f64,
+
+    /// The cumulative (pseudo-)regret incurred up to and including this round.
+    pub cumulative_regret: f64,
+}
+
+impl KV for Round {
+    fn serialize(&self, _: &Record, serializer: &mut dyn Serializer) -> LogResult {
+        serializer.emit_usize("arm", self.arm)?;
+        serializer.emit_f64("reward", self.reward)?;
+        serializer.emit_f64("regret", self.regret)?;
+        serializer.emit_f64("cumulative_regret", self.cumulative_regret)?;
+
+        Ok(())
+    }
+}
+
+/// Utility for running a sequence of bandit rounds.
+pub struct BanditExperiment<'a, A: 'a, B> {
+    agent: &'a mut A,
+    bandit: B,
+
+    cumulative_regret: f64,
+}
+
+impl<'a, A, B: Bandit> BanditExperiment<'a, A, B> {
+    pub fn new(agent: &'a mut A, bandit: B) -> BanditExperiment<'a, A, B> {
+        BanditExperiment {
+            agent,
+            bandit,
+
+            cumulative_regret: 0.0,
+        }
+    }
+}
+
+impl<'a, A, B> Iterator for BanditExperiment<'a, A, B>
+where
+    A: Policy<B::Context, Action = usize> + BanditLearner<B::Context>,
+    B: Bandit,
+{
+    type Item = Round;
+
+    fn next(&mut self) -> Option<Round> {
+        let context = self.bandit.context();
+        let expected = self.bandit.expected_rewards();
+
+        let arm = self.agent.sample(&mut thread_rng(), &context);
+        let reward = self.bandit.pull(arm);
+
+        self.agent.handle_pull(&context, arm, reward);
+
+        let best = expected.iter().cloned().fold(::std::f64::MIN, f64::max);
+        let regret = best - expected[arm];
+
+        self.cumulative_regret += regret;
+
+        Some(Round {
+            arm,
+            reward,
+            regret,
+            cumulative_regret: self.cumulative_regret,
+        })
+    }
+}
+
+/// Helper function for running bandit experiments.
+pub fn run_bandit(
+    runner: impl Iterator<Item = Round>,
+    n_rounds: usize,
+    logger: Option<Logger>,
+) -> Vec<Round>
+{
+    let exp = runner.take(n_rounds);
+
+    match logger {
+        Some(logger) => exp
+            .zip(1..(n_rounds + 1))
+            .inspect(|&(ref res, i)| {
+                info!(logger, "round {}", i; res);
+            })
+            .map(|(res, _)| res)
+            .collect(),
+
+        None => exp.collect(),
+    }
+}
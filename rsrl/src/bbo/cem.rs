@@ -0,0 +1,97 @@
+use ndarray::{Array1, ArrayView1};
+use rand::Rng;
+use rstat::{Distribution, univariate::continuous::Normal};
+use super::{rank, Optimiser};
+
+/// Cross-entropy method with a diagonal Gaussian search distribution.
+///
+/// Each generation, the `n_elite` fittest candidates are used to refit the
+/// mean and variance of the sampling distribution. A constant `extra_noise`
+/// term is added to the variance to avoid premature convergence.
+///
+/// # References
+/// - Rubinstein, R. Y. (1999). The cross-entropy method for combinatorial and
+/// continuous optimization. Methodology and Computing in Applied Probability,
+/// 1(2), 127–190.
+/// - Szita, I., Lőrincz, A. (2006). Learning Tetris using the noisy
+/// cross-entropy method. Neural Computation, 18(12), 2936–2941.
+#[derive(Clone, Debug)]
+pub struct CEM {
+    pub mean: Array1<f64>,
+    pub variance: Array1<f64>,
+
+    pub population_size: usize,
+    pub n_elite: usize,
+    pub extra_noise: f64,
+}
+
+impl CEM {
+    pub fn new(mean: Array1<f64>, stddev: f64, population_size: usize, n_elite: usize) -> Self {
+        assert!(n_elite > 0 && n_elite <= population_size, "Invalid number of elites.");
+
+        let variance = Array1::from_elem(mean.len(), stddev * stddev);
+
+        CEM {
+            mean,
+            variance,
+
+            population_size,
+            n_elite,
+            extra_noise: 0.0,
+        }
+    }
+
+    /// Set the constant noise added to the variance after each update.
+    pub fn with_extra_noise(mut self, extra_noise: f64) -> Self {
+        self.extra_noise = extra_noise;
+        self
+    }
+}
+
+impl Optimiser for CEM {
+    fn dim(&self) -> usize { self.mean.len() }
+
+    fn mean(&self) -> ArrayView1<f64> { self.mean.view() }
+
+    fn ask<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec<Array1<f64>> {
+        let standard = Normal::new(0.0, 1.0);
+        let stddev = self.variance.mapv(f64::sqrt);
+
+        (0..self.population_size).map(|_| {
+            let z = Array1::from_shape_fn(self.dim(), |_| standard.sample(rng));
+
+            &self.mean + &(z * &stddev)
+        }).collect()
+    }
+
+    fn tell(&mut self, candidates: &[Array1<f64>], fitnesses: &[f64]) {
+        let elites = rank(fitnesses);
+        let elites = &elites[..self.n_elite.min(elites.len())];
+        let n = elites.len() as f64;
+
+        let mean = elites.iter().fold(Array1::<f64>::zeros(self.dim()), |acc, &i| {
+            acc + &candidates[i]
+        }) / n;
+        let variance = elites.iter().fold(Array1::<f64>::zeros(self.dim()), |acc, &i| {
+            acc + (&candidates[i] - &mean).mapv(|v| v * v)
+        }) / n;
+
+        self.mean = mean;
+        self.variance = variance + self.extra_noise;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CEM;
+    use crate::bbo::optimise;
+    use ndarray::Array1;
+
+    #[test]
+    fn test_sphere() {
+        let cem = CEM::new(Array1::zeros(3), 1.0, 50, 10).with_extra_noise(1e-6);
+        let x = optimise(cem, 50);
+
+        assert!(x.iter().all(|&v| (v - 1.0).abs() < 0.05));
+    }
+}
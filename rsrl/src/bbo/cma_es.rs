@@ -0,0 +1,195 @@
+use ndarray::{Array1, Array2, ArrayView1, Axis};
+use ndarray_linalg::{UPLO, eigh::Eigh};
+use rand::Rng;
+use rstat::{Distribution, univariate::continuous::Normal};
+use super::{rank, Optimiser};
+
+/// Covariance matrix adaptation evolution strategy.
+///
+/// Maintains a full-covariance Gaussian search distribution, `m + σ N(0, C)`,
+/// adapting `C` through rank-one and rank-μ updates and the global step-size
+/// `σ` through cumulative step-length adaptation. The population size and all
+/// learning rates take their standard default values.
+///
+/// # References
+/// - Hansen, N., Ostermeier, A. (2001). Completely derandomized
+/// self-adaptation in evolution strategies. Evolutionary Computation, 9(2),
+/// 159–195.
+/// - Hansen, N. (2016). The CMA evolution strategy: A tutorial.
+/// arXiv:1604.00772.
+#[derive(Clone, Debug)]
+pub struct CMAES {
+    pub mean: Array1<f64>,
+    pub sigma: f64,
+    pub covariance: Array2<f64>,
+
+    pub population_size: usize,
+
+    weights: Array1<f64>,
+    mu_eff: f64,
+
+    c_sigma: f64,
+    d_sigma: f64,
+    c_c: f64,
+    c_1: f64,
+    c_mu: f64,
+    chi_n: f64,
+
+    p_sigma: Array1<f64>,
+    p_c: Array1<f64>,
+
+    basis: Array2<f64>,
+    scales: Array1<f64>,
+    generation: usize,
+}
+
+impl CMAES {
+    pub fn new(mean: Array1<f64>, sigma: f64) -> Self {
+        let n = mean.len();
+        let population_size = 4 + (3.0 * (n as f64).ln()).floor() as usize;
+
+        CMAES::with_population(mean, sigma, population_size)
+    }
+
+    /// Construct an instance with a non-default population size.
+    pub fn with_population(mean: Array1<f64>, sigma: f64, population_size: usize) -> Self {
+        let n = mean.len();
+        let nf = n as f64;
+        let mu = population_size / 2;
+
+        let weights = Array1::from_shape_fn(mu, |i| {
+            (mu as f64 + 0.5).ln() - (i as f64 + 1.0).ln()
+        });
+        let weights = &weights / weights.sum();
+        let mu_eff = 1.0 / weights.dot(&weights);
+
+        let c_sigma = (mu_eff + 2.0) / (nf + mu_eff + 5.0);
+        let d_sigma =
+            1.0 + 2.0 * (((mu_eff - 1.0) / (nf + 1.0)).sqrt() - 1.0).max(0.0) + c_sigma;
+        let c_c = (4.0 + mu_eff / nf) / (nf + 4.0 + 2.0 * mu_eff / nf);
+        let c_1 = 2.0 / ((nf + 1.3).powi(2) + mu_eff);
+        let c_mu = (1.0 - c_1).min(
+            2.0 * (mu_eff - 2.0 + 1.0 / mu_eff) / ((nf + 2.0).powi(2) + mu_eff)
+        );
+        let chi_n = nf.sqrt() * (1.0 - 1.0 / (4.0 * nf) + 1.0 / (21.0 * nf * nf));
+
+        CMAES {
+            mean,
+            sigma,
+            covariance: Array2::eye(n),
+
+            population_size,
+
+            weights,
+            mu_eff,
+
+            c_sigma,
+            d_sigma,
+            c_c,
+            c_1,
+            c_mu,
+            chi_n,
+
+            p_sigma: Array1::zeros(n),
+            p_c: Array1::zeros(n),
+
+            basis: Array2::eye(n),
+            scales: Array1::ones(n),
+            generation: 0,
+        }
+    }
+
+    // Refresh the eigendecomposition, C = B D² Bᵀ, after updating C.
+    fn decompose(&mut self) {
+        // Enforce symmetry to guard against the accumulation of round-off:
+        let c = (&self.covariance + &self.covariance.t()) / 2.0;
+
+        if let Ok((eigvals, eigvecs)) = c.eigh(UPLO::Lower) {
+            self.scales = eigvals.mapv(|v| v.max(1e-20).sqrt());
+            self.basis = eigvecs;
+            self.covariance = c;
+        }
+    }
+}
+
+impl Optimiser for CMAES {
+    fn dim(&self) -> usize { self.mean.len() }
+
+    fn mean(&self) -> ArrayView1<f64> { self.mean.view() }
+
+    fn ask<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec<Array1<f64>> {
+        let standard = Normal::new(0.0, 1.0);
+
+        (0..self.population_size).map(|_| {
+            let z = Array1::from_shape_fn(self.dim(), |_| standard.sample(rng));
+
+            &self.mean + &(self.basis.dot(&(z * &self.scales)) * self.sigma)
+        }).collect()
+    }
+
+    fn tell(&mut self, candidates: &[Array1<f64>], fitnesses: &[f64]) {
+        let n = self.dim();
+        let ys: Vec<Array1<f64>> = rank(fitnesses)
+            .into_iter()
+            .take(self.weights.len())
+            .map(|i| (&candidates[i] - &self.mean) / self.sigma)
+            .collect();
+
+        let y_w = ys.iter().zip(self.weights.iter()).fold(Array1::<f64>::zeros(n), |acc, (y, &w)| {
+            acc + y * w
+        });
+
+        self.mean.scaled_add(self.sigma, &y_w);
+        self.generation += 1;
+
+        // Step-size path, using C^{-1/2} y_w = B D^{-1} Bᵀ y_w:
+        let c_inv_sqrt_y = self.basis.dot(&(self.basis.t().dot(&y_w) / &self.scales));
+
+        self.p_sigma *= 1.0 - self.c_sigma;
+        self.p_sigma.scaled_add(
+            (self.c_sigma * (2.0 - self.c_sigma) * self.mu_eff).sqrt(),
+            &c_inv_sqrt_y
+        );
+
+        let ps_norm = self.p_sigma.dot(&self.p_sigma).sqrt();
+        let ps_decay = (1.0 - (1.0 - self.c_sigma).powi(2 * self.generation as i32)).sqrt();
+        let h_sigma = ps_norm / ps_decay < (1.4 + 2.0 / (n as f64 + 1.0)) * self.chi_n;
+
+        // Covariance path:
+        self.p_c *= 1.0 - self.c_c;
+
+        if h_sigma {
+            self.p_c.scaled_add((self.c_c * (2.0 - self.c_c) * self.mu_eff).sqrt(), &y_w);
+        }
+
+        // Rank-one and rank-μ updates:
+        let delta_h = if h_sigma { 0.0 } else { self.c_c * (2.0 - self.c_c) };
+        let p_c = self.p_c.view().insert_axis(Axis(1));
+
+        self.covariance *= 1.0 - self.c_1 - self.c_mu + self.c_1 * delta_h;
+        self.covariance.scaled_add(self.c_1, &p_c.dot(&p_c.t()));
+
+        for (y, &w) in ys.iter().zip(self.weights.iter()) {
+            let y = y.view().insert_axis(Axis(1));
+
+            self.covariance.scaled_add(self.c_mu * w, &y.dot(&y.t()));
+        }
+
+        self.sigma *= ((self.c_sigma / self.d_sigma) * (ps_norm / self.chi_n - 1.0)).exp();
+        self.decompose();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CMAES;
+    use crate::bbo::optimise;
+    use ndarray::Array1;
+
+    #[test]
+    fn test_sphere() {
+        let x = optimise(CMAES::new(Array1::zeros(3), 0.5), 200);
+
+        assert!(x.iter().all(|&v| (v - 1.0).abs() < 1e-3));
+    }
+}
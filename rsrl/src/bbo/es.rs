@@ -0,0 +1,101 @@
+use ndarray::{Array1, ArrayView1};
+use rand::Rng;
+use rstat::{Distribution, univariate::continuous::Normal};
+use super::Optimiser;
+
+/// Gaussian evolution strategy with antithetic sampling.
+///
+/// Candidates are generated in mirrored pairs, `θ ± σε`, and the mean is
+/// moved along the Monte-Carlo estimate of the gradient of the smoothed
+/// fitness, `Σ (F⁺ - F⁻) ε / (2nσ)`. Fitnesses are replaced by their centred
+/// ranks to make the update invariant to monotonic transformations.
+///
+/// # References
+/// - Salimans, T., Ho, J., Chen, X., Sidor, S., Sutskever, I. (2017).
+/// Evolution strategies as a scalable alternative to reinforcement learning.
+/// arXiv:1703.03864.
+#[derive(Clone, Debug)]
+pub struct ES {
+    pub mean: Array1<f64>,
+    pub sigma: f64,
+    pub alpha: f64,
+
+    pub n_pairs: usize,
+}
+
+impl ES {
+    pub fn new(mean: Array1<f64>, sigma: f64, alpha: f64, n_pairs: usize) -> Self {
+        ES {
+            mean,
+            sigma,
+            alpha,
+
+            n_pairs,
+        }
+    }
+}
+
+// Replace each fitness by its rank, scaled to the interval [-0.5, 0.5].
+fn centred_ranks(fitnesses: &[f64]) -> Vec<f64> {
+    let n = fitnesses.len();
+    let mut ranks = vec![0.0; n];
+
+    if n < 2 {
+        return ranks;
+    }
+
+    for (r, i) in super::rank(fitnesses).into_iter().rev().enumerate() {
+        ranks[i] = r as f64 / (n - 1) as f64 - 0.5;
+    }
+
+    ranks
+}
+
+impl Optimiser for ES {
+    fn dim(&self) -> usize { self.mean.len() }
+
+    fn mean(&self) -> ArrayView1<f64> { self.mean.view() }
+
+    fn ask<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec<Array1<f64>> {
+        let standard = Normal::new(0.0, 1.0);
+
+        (0..self.n_pairs).flat_map(|_| {
+            let eps = Array1::from_shape_fn(self.dim(), |_| standard.sample(rng)) * self.sigma;
+
+            vec![&self.mean + &eps, &self.mean - &eps]
+        }).collect()
+    }
+
+    fn tell(&mut self, candidates: &[Array1<f64>], fitnesses: &[f64]) {
+        let ranks = centred_ranks(fitnesses);
+        let n_pairs = candidates.len() / 2;
+
+        let grad = (0..n_pairs).fold(Array1::<f64>::zeros(self.dim()), |acc, i| {
+            let eps = (&candidates[2 * i] - &self.mean) / self.sigma;
+
+            acc + eps * (ranks[2 * i] - ranks[2 * i + 1])
+        }) / (2.0 * n_pairs as f64 * self.sigma);
+
+        self.mean.scaled_add(self.alpha, &grad);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{centred_ranks, ES};
+    use crate::bbo::optimise;
+    use ndarray::Array1;
+
+    #[test]
+    fn test_centred_ranks() {
+        assert_eq!(centred_ranks(&[3.0, 1.0, 2.0]), vec![0.5, -0.5, 0.0]);
+    }
+
+    #[test]
+    fn test_sphere() {
+        let es = ES::new(Array1::zeros(3), 0.1, 0.05, 20);
+        let x = optimise(es, 300);
+
+        assert!(x.iter().all(|&v| (v - 1.0).abs() < 0.1));
+    }
+}
@@ -0,0 +1,197 @@
+//! Black-box optimisation module.
+//!
+//! Derivative-free methods which treat the weights of a `Parameterised` policy
+//! as a single flat vector and improve them using only episodic returns.
+use crate::{
+    control::Controller,
+    domains::{Domain, State, Action},
+    fa::Parameterised,
+    policies::Policy,
+};
+use ndarray::{Array1, ArrayView1};
+use rand::{thread_rng, Rng};
+
+/// Interface for black-box optimisers which maximise a fitness function.
+pub trait Optimiser {
+    /// Return the dimensionality of the search space.
+    fn dim(&self) -> usize;
+
+    /// Return the current estimate of the optimal solution.
+    fn mean(&self) -> ArrayView1<f64>;
+
+    /// Sample a population of candidate solutions.
+    fn ask<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec<Array1<f64>>;
+
+    /// Update the search distribution given the fitness of each candidate.
+    fn tell(&mut self, candidates: &[Array1<f64>], fitnesses: &[f64]);
+}
+
+/// Flatten the weights of `policy` into a vector.
+pub fn get_weights<P: Parameterised>(policy: &P) -> Array1<f64> {
+    policy.weights_view().iter().cloned().collect()
+}
+
+/// Overwrite the weights of `policy` with the entries of `weights`.
+pub fn set_weights<P: Parameterised>(policy: &mut P, weights: ArrayView1<f64>) {
+    let mut view = policy.weights_view_mut();
+
+    assert_eq!(view.len(), weights.len(), "Weight vector has incorrect length.");
+
+    view.iter_mut().zip(weights.iter()).for_each(|(w, &x)| *w = x);
+}
+
+/// Return the mean total reward of `policy` over `n_episodes` rollouts, each of
+/// which is truncated after at most `step_limit` steps.
+pub fn evaluate<D, P>(
+    policy: &P,
+    domain_factory: &impl Fn() -> D,
+    n_episodes: usize,
+    step_limit: u64,
+) -> f64
+where
+    D: Domain,
+    P: Policy<State<D>, Action = Action<D>>,
+{
+    let mut rng = thread_rng();
+    let total: f64 = (0..n_episodes).map(|_| {
+        let mut domain = domain_factory();
+        let mut a = policy.sample(&mut rng, domain.emit().state());
+        let mut ret = 0.0;
+
+        for j in 1..(step_limit + 1) {
+            let t = domain.step(a);
+
+            ret += t.reward;
+
+            if t.terminated() || j >= step_limit {
+                break
+            } else {
+                a = policy.sample(&mut rng, t.to.state());
+            }
+        }
+
+        ret
+    }).sum();
+
+    total / n_episodes as f64
+}
+
+/// Episodic policy search driven by a black-box optimiser.
+///
+/// Each generation, the optimiser proposes a population of weight vectors,
+/// every candidate is loaded into the policy and evaluated over `n_episodes`
+/// rollouts of at most `step_limit` steps, and the resulting returns are fed
+/// back to the optimiser. The policy is left holding the optimiser's current
+/// mean.
+pub struct PolicySearch<O, P> {
+    pub optimiser: O,
+    pub policy: P,
+
+    pub n_episodes: usize,
+    pub step_limit: u64,
+}
+
+impl<O: Optimiser, P: Parameterised> PolicySearch<O, P> {
+    pub fn new(optimiser: O, mut policy: P, step_limit: u64) -> Self {
+        set_weights(&mut policy, optimiser.mean());
+
+        PolicySearch {
+            optimiser,
+            policy,
+
+            n_episodes: 1,
+            step_limit,
+        }
+    }
+
+    /// Set the number of episodes used to evaluate each candidate.
+    pub fn with_episodes(mut self, n_episodes: usize) -> Self {
+        self.n_episodes = n_episodes;
+        self
+    }
+
+    /// Run a single generation of the search and return the mean fitness of
+    /// the population.
+    pub fn generation<D>(&mut self, domain_factory: impl Fn() -> D) -> f64
+    where
+        D: Domain,
+        P: Policy<State<D>, Action = Action<D>>,
+    {
+        let candidates = self.optimiser.ask(&mut thread_rng());
+        let fitnesses: Vec<f64> = candidates.iter().map(|c| {
+            set_weights(&mut self.policy, c.view());
+
+            evaluate(&self.policy, &domain_factory, self.n_episodes, self.step_limit)
+        }).collect();
+
+        self.optimiser.tell(&candidates, &fitnesses);
+        set_weights(&mut self.policy, self.optimiser.mean());
+
+        fitnesses.iter().sum::<f64>() / fitnesses.len() as f64
+    }
+}
+
+impl<S, O, P: Policy<S>> Controller<S, P::Action> for PolicySearch<O, P> {
+    fn sample_target(&self, rng: &mut impl Rng, s: &S) -> P::Action {
+        self.policy.sample(rng, s)
+    }
+
+    fn sample_behaviour(&self, rng: &mut impl Rng, s: &S) -> P::Action {
+        self.policy.sample(rng, s)
+    }
+}
+
+// Return the indices of `fitnesses` sorted from best to worst, with NaN
+// fitnesses ranked as the worst.
+fn rank(fitnesses: &[f64]) -> Vec<usize> {
+    let key = |i: usize| {
+        if fitnesses[i].is_nan() { ::std::f64::NEG_INFINITY } else { fitnesses[i] }
+    };
+    let mut idx: Vec<usize> = (0..fitnesses.len()).collect();
+
+    idx.sort_by(|&i, &j| key(j).partial_cmp(&key(i)).unwrap());
+    idx
+}
+
+import_all!(cem);
+import_all!(es);
+import_all!(cma_es);
+
+#[cfg(test)]
+pub(crate) fn sphere(x: &Array1<f64>) -> f64 { -(x - 1.0).mapv(|v| v * v).sum() }
+
+#[cfg(test)]
+pub(crate) fn optimise<O: Optimiser>(mut opt: O, n_generations: usize) -> Array1<f64> {
+    let mut rng = thread_rng();
+
+    for _ in 0..n_generations {
+        let candidates = opt.ask(&mut rng);
+        let fitnesses: Vec<f64> = candidates.iter().map(sphere).collect();
+
+        opt.tell(&candidates, &fitnesses);
+    }
+
+    opt.mean().to_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{domains::MountainCar, policies::Random};
+    use super::{evaluate, rank};
+
+    #[test]
+    fn test_evaluate_step_limit() {
+        // Pushing left never reaches the goal, so each rollout must be truncated:
+        assert_eq!(evaluate(&Random::new(1), &MountainCar::default, 2, 10), -10.0);
+    }
+
+    #[test]
+    fn test_rank() {
+        assert_eq!(rank(&[0.0, 2.0, -1.0, 1.0]), vec![1, 3, 0, 2]);
+    }
+
+    #[test]
+    fn test_rank_nan() {
+        assert_eq!(rank(&[0.0, ::std::f64::NAN, 1.0]), vec![2, 0, 1]);
+    }
+}
@@ -2,17 +2,21 @@ use crate::{
     OnlineLearner,
     control::Controller,
     domains::Transition,
-    policies::{Policy, DifferentiablePolicy},
+    policies::{Policy, DifferentiablePolicy, EntropyBonus, PolicyBonus},
     prediction::{ValuePredictor, ActionValuePredictor},
 };
 use rand::Rng;
 
 /// Advantage actor-critic.
-pub struct A2C<C, P> {
+///
+/// An optional bonus, such as an `EntropyBonus` added by `with_entropy_bonus`,
+/// may be added to the policy update to discourage premature convergence.
+pub struct A2C<C, P, B = ()> {
     pub critic: C,
     pub policy: P,
 
     pub alpha: f64,
+    pub bonus: B,
 }
 
 impl<C, P> A2C<C, P> {
@@ -22,14 +26,30 @@ impl<C, P> A2C<C, P> {
             policy,
 
             alpha,
+            bonus: (),
+        }
+    }
+
+    /// Add an entropy bonus with weight `tau` to the policy update.
+    ///
+    /// The policy is then required to implement `Entropy`, which is used as a
+    /// baseline for the bonus.
+    pub fn with_entropy_bonus(self, tau: f64) -> A2C<C, P, EntropyBonus> {
+        A2C {
+            critic: self.critic,
+            policy: self.policy,
+
+            alpha: self.alpha,
+            bonus: EntropyBonus(tau),
         }
     }
 }
 
-impl<S, C, P> OnlineLearner<S, P::Action> for A2C<C, P>
+impl<S, C, P, B> OnlineLearner<S, P::Action> for A2C<C, P, B>
 where
     C: OnlineLearner<S, P::Action> + ValuePredictor<S> + ActionValuePredictor<S, P::Action>,
     P: DifferentiablePolicy<S>,
+    B: PolicyBonus<S, P>,
     P::Action: Clone,
 {
     fn handle_transition(&mut self, t: &Transition<S, P::Action>) {
@@ -39,7 +59,9 @@ where
         let v = self.critic.predict_v(s);
         let qsa = self.critic.predict_q(s, &t.action);
 
-        self.policy.update(s, &t.action, self.alpha * (qsa - v));
+        let bonus = self.bonus.bonus(&self.policy, s, &t.action);
+
+        self.policy.update(s, &t.action, self.alpha * (qsa - v + bonus));
     }
 
     fn handle_terminal(&mut self) {
@@ -47,7 +69,7 @@ where
     }
 }
 
-impl<S, C, P> ValuePredictor<S> for A2C<C, P>
+impl<S, C, P, B> ValuePredictor<S> for A2C<C, P, B>
 where
     C: ValuePredictor<S>,
 {
@@ -56,7 +78,7 @@ where
     }
 }
 
-impl<S, C, P> ActionValuePredictor<S, P::Action> for A2C<C, P>
+impl<S, C, P, B> ActionValuePredictor<S, P::Action> for A2C<C, P, B>
 where
     C: ActionValuePredictor<S, P::Action>,
     P: Policy<S>,
@@ -66,7 +88,7 @@ where
     }
 }
 
-impl<S, C, P> Controller<S, P::Action> for A2C<C, P>
+impl<S, C, P, B> Controller<S, P::Action> for A2C<C, P, B>
 where
     P: Policy<S>,
 {
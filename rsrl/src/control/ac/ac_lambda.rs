@@ -0,0 +1,240 @@
+use crate::{
+    OnlineLearner,
+    control::Controller,
+    domains::Transition,
+    fa::{
+        Weights, WeightsView, WeightsViewMut, Parameterised,
+        StateFunction, DifferentiableStateFunction,
+    },
+    policies::{Policy, DifferentiablePolicy},
+    prediction::{AverageRewardPredictor, ValuePredictor},
+    traces::Trace,
+};
+use ndarray::Array2;
+use rand::Rng;
+
+/// Actor-critic with eligibility traces for both the actor and critic.
+///
+/// The critic is a TD(λ) learner over the state-value function with trace
+/// decay `lambda_critic` and step size `beta`; the actor follows the TD error
+/// along a trace of policy score functions, decayed by `lambda_actor`, with step
+/// size `alpha`. This is the episodic algorithm, though, as is common in
+/// practice, the `γ^t` weighting of the actor update is omitted; see
+/// `DifferentialACLambda` for continuing tasks.
+///
+/// # References
+/// - Sutton, R. S. and Barto, A. G. (2018). Reinforcement Learning: An
+/// Introduction (2nd ed.). MIT Press, Section 13.5.
+#[derive(Parameterised)]
+pub struct ACLambda<V, P, TC, TA = TC> {
+    #[weights] pub v_func: V,
+    pub policy: P,
+
+    pub alpha: f64,
+    pub beta: f64,
+    pub gamma: f64,
+    pub lambda_actor: f64,
+    pub lambda_critic: f64,
+
+    critic_trace: TC,
+    actor_trace: TA,
+}
+
+impl<V, P, TC, TA> ACLambda<V, P, TC, TA> {
+    pub fn new(
+        v_func: V,
+        policy: P,
+        critic_trace: TC,
+        actor_trace: TA,
+        alpha: f64,
+        beta: f64,
+        gamma: f64,
+        lambda_actor: f64,
+        lambda_critic: f64,
+    ) -> Self {
+        ACLambda {
+            v_func,
+            policy,
+
+            alpha,
+            beta,
+            gamma,
+            lambda_actor,
+            lambda_critic,
+
+            critic_trace,
+            actor_trace,
+        }
+    }
+}
+
+impl<S, V, P, TC, TA> OnlineLearner<S, P::Action> for ACLambda<V, P, TC, TA>
+where
+    V: DifferentiableStateFunction<S, Output = f64>,
+    P: DifferentiablePolicy<S>,
+    TC: Trace<V::Gradient>,
+    TA: Trace<Array2<f64>>,
+{
+    fn handle_transition(&mut self, t: &Transition<S, P::Action>) {
+        let s = t.from.state();
+        let v = self.v_func.evaluate(s);
+
+        let td_error = if t.terminated() {
+            t.reward - v
+        } else {
+            t.reward + self.gamma * self.v_func.evaluate(t.to.state()) - v
+        };
+
+        self.critic_trace.scaled_update(self.gamma * self.lambda_critic, &self.v_func.grad(s));
+        self.actor_trace.scaled_update(
+            self.gamma * self.lambda_actor,
+            &self.policy.grad_log(s, &t.action)
+        );
+
+        self.v_func.update_grad_scaled(self.critic_trace.deref(), self.beta * td_error);
+        self.policy.update_grad_scaled(&self.actor_trace.view(), self.alpha * td_error);
+    }
+
+    fn handle_terminal(&mut self) {
+        self.critic_trace.reset();
+        self.actor_trace.reset();
+    }
+}
+
+impl<S, V, P, TC, TA> ValuePredictor<S> for ACLambda<V, P, TC, TA>
+where
+    V: StateFunction<S, Output = f64>,
+{
+    fn predict_v(&self, s: &S) -> f64 {
+        self.v_func.evaluate(s)
+    }
+}
+
+impl<S, V, P, TC, TA> Controller<S, P::Action> for ACLambda<V, P, TC, TA>
+where
+    P: Policy<S>,
+{
+    fn sample_target(&self, rng: &mut impl Rng, s: &S) -> P::Action {
+        self.policy.sample(rng, s)
+    }
+
+    fn sample_behaviour(&self, rng: &mut impl Rng, s: &S) -> P::Action {
+        self.policy.sample(rng, s)
+    }
+}
+
+/// Actor-critic with eligibility traces for the average reward setting.
+///
+/// The continuing counterpart of `ACLambda`: the critic learns a differential
+/// state-value function along with an estimate of the average reward rate,
+/// `rho`, with step size `eta`, and both actor and critic follow the
+/// differential TD error, `r - rho + v(s') - v(s)`. The traces are decayed by
+/// `lambda_critic` and `lambda_actor` alone, without discounting.
+///
+/// # References
+/// - Sutton, R. S. and Barto, A. G. (2018). Reinforcement Learning: An
+/// Introduction (2nd ed.). MIT Press, Section 13.6.
+#[derive(Parameterised)]
+pub struct DifferentialACLambda<V, P, TC, TA = TC> {
+    #[weights] pub v_func: V,
+    pub policy: P,
+
+    pub alpha: f64,
+    pub beta: f64,
+    pub eta: f64,
+    pub lambda_actor: f64,
+    pub lambda_critic: f64,
+
+    pub rho: f64,
+
+    critic_trace: TC,
+    actor_trace: TA,
+}
+
+impl<V, P, TC, TA> DifferentialACLambda<V, P, TC, TA> {
+    pub fn new(
+        v_func: V,
+        policy: P,
+        critic_trace: TC,
+        actor_trace: TA,
+        alpha: f64,
+        beta: f64,
+        eta: f64,
+        lambda_actor: f64,
+        lambda_critic: f64,
+    ) -> Self {
+        DifferentialACLambda {
+            v_func,
+            policy,
+
+            alpha,
+            beta,
+            eta,
+            lambda_actor,
+            lambda_critic,
+
+            rho: 0.0,
+
+            critic_trace,
+            actor_trace,
+        }
+    }
+}
+
+impl<S, V, P, TC, TA> OnlineLearner<S, P::Action> for DifferentialACLambda<V, P, TC, TA>
+where
+    V: DifferentiableStateFunction<S, Output = f64>,
+    P: DifferentiablePolicy<S>,
+    TC: Trace<V::Gradient>,
+    TA: Trace<Array2<f64>>,
+{
+    fn handle_transition(&mut self, t: &Transition<S, P::Action>) {
+        let s = t.from.state();
+        let v = self.v_func.evaluate(s);
+
+        let td_error = if t.terminated() {
+            t.reward - self.rho - v
+        } else {
+            t.reward - self.rho + self.v_func.evaluate(t.to.state()) - v
+        };
+
+        self.rho += self.eta * td_error;
+
+        self.critic_trace.scaled_update(self.lambda_critic, &self.v_func.grad(s));
+        self.actor_trace.scaled_update(self.lambda_actor, &self.policy.grad_log(s, &t.action));
+
+        self.v_func.update_grad_scaled(self.critic_trace.deref(), self.beta * td_error);
+        self.policy.update_grad_scaled(&self.actor_trace.view(), self.alpha * td_error);
+    }
+
+    fn handle_terminal(&mut self) {
+        self.critic_trace.reset();
+        self.actor_trace.reset();
+    }
+}
+
+impl<S, V, P, TC, TA> ValuePredictor<S> for DifferentialACLambda<V, P, TC, TA>
+where
+    V: StateFunction<S, Output = f64>,
+{
+    fn predict_v(&self, s: &S) -> f64 {
+        self.v_func.evaluate(s)
+    }
+}
+
+impl<V, P, TC, TA> AverageRewardPredictor for DifferentialACLambda<V, P, TC, TA> {
+    fn predict_rho(&self) -> f64 { self.rho }
+}
+
+impl<S, V, P, TC, TA> Controller<S, P::Action> for DifferentialACLambda<V, P, TC, TA>
+where
+    P: Policy<S>,
+{
+    fn sample_target(&self, rng: &mut impl Rng, s: &S) -> P::Action {
+        self.policy.sample(rng, s)
+    }
+
+    fn sample_behaviour(&self, rng: &mut impl Rng, s: &S) -> P::Action {
+        self.policy.sample(rng, s)
+    }
+}
@@ -42,6 +42,7 @@ impl<S, C, PT, PB> OnlineLearner<S, PT::Action> for CACLA<C, PT, PB>
 where
     C: OnlineLearner<S, PT::Action> + ValuePredictor<S>,
     PT: DifferentiablePolicy<S, Action = f64>,
+    PB: Policy<S, Action = f64>,
 {
     fn handle_transition(&mut self, t: &Transition<S, PT::Action>) {
         let s = t.from.state();
@@ -63,6 +64,8 @@ where
 
     fn handle_terminal(&mut self) {
         self.critic.handle_terminal();
+        self.target_policy.handle_terminal();
+        self.behaviour_policy.handle_terminal();
     }
 }
 
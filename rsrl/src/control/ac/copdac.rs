@@ -0,0 +1,412 @@
+use crate::{
+    OnlineLearner,
+    control::Controller,
+    domains::Transition,
+    fa::{
+        Parameterised, Weights, WeightsView, WeightsViewMut,
+        StateActionFunction, linear::LinearStateActionFunction,
+    },
+    policies::{Policy, DeterministicPolicy},
+    prediction::{ValuePredictor, ActionValuePredictor},
+};
+use ndarray::{Array1, Array2};
+use rand::{Rng, thread_rng};
+
+// Compute the deterministic policy gradient, `∇_θ μ(x) ∇_θ μ(x)ᵀ w`, using the
+// advantage weights of a compatible critic.
+//
+// The gradient must be computed, and the critic updated, before the step is
+// applied to the actor: the features of a compatible critic depend on `μ(x)`.
+fn actor_grad<S, Q, P>(q_func: &Q, policy: &P, s: &S) -> Array2<f64>
+where
+    Q: Parameterised,
+    P: DeterministicPolicy<S>,
+{
+    let pw_dim = policy.weights_dim();
+    let n_features = pw_dim[0] * pw_dim[1];
+
+    let jacobian = policy.jacobian(s);
+    let w = q_func.weights_view();
+    let dq_du = jacobian.iter().zip(w.slice(s![0..n_features, 0])).fold(0.0, |acc, (j, w)| {
+        acc + j * w
+    });
+
+    jacobian * dq_du
+}
+
+/// Compatible off-policy deterministic actor-critic with a Q-learning critic.
+///
+/// The critic should be a compatible linear approximator, e.g.
+/// `fa::linear::DeterministicCFA`, whose leading weights parameterise the
+/// advantage function and are shared with the actor update.
+///
+/// # References
+/// - Silver, D., Lever, G., Heess, N., Degris, T., Wierstra, D., Riedmiller,
+/// M. (2014). Deterministic policy gradient algorithms. In Proceedings of the
+/// 31st International Conference on Machine Learning, pp. 387–395.
+#[derive(Parameterised)]
+pub struct COPDACQ<Q, P, B> {
+    #[weights] pub q_func: Q,
+
+    pub policy: P,
+    pub behaviour: B,
+
+    pub alpha: f64,
+    pub beta: f64,
+    pub gamma: f64,
+}
+
+impl<Q, P, B> COPDACQ<Q, P, B> {
+    pub fn new(q_func: Q, policy: P, behaviour: B, alpha: f64, beta: f64, gamma: f64) -> Self {
+        COPDACQ {
+            q_func,
+
+            policy,
+            behaviour,
+
+            alpha,
+            beta,
+            gamma,
+        }
+    }
+}
+
+impl<S, Q, P, B> OnlineLearner<S, f64> for COPDACQ<Q, P, B>
+where
+    Q: StateActionFunction<S, f64, Output = f64> + Parameterised,
+    P: DeterministicPolicy<S>,
+    B: Policy<S, Action = f64>,
+{
+    fn handle_transition(&mut self, t: &Transition<S, f64>) {
+        let s = t.from.state();
+        let qsa = self.q_func.evaluate(s, &t.action);
+
+        let td_error = if t.terminated() {
+            t.reward - qsa
+        } else {
+            let ns = t.to.state();
+            let na = self.policy.mpa(ns);
+
+            t.reward + self.gamma * self.q_func.evaluate(ns, &na) - qsa
+        };
+
+        let grad = actor_grad(&self.q_func, &self.policy, s);
+
+        self.q_func.update(s, &t.action, self.beta * td_error);
+        self.policy.weights_view_mut().scaled_add(self.alpha, &grad);
+    }
+
+    fn handle_terminal(&mut self) { self.behaviour.handle_terminal(); }
+}
+
+impl<S, Q, P, B> ValuePredictor<S> for COPDACQ<Q, P, B>
+where
+    Q: StateActionFunction<S, f64, Output = f64>,
+    P: DeterministicPolicy<S>,
+{
+    fn predict_v(&self, s: &S) -> f64 { self.q_func.evaluate(s, &self.policy.mpa(s)) }
+}
+
+impl<S, Q, P, B> ActionValuePredictor<S, f64> for COPDACQ<Q, P, B>
+where
+    Q: StateActionFunction<S, f64, Output = f64>,
+{
+    fn predict_q(&self, s: &S, a: &f64) -> f64 { self.q_func.evaluate(s, a) }
+}
+
+impl<S, Q, P, B> Controller<S, f64> for COPDACQ<Q, P, B>
+where
+    P: DeterministicPolicy<S>,
+    B: Policy<S, Action = f64>,
+{
+    fn sample_target(&self, _: &mut impl Rng, s: &S) -> f64 { self.policy.mpa(s) }
+
+    fn sample_behaviour(&self, rng: &mut impl Rng, s: &S) -> f64 {
+        self.behaviour.sample(rng, s)
+    }
+}
+
+/// Compatible on-policy deterministic actor-critic with a SARSA critic.
+///
+/// Equivalent to `COPDACQ` with the target policy acting as its own behaviour
+/// policy: the critic bootstraps from the action taken by the policy in the
+/// next state. Exploration must therefore come from the policy itself, or from
+/// the environment.
+///
+/// # References
+/// - Silver, D., Lever, G., Heess, N., Degris, T., Wierstra, D., Riedmiller,
+/// M. (2014). Deterministic policy gradient algorithms. In Proceedings of the
+/// 31st International Conference on Machine Learning, pp. 387–395.
+#[derive(Parameterised)]
+pub struct CDAC<Q, P> {
+    #[weights] pub q_func: Q,
+    pub policy: P,
+
+    pub alpha: f64,
+    pub beta: f64,
+    pub gamma: f64,
+}
+
+impl<Q, P> CDAC<Q, P> {
+    pub fn new(q_func: Q, policy: P, alpha: f64, beta: f64, gamma: f64) -> Self {
+        CDAC {
+            q_func,
+            policy,
+
+            alpha,
+            beta,
+            gamma,
+        }
+    }
+}
+
+impl<S, Q, P> OnlineLearner<S, f64> for CDAC<Q, P>
+where
+    Q: StateActionFunction<S, f64, Output = f64> + Parameterised,
+    P: DeterministicPolicy<S>,
+{
+    fn handle_transition(&mut self, t: &Transition<S, f64>) {
+        let s = t.from.state();
+        let qsa = self.q_func.evaluate(s, &t.action);
+
+        let td_error = if t.terminated() {
+            t.reward - qsa
+        } else {
+            let ns = t.to.state();
+            let na = self.policy.sample(&mut thread_rng(), ns);
+
+            t.reward + self.gamma * self.q_func.evaluate(ns, &na) - qsa
+        };
+
+        let grad = actor_grad(&self.q_func, &self.policy, s);
+
+        self.q_func.update(s, &t.action, self.beta * td_error);
+        self.policy.weights_view_mut().scaled_add(self.alpha, &grad);
+    }
+
+    fn handle_terminal(&mut self) { self.policy.handle_terminal(); }
+}
+
+impl<S, Q, P> ValuePredictor<S> for CDAC<Q, P>
+where
+    Q: StateActionFunction<S, f64, Output = f64>,
+    P: DeterministicPolicy<S>,
+{
+    fn predict_v(&self, s: &S) -> f64 { self.q_func.evaluate(s, &self.policy.mpa(s)) }
+}
+
+impl<S, Q, P> ActionValuePredictor<S, f64> for CDAC<Q, P>
+where
+    Q: StateActionFunction<S, f64, Output = f64>,
+{
+    fn predict_q(&self, s: &S, a: &f64) -> f64 { self.q_func.evaluate(s, a) }
+}
+
+impl<S, Q, P> Controller<S, f64> for CDAC<Q, P>
+where
+    P: DeterministicPolicy<S>,
+{
+    fn sample_target(&self, _: &mut impl Rng, s: &S) -> f64 { self.policy.mpa(s) }
+
+    fn sample_behaviour(&self, rng: &mut impl Rng, s: &S) -> f64 { self.policy.sample(rng, s) }
+}
+
+/// Compatible off-policy deterministic actor-critic with a gradient Q-learning
+/// critic.
+///
+/// Replaces the semi-gradient critic of `COPDACQ` with a linear gradient-TD
+/// update using auxiliary weights, `u`, which guarantees stability of the
+/// critic under off-policy sampling. The secondary step size is given by `eta`.
+///
+/// # References
+/// - Silver, D., Lever, G., Heess, N., Degris, T., Wierstra, D., Riedmiller,
+/// M. (2014). Deterministic policy gradient algorithms. In Proceedings of the
+/// 31st International Conference on Machine Learning, pp. 387–395.
+/// - Sutton, R. S., Maei, H. R., Precup, D., Bhatnagar, S., Silver, D.,
+/// Szepesvári, Cs. & Wiewiora, E. (2009). Fast gradient-descent methods for
+/// temporal-difference learning with linear function approximation. In
+/// Proceedings of the 26th Annual International Conference on Machine
+/// Learning, pp. 993–1000.
+#[derive(Parameterised)]
+pub struct COPDACGQ<Q, P, B> {
+    #[weights] pub q_func: Q,
+
+    pub policy: P,
+    pub behaviour: B,
+
+    pub alpha: f64,
+    pub beta: f64,
+    pub eta: f64,
+    pub gamma: f64,
+
+    u: Array1<f64>,
+}
+
+impl<Q: Parameterised, P, B> COPDACGQ<Q, P, B> {
+    pub fn new(
+        q_func: Q,
+        policy: P,
+        behaviour: B,
+        alpha: f64,
+        beta: f64,
+        eta: f64,
+        gamma: f64,
+    ) -> Self {
+        let n_features = q_func.weights_dim()[0];
+
+        COPDACGQ {
+            q_func,
+
+            policy,
+            behaviour,
+
+            alpha,
+            beta,
+            eta,
+            gamma,
+
+            u: Array1::zeros(n_features),
+        }
+    }
+}
+
+impl<S, Q, P, B> OnlineLearner<S, f64> for COPDACGQ<Q, P, B>
+where
+    Q: LinearStateActionFunction<S, f64, Output = f64>,
+    P: DeterministicPolicy<S>,
+    B: Policy<S, Action = f64>,
+{
+    fn handle_transition(&mut self, t: &Transition<S, f64>) {
+        let s = t.from.state();
+
+        let phi_s = self.q_func.features(s, &t.action);
+        let qsa = self.q_func.evaluate_features(&phi_s, &t.action);
+        let phi_s = phi_s.expanded();
+
+        let (td_error, phi_ns) = if t.terminated() {
+            (t.reward - qsa, Array1::zeros(phi_s.len()))
+        } else {
+            let ns = t.to.state();
+            let na = self.policy.mpa(ns);
+
+            let phi_ns = self.q_func.features(ns, &na);
+            let nqsna = self.q_func.evaluate_features(&phi_ns, &na);
+
+            (t.reward + self.gamma * nqsna - qsa, phi_ns.expanded())
+        };
+        let u_phi = self.u.dot(&phi_s);
+        let grad = actor_grad(&self.q_func, &self.policy, s);
+
+        {
+            let mut w = self.q_func.weights_view_mut();
+            let mut w = w.column_mut(0);
+
+            w.scaled_add(self.beta * td_error, &phi_s);
+            w.scaled_add(-self.beta * self.gamma * u_phi, &phi_ns);
+        }
+
+        self.u.scaled_add(self.eta * (td_error - u_phi), &phi_s);
+        self.policy.weights_view_mut().scaled_add(self.alpha, &grad);
+    }
+
+    fn handle_terminal(&mut self) { self.behaviour.handle_terminal(); }
+}
+
+impl<S, Q, P, B> ValuePredictor<S> for COPDACGQ<Q, P, B>
+where
+    Q: StateActionFunction<S, f64, Output = f64>,
+    P: DeterministicPolicy<S>,
+{
+    fn predict_v(&self, s: &S) -> f64 { self.q_func.evaluate(s, &self.policy.mpa(s)) }
+}
+
+impl<S, Q, P, B> ActionValuePredictor<S, f64> for COPDACGQ<Q, P, B>
+where
+    Q: StateActionFunction<S, f64, Output = f64>,
+{
+    fn predict_q(&self, s: &S, a: &f64) -> f64 { self.q_func.evaluate(s, a) }
+}
+
+impl<S, Q, P, B> Controller<S, f64> for COPDACGQ<Q, P, B>
+where
+    P: DeterministicPolicy<S>,
+    B: Policy<S, Action = f64>,
+{
+    fn sample_target(&self, _: &mut impl Rng, s: &S) -> f64 { self.policy.mpa(s) }
+
+    fn sample_behaviour(&self, rng: &mut impl Rng, s: &S) -> f64 {
+        self.behaviour.sample(rng, s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        OnlineLearner,
+        domains::{Observation, Transition},
+        fa::{
+            Parameterised,
+            linear::{DeterministicCFA, LFA, basis::{Polynomial, Projector}, optim::SGD},
+        },
+        make_shared,
+        policies::{Deterministic, Policy},
+    };
+    use super::{CDAC, COPDACGQ, COPDACQ};
+
+    fn transition() -> Transition<Vec<f64>, f64> {
+        Transition {
+            from: Observation::Full(vec![0.0]),
+            action: 1.0,
+            reward: 1.0,
+            to: Observation::Terminal(vec![0.0]),
+        }
+    }
+
+    macro_rules! make_critic {
+        () => {{
+            let basis = Polynomial::new(1, 1).with_constant();
+            let policy = make_shared(Deterministic::new(LFA::scalar(basis.clone(), SGD(1.0))));
+
+            (DeterministicCFA::new(policy.clone(), basis, SGD(1.0)), policy)
+        }};
+    }
+
+    // After the first transition, the critic has weight 1 on each non-zero
+    // feature and the actor is unchanged. The second transition then has a TD
+    // error of -1, and the critic step must use the features of the old actor,
+    // μ(0) = 0, which returns every critic weight to 0.
+    macro_rules! check_critic_before_actor {
+        ($agent:expr) => {{
+            let mut agent = $agent;
+
+            agent.handle_transition(&transition());
+            agent.handle_transition(&transition());
+
+            assert!(agent.q_func.weights().iter().all(|&w| w == 0.0));
+            assert_eq!(agent.policy.mpa(&vec![0.0]), 0.5);
+        }};
+    }
+
+    #[test]
+    fn test_copdac_q() {
+        let (q_func, policy) = make_critic!();
+
+        check_critic_before_actor!(COPDACQ::new(q_func, policy.clone(), policy, 0.5, 1.0, 1.0));
+    }
+
+    #[test]
+    fn test_copdac_gq() {
+        let (q_func, policy) = make_critic!();
+
+        check_critic_before_actor!(
+            COPDACGQ::new(q_func, policy.clone(), policy, 0.5, 1.0, 0.1, 1.0)
+        );
+    }
+
+    #[test]
+    fn test_cdac() {
+        let (q_func, policy) = make_critic!();
+
+        check_critic_before_actor!(CDAC::new(q_func, policy, 0.5, 1.0, 1.0));
+    }
+}
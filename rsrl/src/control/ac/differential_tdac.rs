@@ -0,0 +1,97 @@
+use crate::{
+    OnlineLearner,
+    control::Controller,
+    domains::Transition,
+    policies::{Policy, DifferentiablePolicy},
+    prediction::{AverageRewardPredictor, ValuePredictor, ActionValuePredictor},
+};
+use rand::Rng;
+
+/// Differential TD-error actor-critic for the average reward setting.
+///
+/// The critic is expected to learn a differential value function along with
+/// the average reward rate, e.g. `prediction::td::DifferentialTD`; the actor
+/// then follows the differential TD error, `r - rho + v(s') - v(s)`.
+///
+/// # References
+/// - Sutton, R. S. and Barto, A. G. (2018). Reinforcement Learning: An
+/// Introduction (2nd ed.). MIT Press, Section 13.6.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct DifferentialTDAC<C, P> {
+    pub critic: C,
+    pub policy: P,
+
+    pub alpha: f64,
+}
+
+impl<C, P> DifferentialTDAC<C, P> {
+    pub fn new(critic: C, policy: P, alpha: f64) -> Self {
+        DifferentialTDAC {
+            critic,
+            policy,
+
+            alpha,
+        }
+    }
+}
+
+impl<S, C, P> OnlineLearner<S, P::Action> for DifferentialTDAC<C, P>
+where
+    C: OnlineLearner<S, P::Action> + ValuePredictor<S> + AverageRewardPredictor,
+    P: DifferentiablePolicy<S>,
+    P::Action: Clone,
+{
+    fn handle_transition(&mut self, t: &Transition<S, P::Action>) {
+        let s = t.from.state();
+        let v = self.critic.predict_v(s);
+        let rho = self.critic.predict_rho();
+        let td_error = if t.terminated() {
+            t.reward - rho - v
+        } else {
+            t.reward - rho + self.critic.predict_v(t.to.state()) - v
+        };
+
+        self.critic.handle_transition(t);
+        self.policy.update(s, &t.action, self.alpha * td_error);
+    }
+
+    fn handle_terminal(&mut self) {
+        self.critic.handle_terminal();
+    }
+}
+
+impl<S, C, P> ValuePredictor<S> for DifferentialTDAC<C, P>
+where
+    C: ValuePredictor<S>,
+{
+    fn predict_v(&self, s: &S) -> f64 {
+        self.critic.predict_v(s)
+    }
+}
+
+impl<S, C, P> ActionValuePredictor<S, P::Action> for DifferentialTDAC<C, P>
+where
+    C: ActionValuePredictor<S, P::Action>,
+    P: Policy<S>,
+{
+    fn predict_q(&self, s: &S, a: &P::Action) -> f64 {
+        self.critic.predict_q(s, a)
+    }
+}
+
+impl<C: AverageRewardPredictor, P> AverageRewardPredictor for DifferentialTDAC<C, P> {
+    fn predict_rho(&self) -> f64 { self.critic.predict_rho() }
+}
+
+impl<S, C, P> Controller<S, P::Action> for DifferentialTDAC<C, P>
+where
+    P: Policy<S>,
+{
+    fn sample_target(&self, rng: &mut impl Rng, s: &S) -> P::Action {
+        self.policy.sample(rng, s)
+    }
+
+    fn sample_behaviour(&self, rng: &mut impl Rng, s: &S) -> P::Action {
+        self.policy.sample(rng, s)
+    }
+}
@@ -2,6 +2,9 @@ import_all!(cacla);
 
 import_all!(qac);
 import_all!(tdac);
+import_all!(ac_lambda);
+import_all!(differential_tdac);
 import_all!(a2c);
 import_all!(nac);
 import_all!(offpac);
+import_all!(copdac);
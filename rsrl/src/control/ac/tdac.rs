@@ -2,19 +2,23 @@ use crate::{
     OnlineLearner,
     control::Controller,
     domains::Transition,
-    policies::{Policy, DifferentiablePolicy},
+    policies::{Policy, DifferentiablePolicy, EntropyBonus, PolicyBonus},
     prediction::{ValuePredictor, ActionValuePredictor},
 };
 use rand::Rng;
 
 /// TD-error actor-critic.
+///
+/// An optional bonus, such as an `EntropyBonus` added by `with_entropy_bonus`,
+/// may be added to the policy update to discourage premature convergence.
 #[derive(Clone, Debug, Serialize, Deserialize)]
-pub struct TDAC<C, P> {
+pub struct TDAC<C, P, B = ()> {
     pub critic: C,
     pub policy: P,
 
     pub alpha: f64,
     pub gamma: f64,
+    pub bonus: B,
 }
 
 impl<C, P> TDAC<C, P> {
@@ -25,14 +29,31 @@ impl<C, P> TDAC<C, P> {
 
             alpha,
             gamma,
+            bonus: (),
+        }
+    }
+
+    /// Add an entropy bonus with weight `tau` to the policy update.
+    ///
+    /// The policy is then required to implement `Entropy`, which is used as a
+    /// baseline for the bonus.
+    pub fn with_entropy_bonus(self, tau: f64) -> TDAC<C, P, EntropyBonus> {
+        TDAC {
+            critic: self.critic,
+            policy: self.policy,
+
+            alpha: self.alpha,
+            gamma: self.gamma,
+            bonus: EntropyBonus(tau),
         }
     }
 }
 
-impl<S, C, P> OnlineLearner<S, P::Action> for TDAC<C, P>
+impl<S, C, P, B> OnlineLearner<S, P::Action> for TDAC<C, P, B>
 where
     C: OnlineLearner<S, P::Action> + ValuePredictor<S>,
     P: DifferentiablePolicy<S>,
+    B: PolicyBonus<S, P>,
     P::Action: Clone,
 {
     fn handle_transition(&mut self, t: &Transition<S, P::Action>) {
@@ -44,8 +65,10 @@ where
             t.reward + self.gamma * self.predict_v(t.to.state()) - v
         };
 
+        let bonus = self.bonus.bonus(&self.policy, s, &t.action);
+
         self.critic.handle_transition(t);
-        self.policy.update(s, &t.action, self.alpha * td_error);
+        self.policy.update(s, &t.action, self.alpha * (td_error + bonus));
     }
 
     fn handle_terminal(&mut self) {
@@ -53,7 +76,7 @@ where
     }
 }
 
-impl<S, C, P> ValuePredictor<S> for TDAC<C, P>
+impl<S, C, P, B> ValuePredictor<S> for TDAC<C, P, B>
 where
     C: ValuePredictor<S>,
 {
@@ -62,7 +85,7 @@ where
     }
 }
 
-impl<S, C, P> ActionValuePredictor<S, P::Action> for TDAC<C, P>
+impl<S, C, P, B> ActionValuePredictor<S, P::Action> for TDAC<C, P, B>
 where
     C: ActionValuePredictor<S, P::Action>,
     P: Policy<S>,
@@ -72,7 +95,7 @@ where
     }
 }
 
-impl<S, C, P> Controller<S, P::Action> for TDAC<C, P>
+impl<S, C, P, B> Controller<S, P::Action> for TDAC<C, P, B>
 where
     P: Policy<S>,
 {
@@ -0,0 +1,87 @@
+use crate::{
+    OnlineLearner,
+    control::Controller,
+    domains::Transition,
+    policies::VisitCounter,
+    prediction::{ValuePredictor, ActionValuePredictor},
+};
+use rand::Rng;
+
+/// Count-based exploration wrapper for online learners.
+///
+/// Every transition handled by the wrapper increments the visit count of the
+/// state-action pair it originated from. When `beta` is positive, an
+/// intrinsic bonus of `beta / sqrt(N(s, a))` is also added to the reward
+/// before the transition is forwarded to the wrapped learner. Sharing the
+/// counter with a `policies::UCB` policy yields UCB-style exploration.
+///
+/// # References
+/// - Strehl, A. L., Littman, M. L. (2008). An analysis of model-based interval
+/// estimation for Markov decision processes. Journal of Computer and System
+/// Sciences, 74(8), 1309–1331.
+/// - Bellemare, M., Srinivasan, S., Ostrovski, G., Schaul, T., Saxton, D.,
+/// Munos, R. (2016). Unifying count-based exploration and intrinsic
+/// motivation. In Advances in Neural Information Processing Systems, pp.
+/// 1471–1479.
+#[derive(Clone, Debug)]
+pub struct CountBased<L, C> {
+    pub learner: L,
+    pub counter: C,
+
+    pub beta: f64,
+}
+
+impl<L, C> CountBased<L, C> {
+    pub fn new(learner: L, counter: C, beta: f64) -> Self {
+        CountBased {
+            learner,
+            counter,
+
+            beta,
+        }
+    }
+}
+
+impl<S, L, C> OnlineLearner<S, usize> for CountBased<L, C>
+where
+    S: Clone,
+    L: OnlineLearner<S, usize>,
+    C: VisitCounter<S>,
+{
+    fn handle_transition(&mut self, t: &Transition<S, usize>) {
+        let s = t.from.state();
+
+        self.counter.increment(s, t.action);
+
+        if self.beta > 0.0 {
+            let n = self.counter.count(s, t.action) as f64;
+
+            self.learner.handle_transition(&Transition {
+                from: t.from.clone(),
+                action: t.action,
+                reward: t.reward + self.beta / n.sqrt(),
+                to: t.to.clone(),
+            });
+        } else {
+            self.learner.handle_transition(t);
+        }
+    }
+
+    fn handle_terminal(&mut self) { self.learner.handle_terminal() }
+}
+
+impl<S, A, L: Controller<S, A>, C> Controller<S, A> for CountBased<L, C> {
+    fn sample_target(&self, rng: &mut impl Rng, s: &S) -> A { self.learner.sample_target(rng, s) }
+
+    fn sample_behaviour(&self, rng: &mut impl Rng, s: &S) -> A {
+        self.learner.sample_behaviour(rng, s)
+    }
+}
+
+impl<S, L: ValuePredictor<S>, C> ValuePredictor<S> for CountBased<L, C> {
+    fn predict_v(&self, s: &S) -> f64 { self.learner.predict_v(s) }
+}
+
+impl<S, A, L: ActionValuePredictor<S, A>, C> ActionValuePredictor<S, A> for CountBased<L, C> {
+    fn predict_q(&self, s: &S, a: &A) -> f64 { self.learner.predict_q(s, a) }
+}
@@ -0,0 +1,2 @@
+//! Exploration wrappers module.
+import_all!(count_based);
@@ -3,16 +3,21 @@ use crate::{
     control::Controller,
     domains::Transition,
     fa::{Weights, WeightsView, WeightsViewMut, Parameterised},
-    policies::{Policy, DifferentiablePolicy},
+    policies::{Policy, DifferentiablePolicy, EntropyBonus, PolicyBonus},
 };
 use rand::Rng;
 
+/// Monte-Carlo policy gradient.
+///
+/// An optional bonus, such as an `EntropyBonus` added by `with_entropy_bonus`,
+/// may be added to the policy update to discourage premature convergence.
 #[derive(Clone, Debug, Serialize, Deserialize, Parameterised)]
-pub struct REINFORCE<P> {
+pub struct REINFORCE<P, B = ()> {
     #[weights] pub policy: P,
 
     pub alpha: f64,
     pub gamma: f64,
+    pub bonus: B,
 }
 
 impl<P> REINFORCE<P> {
@@ -22,13 +27,29 @@ impl<P> REINFORCE<P> {
 
             alpha,
             gamma,
+            bonus: (),
+        }
+    }
+
+    /// Add an entropy bonus with weight `tau` to the policy update.
+    ///
+    /// The policy is then required to implement `Entropy`, which is used as a
+    /// baseline for the bonus.
+    pub fn with_entropy_bonus(self, tau: f64) -> REINFORCE<P, EntropyBonus> {
+        REINFORCE {
+            policy: self.policy,
+
+            alpha: self.alpha,
+            gamma: self.gamma,
+            bonus: EntropyBonus(tau),
         }
     }
 }
 
-impl<S, P> BatchLearner<S, P::Action> for REINFORCE<P>
+impl<S, P, B> BatchLearner<S, P::Action> for REINFORCE<P, B>
 where
     P: DifferentiablePolicy<S>,
+    B: PolicyBonus<S, P>,
     P::Action: Clone,
 {
     fn handle_batch(&mut self, batch: &[Transition<S, P::Action>]) {
@@ -36,18 +57,17 @@ where
         let mut ret = 0.0;
 
         for t in batch.into_iter().rev() {
+            let s = t.from.state();
+            let bonus = self.bonus.bonus(&self.policy, s, &t.action);
+
             ret = t.reward + self.gamma * ret;
 
-            self.policy.update(
-                t.from.state(),
-                &t.action,
-                self.alpha * ret / z
-            );
+            self.policy.update(s, &t.action, self.alpha * (ret + bonus) / z);
         }
     }
 }
 
-impl<S, P: Policy<S>> Controller<S, P::Action> for REINFORCE<P> {
+impl<S, P: Policy<S>, B> Controller<S, P::Action> for REINFORCE<P, B> {
     fn sample_target(&self, rng: &mut impl Rng, s: &S) -> P::Action {
         self.policy.sample(rng, s)
     }
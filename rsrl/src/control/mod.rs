@@ -19,8 +19,12 @@ impl<S, A, T: Controller<S, A>> Controller<S, A> for Shared<T> {
 }
 
 pub mod ac;
+pub mod exploration;
 pub mod gtd;
 pub mod mc;
+pub mod options;
+pub mod pg;
+pub mod planning;
 pub mod td;
 pub mod totd;
 
@@ -0,0 +1,212 @@
+use crate::{
+    OnlineLearner,
+    control::{
+        Controller,
+        options::{
+            Execution, InitiationSet, MarkovOption, Termination,
+            greedy_option, sample_option,
+        },
+    },
+    domains::Transition,
+    fa::{
+        EnumerableStateActionFunction,
+        Parameterised, Weights, WeightsView, WeightsViewMut,
+    },
+    policies::Policy,
+    prediction::{ValuePredictor, ActionValuePredictor},
+};
+use rand::Rng;
+
+/// Intra-option variant of Q-learning.
+///
+/// Every option whose policy is consistent with the action taken is updated
+/// after each transition, not just the one under execution. The target
+/// accounts for the probability that the option continues in the next state,
+/// `U(s', o) = (1 - β(s')) Q(s', o) + β(s') max_o' Q(s', o')`. For stochastic
+/// option policies, each update is weighted by the importance sampling ratio
+/// `π_o(a | s) / π_x(a | s)`, where `x` is the option under execution; for
+/// deterministic options this reduces to the consistency check of the
+/// original algorithm.
+///
+/// # References
+/// - Sutton, R. S., Precup, D., & Singh, S. (1998). Intra-option learning
+/// about temporally abstract actions. In Proceedings of the Fifteenth
+/// International Conference on Machine Learning (pp. 556-564).
+#[derive(Parameterised)]
+pub struct IntraOptionQLearning<Q, I, P, B> {
+    #[weights] pub q_func: Q,
+    pub options: Vec<MarkovOption<I, P, B>>,
+
+    pub alpha: f64,
+    pub gamma: f64,
+    pub epsilon: f64,
+
+    execution: Execution,
+}
+
+impl<Q, I, P, B> IntraOptionQLearning<Q, I, P, B> {
+    pub fn new(
+        q_func: Q,
+        options: Vec<MarkovOption<I, P, B>>,
+        alpha: f64,
+        gamma: f64,
+        epsilon: f64,
+    ) -> Self {
+        IntraOptionQLearning {
+            q_func,
+            options,
+
+            alpha,
+            gamma,
+            epsilon,
+
+            execution: Execution::default(),
+        }
+    }
+}
+
+impl<S, Q, I, P, B> OnlineLearner<S, P::Action> for IntraOptionQLearning<Q, I, P, B>
+where
+    Q: EnumerableStateActionFunction<S>,
+    I: InitiationSet<S>,
+    P: Policy<S>,
+    B: Termination<S>,
+{
+    fn handle_transition(&mut self, t: &Transition<S, P::Action>) {
+        let s = t.from.state();
+        let a = &t.action;
+
+        let mu = self.execution.current().map_or(1.0, |x| self.options[x].policy.probability(s, a));
+        let next = if t.terminated() { None } else {
+            let ns = t.to.state();
+            let nqs = self.q_func.evaluate_all(ns);
+            let (_, nv) = greedy_option(&nqs, &self.options, ns);
+
+            Some((ns, nqs, nv))
+        };
+
+        for o in 0..self.options.len() {
+            let rho = self.options[o].policy.probability(s, a) / mu;
+
+            if rho <= 0.0 { continue; }
+
+            let qso = self.q_func.evaluate(s, &o);
+            let target = match next {
+                Some((ns, ref nqs, nv)) => {
+                    let beta = self.options[o].termination.probability(ns);
+
+                    t.reward + self.gamma * ((1.0 - beta) * nqs[o] + beta * nv)
+                },
+                None => t.reward,
+            };
+
+            self.q_func.update(s, &o, self.alpha * rho * (target - qso));
+        }
+
+        if t.terminated() { self.execution.reset(); }
+    }
+
+    fn handle_terminal(&mut self) {
+        self.execution.reset();
+
+        for o in self.options.iter_mut() {
+            o.policy.handle_terminal();
+        }
+    }
+}
+
+impl<S, Q, I, P, B> Controller<S, P::Action> for IntraOptionQLearning<Q, I, P, B>
+where
+    Q: EnumerableStateActionFunction<S>,
+    I: InitiationSet<S>,
+    P: Policy<S>,
+    B: Termination<S>,
+{
+    fn sample_target(&self, rng: &mut impl Rng, s: &S) -> P::Action {
+        let o = self.execution.step(rng, &self.options, s, |_| {
+            greedy_option(&self.q_func.evaluate_all(s), &self.options, s).0
+        });
+
+        self.options[o].policy.mpa(s)
+    }
+
+    fn sample_behaviour(&self, rng: &mut impl Rng, s: &S) -> P::Action {
+        let o = self.execution.step(rng, &self.options, s, |rng| {
+            sample_option(rng, &self.q_func.evaluate_all(s), &self.options, s, self.epsilon)
+        });
+
+        self.options[o].policy.sample(rng, s)
+    }
+}
+
+impl<S, Q, I, P, B> ValuePredictor<S> for IntraOptionQLearning<Q, I, P, B>
+where
+    Q: EnumerableStateActionFunction<S>,
+    I: InitiationSet<S>,
+{
+    fn predict_v(&self, s: &S) -> f64 {
+        greedy_option(&self.q_func.evaluate_all(s), &self.options, s).1
+    }
+}
+
+impl<S, Q, I, P, B> ActionValuePredictor<S, usize> for IntraOptionQLearning<Q, I, P, B>
+where
+    Q: EnumerableStateActionFunction<S>,
+    I: InitiationSet<S>,
+{
+    fn predict_q(&self, s: &S, o: &usize) -> f64 { self.q_func.evaluate(s, o) }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        OnlineLearner,
+        control::{Controller, options::{Everywhere, MarkovOption}},
+        domains::{Observation, Transition},
+        fa::linear::{LFA, basis::{Polynomial, Projector}, optim::SGD},
+        policies::Policy,
+        prediction::ActionValuePredictor,
+    };
+    use rand::thread_rng;
+    use super::IntraOptionQLearning;
+
+    struct Fixed(usize);
+
+    impl Policy<Vec<f64>> for Fixed {
+        type Action = usize;
+
+        fn mpa(&self, _: &Vec<f64>) -> usize { self.0 }
+
+        fn probability(&self, _: &Vec<f64>, a: &usize) -> f64 {
+            if *a == self.0 { 1.0 } else { 0.0 }
+        }
+    }
+
+    fn no_termination(_: &Vec<f64>) -> f64 { 0.0 }
+
+    #[test]
+    fn test_consistent_options() {
+        let q = LFA::vector(Polynomial::new(1, 1).with_constant(), SGD(1.0), 3);
+        let options = vec![
+            MarkovOption::new(Everywhere, Fixed(0), no_termination),
+            MarkovOption::new(Everywhere, Fixed(1), no_termination),
+            MarkovOption::new(Everywhere, Fixed(0), no_termination),
+        ];
+        let mut agent = IntraOptionQLearning::new(q, options, 1.0, 0.9, 0.0);
+
+        let s = vec![0.0];
+        let a = agent.sample_behaviour(&mut thread_rng(), &s);
+
+        agent.handle_transition(&Transition {
+            from: Observation::Full(s.clone()),
+            action: a,
+            reward: 1.0,
+            to: Observation::Terminal(vec![1.0]),
+        });
+
+        assert_eq!(a, 0);
+        assert!((agent.predict_q(&s, &0) - 1.0).abs() < 1e-7);
+        assert!(agent.predict_q(&s, &1).abs() < 1e-7);
+        assert!((agent.predict_q(&s, &2) - 1.0).abs() < 1e-7);
+    }
+}
@@ -0,0 +1,427 @@
+//! Temporal abstraction via options.
+//!
+//! An option, `⟨I, π, β⟩`, is a closed-loop policy that may be initiated in
+//! any state of `I`, selects actions according to `π` and terminates in each
+//! subsequent state with probability `β(s)`. The agents in this module learn
+//! values over a finite set of options, and select amongst them, in place of
+//! primitive actions.
+//!
+//! # References
+//! - Sutton, R. S., Precup, D., & Singh, S. (1999). Between MDPs and
+//! semi-MDPs: A framework for temporal abstraction in reinforcement learning.
+//! Artificial Intelligence, 112(1-2), 181-211.
+use crate::fa::{
+    DifferentiableStateFunction, StateFunction,
+    Parameterised, Weights, WeightsView, WeightsViewMut,
+};
+use ndarray::Array2;
+use rand::Rng;
+use std::cell::Cell;
+
+import_all!(smdp_q_learning);
+import_all!(intra_option_q_learning);
+import_all!(option_critic);
+
+/// Trait for the set of states in which an option may be initiated.
+pub trait InitiationSet<S: ?Sized> {
+    /// Return true if the option may be initiated in `state`.
+    fn can_initiate(&self, state: &S) -> bool;
+}
+
+impl<S: ?Sized, F: Fn(&S) -> bool> InitiationSet<S> for F {
+    fn can_initiate(&self, state: &S) -> bool { self(state) }
+}
+
+/// Initiation set containing every state.
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Everywhere;
+
+impl<S: ?Sized> InitiationSet<S> for Everywhere {
+    fn can_initiate(&self, _: &S) -> bool { true }
+}
+
+/// Trait for the termination condition of an option, `β(s)`.
+pub trait Termination<S: ?Sized> {
+    /// Return the probability of terminating in `state`.
+    fn probability(&self, state: &S) -> f64;
+
+    /// Sample whether or not to terminate in `state`.
+    fn terminates<R: Rng + ?Sized>(&self, rng: &mut R, state: &S) -> bool {
+        rng.gen_bool(self.probability(state).max(0.0).min(1.0))
+    }
+}
+
+impl<S: ?Sized, F: Fn(&S) -> f64> Termination<S> for F {
+    fn probability(&self, state: &S) -> f64 { self(state) }
+}
+
+/// Trait for termination conditions with a representation that is
+/// differentiable wrt its parameters.
+pub trait DifferentiableTermination<S: ?Sized>: Termination<S> + Parameterised {
+    /// Compute the gradient of `β(s)` wrt the weights.
+    fn grad(&self, state: &S) -> Array2<f64>;
+
+    /// Update the weights in the direction of an error for a given state.
+    fn update(&mut self, state: &S, error: f64);
+}
+
+/// Termination condition given by the logistic function of a state function,
+/// `β(s) = σ(f(s))`.
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, Parameterised)]
+pub struct SigmoidTermination<F> {
+    #[weights] pub fa: F,
+}
+
+impl<F> SigmoidTermination<F> {
+    pub fn new(fa: F) -> Self { SigmoidTermination { fa } }
+}
+
+impl<S, F: StateFunction<S, Output = f64>> Termination<S> for SigmoidTermination<F> {
+    fn probability(&self, state: &S) -> f64 { 1.0 / (1.0 + (-self.fa.evaluate(state)).exp()) }
+}
+
+impl<S, F> DifferentiableTermination<S> for SigmoidTermination<F>
+where
+    F: DifferentiableStateFunction<S, Output = f64>,
+{
+    fn grad(&self, state: &S) -> Array2<f64> {
+        let beta = self.probability(state);
+        let grad: Array2<f64> = self.fa.grad(state).into();
+
+        grad * (beta * (1.0 - beta))
+    }
+
+    fn update(&mut self, state: &S, error: f64) {
+        let beta = self.probability(state);
+
+        self.fa.update(state, beta * (1.0 - beta) * error);
+    }
+}
+
+/// A Markov option, `⟨I, π, β⟩`.
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug)]
+pub struct MarkovOption<I, P, B> {
+    pub initiation: I,
+    pub policy: P,
+    pub termination: B,
+}
+
+impl<I, P, B> MarkovOption<I, P, B> {
+    pub fn new(initiation: I, policy: P, termination: B) -> Self {
+        MarkovOption {
+            initiation,
+            policy,
+            termination,
+        }
+    }
+}
+
+/// Bookkeeping for the option currently under execution.
+///
+/// Options are (re)selected lazily as actions are sampled, for which agents
+/// only have shared access to themselves; the state is thus held in `Cell`s.
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, Default)]
+pub struct Execution {
+    current: Cell<Option<usize>>,
+    initiated: Cell<bool>,
+}
+
+impl Execution {
+    /// Return the index of the option under execution, if any.
+    pub fn current(&self) -> Option<usize> { self.current.get() }
+
+    /// Return the option to follow in `state`.
+    ///
+    /// The current option terminates with probability `β(s)`, in which case
+    /// (or if there is no current option) a new one is initiated with
+    /// `select`.
+    pub fn step<S, I, P, B, R>(
+        &self,
+        rng: &mut R,
+        options: &[MarkovOption<I, P, B>],
+        state: &S,
+        select: impl FnOnce(&mut R) -> usize,
+    ) -> usize
+    where
+        B: Termination<S>,
+        R: Rng + ?Sized,
+    {
+        match self.current.get() {
+            Some(o) if !options[o].termination.terminates(rng, state) => o,
+            _ => {
+                let o = select(rng);
+
+                self.current.set(Some(o));
+                self.initiated.set(true);
+
+                o
+            },
+        }
+    }
+
+    /// Return true if an option was initiated since the last call, resetting
+    /// the flag.
+    pub fn take_initiated(&self) -> bool { self.initiated.replace(false) }
+
+    /// Clear the option under execution, e.g. at the end of an episode.
+    pub fn reset(&self) {
+        self.current.set(None);
+        self.initiated.set(false);
+    }
+}
+
+/// Return the greedy option, and its value, amongst those that may be
+/// initiated in `state`.
+pub fn greedy_option<S, I, P, B>(
+    qs: &[f64],
+    options: &[MarkovOption<I, P, B>],
+    state: &S,
+) -> (usize, f64)
+where
+    I: InitiationSet<S>,
+{
+    qs.iter().enumerate()
+        .filter(|&(o, _)| options[o].initiation.can_initiate(state))
+        .fold(None, |acc: Option<(usize, f64)>, (o, &q)| match acc {
+            Some((_, best)) if best >= q => acc,
+            _ => Some((o, q)),
+        })
+        .expect("No option can be initiated in the given state.")
+}
+
+/// Sample an option ε-greedily from amongst those that may be initiated in
+/// `state`.
+pub fn sample_option<S, I, P, B, R>(
+    rng: &mut R,
+    qs: &[f64],
+    options: &[MarkovOption<I, P, B>],
+    state: &S,
+    epsilon: f64,
+) -> usize
+where
+    I: InitiationSet<S>,
+    R: Rng + ?Sized,
+{
+    if rng.gen_bool(epsilon) {
+        let available: Vec<usize> = (0..options.len())
+            .filter(|&o| options[o].initiation.can_initiate(state))
+            .collect();
+
+        if available.is_empty() {
+            panic!("No option can be initiated in the given state.");
+        }
+
+        available[rng.gen_range(0, available.len())]
+    } else {
+        greedy_option(qs, options, state).0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        OnlineLearner,
+        control::Controller,
+        domains::{ActionMask, Domain, FourRooms, FOUR_ROOMS_HALLWAYS},
+        fa::{
+            EnumerableStateActionFunction, StateActionFunction,
+            linear::{LFA, basis::{Polynomial, Projector}, optim::SGD},
+        },
+        policies::{Policy, Random},
+        prediction::ActionValuePredictor,
+    };
+    use rand::thread_rng;
+    use std::rc::Rc;
+    use super::*;
+
+    fn no_termination(_: &Vec<f64>) -> f64 { 0.0 }
+
+    fn always(_: &Vec<f64>) -> bool { true }
+
+    fn positive(s: &Vec<f64>) -> bool { s[0] > 0.0 }
+
+    #[test]
+    fn test_greedy_option() {
+        let options = vec![
+            MarkovOption::new(always as fn(&Vec<f64>) -> bool, Random::new(2), no_termination),
+            MarkovOption::new(positive, Random::new(2), no_termination),
+        ];
+
+        assert_eq!(greedy_option(&[0.0, 1.0], &options, &vec![1.0]), (1, 1.0));
+        assert_eq!(greedy_option(&[0.0, 1.0], &options, &vec![-1.0]), (0, 0.0));
+        assert_eq!(sample_option(&mut thread_rng(), &[0.0, 1.0], &options, &vec![-1.0], 1.0), 0);
+    }
+
+    #[test]
+    fn test_execution() {
+        let options = vec![
+            MarkovOption::new(Everywhere, Random::new(2), no_termination as fn(&Vec<f64>) -> f64),
+            MarkovOption::new(Everywhere, Random::new(2), |_: &Vec<f64>| 1.0),
+        ];
+        let mut rng = thread_rng();
+        let exec = Execution::default();
+
+        assert_eq!(exec.step(&mut rng, &options, &vec![0.0], |_| 0), 0);
+        assert!(exec.take_initiated());
+        assert_eq!(exec.step(&mut rng, &options, &vec![0.0], |_| 1), 0);
+        assert!(!exec.take_initiated());
+
+        exec.reset();
+
+        assert_eq!(exec.current(), None);
+    }
+
+    #[test]
+    fn test_sigmoid_termination() {
+        let fa = LFA::scalar(Polynomial::new(1, 1).with_constant(), SGD(1.0));
+        let mut beta = SigmoidTermination::new(fa);
+        let s = vec![0.0];
+
+        assert!((beta.probability(&s) - 0.5).abs() < 1e-7);
+        assert!(beta.grad(&s).iter().any(|g| (g - 0.25).abs() < 1e-7));
+
+        beta.update(&s, -1.0);
+
+        assert!(beta.probability(&s) < 0.5);
+    }
+
+    // Option-values over the cells of a four-rooms grid.
+    struct Table(Vec<Vec<f64>>);
+
+    impl StateActionFunction<[usize; 2], usize> for Table {
+        type Output = f64;
+
+        fn evaluate(&self, s: &[usize; 2], o: &usize) -> f64 { self.0[13 * s[0] + s[1]][*o] }
+
+        fn update(&mut self, s: &[usize; 2], o: &usize, error: f64) {
+            self.0[13 * s[0] + s[1]][*o] += error;
+        }
+    }
+
+    impl EnumerableStateActionFunction<[usize; 2]> for Table {
+        fn n_actions(&self) -> usize { self.0[0].len() }
+
+        fn evaluate_all(&self, s: &[usize; 2]) -> Vec<f64> { self.0[13 * s[0] + s[1]].clone() }
+
+        fn update_all(&mut self, s: &[usize; 2], errors: Vec<f64>) {
+            for (q, e) in self.0[13 * s[0] + s[1]].iter_mut().zip(errors.into_iter()) {
+                *q += e;
+            }
+        }
+    }
+
+    // Hallway option that may be initiated in a room, or the hallway through
+    // which it is entered, and heads for another of the room's hallways.
+    struct Hallway {
+        domain: Rc<FourRooms>,
+        room: usize,
+        entry: [usize; 2],
+        target: [usize; 2],
+    }
+
+    impl InitiationSet<[usize; 2]> for Hallway {
+        fn can_initiate(&self, s: &[usize; 2]) -> bool {
+            self.domain.room(*s) == Some(self.room) || *s == self.entry
+        }
+    }
+
+    impl Policy<[usize; 2]> for Hallway {
+        type Action = usize;
+
+        // Take the first legal action that moves closer to the target.
+        fn mpa(&self, s: &[usize; 2]) -> usize {
+            let legal = self.domain.legal_actions(s);
+            let closer = [
+                self.target[1] > s[1],
+                self.target[0] > s[0],
+                self.target[1] < s[1],
+                self.target[0] < s[0],
+            ];
+
+            (0..4).find(|&a| legal[a] && closer[a]).unwrap_or(0)
+        }
+
+        fn probability(&self, s: &[usize; 2], a: &usize) -> f64 {
+            if *a == self.mpa(s) { 1.0 } else { 0.0 }
+        }
+    }
+
+    impl Termination<[usize; 2]> for Hallway {
+        fn probability(&self, s: &[usize; 2]) -> f64 {
+            if self.can_initiate(s) { 0.0 } else { 1.0 }
+        }
+    }
+
+    fn hallway_options() -> Vec<MarkovOption<Hallway, Hallway, Hallway>> {
+        let domain = Rc::new(FourRooms::default());
+        let [h01, h02, h13, h23] = FOUR_ROOMS_HALLWAYS;
+        let hallway = |room, entry, target| {
+            let h = || Hallway { domain: domain.clone(), room, entry, target, };
+
+            MarkovOption::new(h(), h(), h())
+        };
+
+        vec![
+            hallway(0, h02, h01), hallway(0, h01, h02),
+            hallway(1, h13, h01), hallway(1, h01, h13),
+            hallway(2, h23, h02), hallway(2, h02, h23),
+            hallway(3, h23, h13), hallway(3, h13, h23),
+        ]
+    }
+
+    // Run episodes from the top-left corner to the hallway between the upper
+    // and lower right rooms, as in Sutton, Precup & Singh (1999).
+    fn run_four_rooms<A>(agent: &mut A, n_episodes: usize)
+    where
+        A: OnlineLearner<[usize; 2], usize> + Controller<[usize; 2], usize>,
+    {
+        let mut rng = thread_rng();
+
+        for _ in 0..n_episodes {
+            let mut domain = FourRooms::new([1, 1], FOUR_ROOMS_HALLWAYS[2]);
+
+            for _ in 0..1000 {
+                let a = agent.sample_behaviour(&mut rng, domain.emit().state());
+                let t = domain.step(a);
+
+                agent.handle_transition(&t);
+
+                if t.terminated() { break; }
+            }
+
+            agent.handle_terminal();
+        }
+    }
+
+    // The shortest route passes through the upper hallway in 14 steps, so
+    // the value of heading for it is the reward discounted over 13 steps.
+    fn check_four_rooms<A: ActionValuePredictor<[usize; 2], usize>>(agent: &A) {
+        let q_upper = agent.predict_q(&[1, 1], &0);
+        let q_lower = agent.predict_q(&[1, 1], &1);
+
+        assert!((q_upper - 0.9f64.powi(13)).abs() < 1e-7);
+        assert!(q_upper > q_lower);
+    }
+
+    #[test]
+    fn test_four_rooms_smdp() {
+        let q = Table(vec![vec![0.0; 8]; 169]);
+        let mut agent = SMDPQLearning::new(q, hallway_options(), 1.0, 0.9, 1.0);
+
+        run_four_rooms(&mut agent, 200);
+        check_four_rooms(&agent);
+    }
+
+    #[test]
+    fn test_four_rooms_intra_option() {
+        let q = Table(vec![vec![0.0; 8]; 169]);
+        let mut agent = IntraOptionQLearning::new(q, hallway_options(), 1.0, 0.9, 1.0);
+
+        run_four_rooms(&mut agent, 200);
+        check_four_rooms(&agent);
+    }
+}
@@ -0,0 +1,235 @@
+use crate::{
+    OnlineLearner,
+    control::{
+        Controller,
+        options::{
+            DifferentiableTermination, Execution, InitiationSet, MarkovOption,
+            greedy_option, sample_option,
+        },
+    },
+    domains::Transition,
+    fa::{
+        EnumerableStateActionFunction,
+        Parameterised, Weights, WeightsView, WeightsViewMut,
+    },
+    policies::{DifferentiablePolicy, Policy},
+    prediction::{ValuePredictor, ActionValuePredictor},
+};
+use rand::Rng;
+
+/// Option-critic architecture.
+///
+/// The intra-option policies and termination functions are learnt jointly
+/// with the option-values, `Q(s, o)`, which are themselves learnt with the
+/// intra-option Q-learning target. Each option policy follows the gradient of
+/// the TD-error of the option under execution, and each termination function
+/// descends `∇β(s') (Q(s', o) - V(s') + ξ)`, where `V(s') = max_o Q(s', o)`
+/// and `ξ` is a regulariser favouring longer options.
+///
+/// # References
+/// - Bacon, P. L., Harb, J., & Precup, D. (2017). The option-critic
+/// architecture. In Proceedings of the Thirty-First AAAI Conference on
+/// Artificial Intelligence (pp. 1726-1734).
+#[derive(Parameterised)]
+pub struct OptionCritic<Q, I, P, B> {
+    #[weights] pub q_func: Q,
+    pub options: Vec<MarkovOption<I, P, B>>,
+
+    pub alpha: f64,
+    pub alpha_policy: f64,
+    pub alpha_termination: f64,
+    pub gamma: f64,
+    pub epsilon: f64,
+    pub termination_reg: f64,
+
+    execution: Execution,
+}
+
+impl<Q, I, P, B> OptionCritic<Q, I, P, B> {
+    pub fn new(
+        q_func: Q,
+        options: Vec<MarkovOption<I, P, B>>,
+        alpha: f64,
+        alpha_policy: f64,
+        alpha_termination: f64,
+        gamma: f64,
+        epsilon: f64,
+    ) -> Self {
+        OptionCritic {
+            q_func,
+            options,
+
+            alpha,
+            alpha_policy,
+            alpha_termination,
+            gamma,
+            epsilon,
+            termination_reg: 0.0,
+
+            execution: Execution::default(),
+        }
+    }
+
+    /// Add a regulariser, `xi`, to the advantage used to update the
+    /// termination functions.
+    pub fn with_termination_reg(mut self, xi: f64) -> Self {
+        self.termination_reg = xi;
+        self
+    }
+}
+
+impl<S, Q, I, P, B> OnlineLearner<S, P::Action> for OptionCritic<Q, I, P, B>
+where
+    Q: EnumerableStateActionFunction<S>,
+    I: InitiationSet<S>,
+    P: DifferentiablePolicy<S>,
+    B: DifferentiableTermination<S>,
+{
+    fn handle_transition(&mut self, t: &Transition<S, P::Action>) {
+        // Without an option under execution, e.g. if the action was not
+        // sampled from the agent, there is no option to credit.
+        let o = match self.execution.current() {
+            Some(o) => o,
+            None => return,
+        };
+
+        let s = t.from.state();
+        let qso = self.q_func.evaluate(s, &o);
+
+        let td_error = if t.terminated() {
+            t.reward - qso
+        } else {
+            let ns = t.to.state();
+            let nqs = self.q_func.evaluate_all(ns);
+            let (_, nv) = greedy_option(&nqs, &self.options, ns);
+
+            let beta = self.options[o].termination.probability(ns);
+            let advantage = nqs[o] - nv + self.termination_reg;
+
+            self.options[o].termination.update(ns, -self.alpha_termination * advantage);
+
+            t.reward + self.gamma * ((1.0 - beta) * nqs[o] + beta * nv) - qso
+        };
+
+        self.q_func.update(s, &o, self.alpha * td_error);
+        self.options[o].policy.update(s, &t.action, self.alpha_policy * td_error);
+
+        if t.terminated() { self.execution.reset(); }
+    }
+
+    fn handle_terminal(&mut self) {
+        self.execution.reset();
+
+        for o in self.options.iter_mut() {
+            o.policy.handle_terminal();
+        }
+    }
+}
+
+impl<S, Q, I, P, B> Controller<S, P::Action> for OptionCritic<Q, I, P, B>
+where
+    Q: EnumerableStateActionFunction<S>,
+    I: InitiationSet<S>,
+    P: Policy<S>,
+    B: DifferentiableTermination<S>,
+{
+    fn sample_target(&self, rng: &mut impl Rng, s: &S) -> P::Action {
+        let o = self.execution.step(rng, &self.options, s, |_| {
+            greedy_option(&self.q_func.evaluate_all(s), &self.options, s).0
+        });
+
+        self.options[o].policy.mpa(s)
+    }
+
+    fn sample_behaviour(&self, rng: &mut impl Rng, s: &S) -> P::Action {
+        let o = self.execution.step(rng, &self.options, s, |rng| {
+            sample_option(rng, &self.q_func.evaluate_all(s), &self.options, s, self.epsilon)
+        });
+
+        self.options[o].policy.sample(rng, s)
+    }
+}
+
+impl<S, Q, I, P, B> ValuePredictor<S> for OptionCritic<Q, I, P, B>
+where
+    Q: EnumerableStateActionFunction<S>,
+    I: InitiationSet<S>,
+{
+    fn predict_v(&self, s: &S) -> f64 {
+        greedy_option(&self.q_func.evaluate_all(s), &self.options, s).1
+    }
+}
+
+impl<S, Q, I, P, B> ActionValuePredictor<S, usize> for OptionCritic<Q, I, P, B>
+where
+    Q: EnumerableStateActionFunction<S>,
+    I: InitiationSet<S>,
+{
+    fn predict_q(&self, s: &S, o: &usize) -> f64 { self.q_func.evaluate(s, o) }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        OnlineLearner,
+        control::{Controller, options::{Everywhere, MarkovOption, SigmoidTermination, Termination}},
+        domains::{Observation, Transition},
+        fa::linear::{LFA, basis::{Polynomial, Projector}, optim::SGD},
+        policies::{Gibbs, Policy},
+        prediction::ActionValuePredictor,
+    };
+    use rand::thread_rng;
+    use super::OptionCritic;
+
+    #[test]
+    fn test_update() {
+        let basis = || Polynomial::new(1, 1).with_constant();
+        let q = LFA::vector(basis(), SGD(1.0), 1);
+        let options = vec![MarkovOption::new(
+            Everywhere,
+            Gibbs::standard(LFA::vector(basis(), SGD(1.0), 2)),
+            SigmoidTermination::new(LFA::scalar(basis(), SGD(1.0))),
+        )];
+        let mut agent = OptionCritic::new(q, options, 0.5, 1.0, 1.0, 0.9, 0.0)
+            .with_termination_reg(0.5);
+
+        let s = vec![0.0];
+        let a = agent.sample_behaviour(&mut thread_rng(), &s);
+
+        agent.handle_transition(&Transition {
+            from: Observation::Full(s.clone()),
+            action: a,
+            reward: 1.0,
+            to: Observation::Full(s.clone()),
+        });
+
+        let o = &agent.options[0];
+
+        assert!(agent.predict_q(&s, &0) > 0.0);
+        assert!(o.policy.probability(&s, &a) > 0.5);
+        assert!(o.termination.probability(&s) < 0.5);
+    }
+
+    #[test]
+    fn test_no_option() {
+        let basis = || Polynomial::new(1, 1).with_constant();
+        let q = LFA::vector(basis(), SGD(1.0), 1);
+        let options = vec![MarkovOption::new(
+            Everywhere,
+            Gibbs::standard(LFA::vector(basis(), SGD(1.0), 2)),
+            SigmoidTermination::new(LFA::scalar(basis(), SGD(1.0))),
+        )];
+        let mut agent = OptionCritic::new(q, options, 0.5, 1.0, 1.0, 0.9, 0.0);
+
+        let s = vec![0.0];
+
+        agent.handle_transition(&Transition {
+            from: Observation::Full(s.clone()),
+            action: 0,
+            reward: 1.0,
+            to: Observation::Full(s.clone()),
+        });
+
+        assert_eq!(agent.predict_q(&s, &0), 0.0);
+    }
+}
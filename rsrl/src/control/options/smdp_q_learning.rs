@@ -0,0 +1,222 @@
+use crate::{
+    OnlineLearner,
+    control::{
+        Controller,
+        options::{
+            Execution, InitiationSet, MarkovOption, Termination,
+            greedy_option, sample_option,
+        },
+    },
+    domains::Transition,
+    fa::{
+        EnumerableStateActionFunction,
+        Parameterised, Weights, WeightsView, WeightsViewMut,
+    },
+    policies::Policy,
+    prediction::{ValuePredictor, ActionValuePredictor},
+};
+use rand::Rng;
+
+// Discounted return accumulated since an option was initiated in `state`.
+struct Pending<S> {
+    state: S,
+    option: usize,
+
+    ret: f64,
+    discount: f64,
+}
+
+/// Semi-Markov decision process variant of Q-learning over options.
+///
+/// Option-values are updated only when an option terminates, using the
+/// discounted return accumulated over its execution and the greedy value of
+/// the state in which it terminated.
+///
+/// # References
+/// - Bradtke, S. J., & Duff, M. O. (1995). Reinforcement learning methods for
+/// continuous-time Markov decision problems. In Advances in Neural Information
+/// Processing Systems (pp. 393-400).
+/// - Sutton, R. S., Precup, D., & Singh, S. (1999). Between MDPs and
+/// semi-MDPs: A framework for temporal abstraction in reinforcement learning.
+/// Artificial Intelligence, 112(1-2), 181-211.
+#[derive(Parameterised)]
+pub struct SMDPQLearning<S, Q, I, P, B> {
+    #[weights] pub q_func: Q,
+    pub options: Vec<MarkovOption<I, P, B>>,
+
+    pub alpha: f64,
+    pub gamma: f64,
+    pub epsilon: f64,
+
+    execution: Execution,
+    pending: Option<Pending<S>>,
+}
+
+impl<S, Q, I, P, B> SMDPQLearning<S, Q, I, P, B> {
+    pub fn new(
+        q_func: Q,
+        options: Vec<MarkovOption<I, P, B>>,
+        alpha: f64,
+        gamma: f64,
+        epsilon: f64,
+    ) -> Self {
+        SMDPQLearning {
+            q_func,
+            options,
+
+            alpha,
+            gamma,
+            epsilon,
+
+            execution: Execution::default(),
+            pending: None,
+        }
+    }
+
+    fn backup(&mut self, pending: Pending<S>, nv: f64) where Q: EnumerableStateActionFunction<S> {
+        let q = self.q_func.evaluate(&pending.state, &pending.option);
+        let target = pending.ret + pending.discount * nv;
+
+        self.q_func.update(&pending.state, &pending.option, self.alpha * (target - q));
+    }
+}
+
+impl<S, Q, I, P, B> OnlineLearner<S, P::Action> for SMDPQLearning<S, Q, I, P, B>
+where
+    S: Clone,
+    Q: EnumerableStateActionFunction<S>,
+    I: InitiationSet<S>,
+    P: Policy<S>,
+{
+    fn handle_transition(&mut self, t: &Transition<S, P::Action>) {
+        let s = t.from.state();
+
+        if self.execution.take_initiated() {
+            if let Some(pending) = self.pending.take() {
+                let (_, nv) = greedy_option(&self.q_func.evaluate_all(s), &self.options, s);
+
+                self.backup(pending, nv);
+            }
+
+            self.pending = self.execution.current().map(|option| Pending {
+                state: s.clone(),
+                option,
+
+                ret: 0.0,
+                discount: 1.0,
+            });
+        }
+
+        if let Some(ref mut pending) = self.pending {
+            pending.ret += pending.discount * t.reward;
+            pending.discount *= self.gamma;
+        }
+
+        if t.terminated() {
+            if let Some(pending) = self.pending.take() {
+                self.backup(pending, 0.0);
+            }
+
+            self.execution.reset();
+        }
+    }
+
+    fn handle_terminal(&mut self) {
+        self.pending = None;
+        self.execution.reset();
+
+        for o in self.options.iter_mut() {
+            o.policy.handle_terminal();
+        }
+    }
+}
+
+impl<S, Q, I, P, B> Controller<S, P::Action> for SMDPQLearning<S, Q, I, P, B>
+where
+    Q: EnumerableStateActionFunction<S>,
+    I: InitiationSet<S>,
+    P: Policy<S>,
+    B: Termination<S>,
+{
+    fn sample_target(&self, rng: &mut impl Rng, s: &S) -> P::Action {
+        let o = self.execution.step(rng, &self.options, s, |_| {
+            greedy_option(&self.q_func.evaluate_all(s), &self.options, s).0
+        });
+
+        self.options[o].policy.mpa(s)
+    }
+
+    fn sample_behaviour(&self, rng: &mut impl Rng, s: &S) -> P::Action {
+        let o = self.execution.step(rng, &self.options, s, |rng| {
+            sample_option(rng, &self.q_func.evaluate_all(s), &self.options, s, self.epsilon)
+        });
+
+        self.options[o].policy.sample(rng, s)
+    }
+}
+
+impl<S, Q, I, P, B> ValuePredictor<S> for SMDPQLearning<S, Q, I, P, B>
+where
+    Q: EnumerableStateActionFunction<S>,
+    I: InitiationSet<S>,
+{
+    fn predict_v(&self, s: &S) -> f64 {
+        greedy_option(&self.q_func.evaluate_all(s), &self.options, s).1
+    }
+}
+
+impl<S, Q, I, P, B> ActionValuePredictor<S, usize> for SMDPQLearning<S, Q, I, P, B>
+where
+    Q: EnumerableStateActionFunction<S>,
+    I: InitiationSet<S>,
+{
+    fn predict_q(&self, s: &S, o: &usize) -> f64 { self.q_func.evaluate(s, o) }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        OnlineLearner,
+        control::{Controller, options::{Everywhere, MarkovOption}},
+        domains::{Observation, Transition},
+        fa::linear::{LFA, basis::{Polynomial, Projector}, optim::SGD},
+        policies::Random,
+        prediction::ActionValuePredictor,
+    };
+    use rand::thread_rng;
+    use super::SMDPQLearning;
+
+    fn no_termination(_: &Vec<f64>) -> f64 { 0.0 }
+
+    #[test]
+    fn test_backup() {
+        let q = LFA::vector(Polynomial::new(1, 1).with_constant(), SGD(1.0), 1);
+        let options = vec![MarkovOption::new(Everywhere, Random::new(2), no_termination)];
+        let mut agent = SMDPQLearning::new(q, options, 1.0, 0.9, 0.0);
+        let mut rng = thread_rng();
+
+        let s0 = vec![0.0];
+        let a0 = agent.sample_behaviour(&mut rng, &s0);
+
+        agent.handle_transition(&Transition {
+            from: Observation::Full(s0.clone()),
+            action: a0,
+            reward: 1.0,
+            to: Observation::Full(vec![1.0]),
+        });
+
+        // The option has not yet terminated:
+        assert_eq!(agent.predict_q(&s0, &0), 0.0);
+
+        let a1 = agent.sample_behaviour(&mut rng, &vec![1.0]);
+
+        agent.handle_transition(&Transition {
+            from: Observation::Full(vec![1.0]),
+            action: a1,
+            reward: 1.0,
+            to: Observation::Terminal(vec![2.0]),
+        });
+
+        assert!((agent.predict_q(&s0, &0) - 1.9).abs() < 1e-7);
+    }
+}
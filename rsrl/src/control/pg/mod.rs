@@ -0,0 +1,161 @@
+//! Batch policy gradient agents.
+use crate::{domains::Transition, prediction::ValuePredictor};
+use ndarray::Array1;
+
+import_all!(ppo);
+import_all!(trpo);
+
+/// Compute generalised advantage estimates, GAE(λ), for a batch of sequential
+/// transitions.
+///
+/// The batch is treated as a single trajectory: episodes may only end within
+/// it at terminal transitions, at which the recursion is reset. If the final
+/// transition is non-terminal, the episode is taken to be truncated and
+/// bootstraps from the value of its successor state. Episodes truncated part
+/// way through should thus be passed as separate batches.
+///
+/// # References
+/// - Schulman, J., Moritz, P., Levine, S., Jordan, M., Abbeel, P. (2016).
+/// High-dimensional continuous control using generalized advantage
+/// estimation. In Proceedings of the International Conference on Learning
+/// Representations.
+pub fn gae<S, A, V>(batch: &[Transition<S, A>], v_func: &V, gamma: f64, lambda: f64) -> Vec<f64>
+where
+    V: ValuePredictor<S>,
+{
+    let mut advantages = vec![0.0; batch.len()];
+    let mut acc = 0.0;
+
+    for (i, t) in batch.iter().enumerate().rev() {
+        let v = v_func.predict_v(t.from.state());
+
+        let td_error = if t.terminated() {
+            acc = 0.0;
+
+            t.reward - v
+        } else {
+            t.reward + gamma * v_func.predict_v(t.to.state()) - v
+        };
+
+        acc = td_error + gamma * lambda * acc;
+        advantages[i] = acc;
+    }
+
+    advantages
+}
+
+/// Approximately solve `A x = b` for symmetric positive definite `A`, given
+/// only the matrix-vector product `x ↦ A x`, using `n_iters` iterations of the
+/// conjugate gradient method.
+pub fn conjugate_gradient(
+    a_dot: impl Fn(&Array1<f64>) -> Array1<f64>,
+    b: &Array1<f64>,
+    n_iters: usize,
+    tol: f64,
+) -> Array1<f64>
+{
+    let mut x = Array1::zeros(b.len());
+    let mut r = b.clone();
+    let mut p = b.clone();
+    let mut r_norm = r.dot(&r);
+
+    for _ in 0..n_iters {
+        if r_norm < tol {
+            break;
+        }
+
+        let ap = a_dot(&p);
+        let step = r_norm / p.dot(&ap);
+
+        x.scaled_add(step, &p);
+        r.scaled_add(-step, &ap);
+
+        let r_norm_new = r.dot(&r);
+
+        p = &r + &(r_norm_new / r_norm * p);
+        r_norm = r_norm_new;
+    }
+
+    x
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{domains::{Observation, Transition}, prediction::ValuePredictor};
+    use super::{conjugate_gradient, gae};
+
+    struct MockV;
+
+    impl ValuePredictor<f64> for MockV {
+        fn predict_v(&self, s: &f64) -> f64 { *s }
+    }
+
+    fn transition(from: f64, reward: f64, to: Observation<f64>) -> Transition<f64, ()> {
+        Transition {
+            from: Observation::Full(from),
+            action: (),
+            reward,
+            to,
+        }
+    }
+
+    #[test]
+    fn test_td_errors() {
+        let batch = vec![
+            transition(1.0, 1.0, Observation::Full(2.0)),
+            transition(2.0, 0.0, Observation::Terminal(0.0)),
+        ];
+
+        // With λ = 0 the advantages reduce to one-step TD errors:
+        assert_eq!(gae(&batch, &MockV, 0.5, 0.0), vec![1.0, -2.0]);
+    }
+
+    #[test]
+    fn test_monte_carlo() {
+        let batch = vec![
+            transition(1.0, 1.0, Observation::Full(2.0)),
+            transition(2.0, 1.0, Observation::Terminal(0.0)),
+            transition(3.0, 5.0, Observation::Full(4.0)),
+        ];
+
+        // With λ = 1 the advantages are returns minus the baseline:
+        let advantages = gae(&batch, &MockV, 1.0, 1.0);
+
+        assert_eq!(advantages[0], 2.0 - 1.0);
+        assert_eq!(advantages[1], 1.0 - 2.0);
+        assert_eq!(advantages[2], 5.0 + 4.0 - 3.0);
+    }
+
+    #[test]
+    fn test_truncation() {
+        let batch = vec![
+            transition(1.0, 1.0, Observation::Full(2.0)),
+            transition(2.0, 1.0, Observation::Full(3.0)),
+        ];
+
+        // The episode was truncated, so it bootstraps from v(3):
+        let advantages = gae(&batch, &MockV, 1.0, 1.0);
+
+        assert_eq!(advantages[0], 1.0 + 1.0 + 3.0 - 1.0);
+        assert_eq!(advantages[1], 1.0 + 3.0 - 2.0);
+
+        // Episodes that revisit their initial state are not split:
+        let batch = vec![
+            transition(1.0, 1.0, Observation::Full(1.0)),
+            transition(1.0, 1.0, Observation::Full(1.0)),
+        ];
+
+        assert_eq!(gae(&batch, &MockV, 1.0, 1.0)[0], 1.0 + 1.0 + 1.0 - 1.0);
+    }
+
+    #[test]
+    fn test_conjugate_gradient() {
+        let a = array![[4.0, 1.0], [1.0, 3.0]];
+        let b = array![1.0, 2.0];
+
+        let x = conjugate_gradient(|v| a.dot(v), &b, 10, 1e-12);
+
+        assert!((x[0] - 1.0 / 11.0).abs() < 1e-7);
+        assert!((x[1] - 7.0 / 11.0).abs() < 1e-7);
+    }
+}
@@ -0,0 +1,190 @@
+use crate::{
+    BatchLearner, OnlineLearner,
+    control::Controller,
+    domains::Transition,
+    fa::{Weights, WeightsView, WeightsViewMut, Parameterised},
+    policies::{Policy, DifferentiablePolicy},
+    prediction::ValuePredictor,
+};
+use ndarray::Array2;
+use rand::{Rng, seq::SliceRandom, thread_rng};
+use super::gae;
+
+const MIN_PROB: f64 = 1e-10;
+
+/// Proximal policy optimisation with a clipped surrogate objective.
+///
+/// Each batch is used for `n_epochs` passes of minibatch updates, following the
+/// gradient of the clipped likelihood-ratio objective with GAE(λ) advantages
+/// computed from the critic. The gradient of each minibatch is evaluated at
+/// fixed weights and applied in a single step. If `max_kl` is set, the epochs
+/// are stopped early once the estimated KL divergence from the pre-update
+/// policy exceeds it. The critic is trained on the batch after the policy
+/// update. Each batch is treated as a single trajectory, as in `gae`.
+///
+/// # References
+/// - Schulman, J., Wolski, F., Dhariwal, P., Radford, A., Klimov, O. (2017).
+/// Proximal policy optimization algorithms. arXiv:1707.06347.
+#[derive(Parameterised)]
+pub struct PPO<C, P> {
+    pub critic: C,
+    #[weights] pub policy: P,
+
+    pub alpha: f64,
+    pub gamma: f64,
+    pub lambda: f64,
+
+    pub epsilon: f64,
+    pub n_epochs: usize,
+    pub minibatch_size: usize,
+    pub max_kl: Option<f64>,
+}
+
+impl<C, P> PPO<C, P> {
+    pub fn new(critic: C, policy: P, alpha: f64, gamma: f64, lambda: f64) -> Self {
+        PPO {
+            critic,
+            policy,
+
+            alpha,
+            gamma,
+            lambda,
+
+            epsilon: 0.2,
+            n_epochs: 4,
+            minibatch_size: 64,
+            max_kl: None,
+        }
+    }
+
+    /// Set the clipping range of the likelihood ratio, `[1 - ε, 1 + ε]`.
+    pub fn with_clipping(mut self, epsilon: f64) -> Self {
+        self.epsilon = epsilon;
+        self
+    }
+
+    /// Set the number of epochs and the minibatch size used per batch.
+    pub fn with_epochs(mut self, n_epochs: usize, minibatch_size: usize) -> Self {
+        if minibatch_size == 0 {
+            panic!("Minibatch size in PPO must be positive.");
+        }
+
+        self.n_epochs = n_epochs;
+        self.minibatch_size = minibatch_size;
+        self
+    }
+
+    /// Stop the epochs early once the estimated KL divergence exceeds `max_kl`.
+    pub fn with_kl_stopping(mut self, max_kl: f64) -> Self {
+        self.max_kl = Some(max_kl);
+        self
+    }
+}
+
+impl<C, P> PPO<C, P> {
+    fn probabilities<S>(&self, batch: &[Transition<S, P::Action>]) -> Vec<f64>
+    where P: Policy<S> {
+        batch.iter().map(|t| {
+            self.policy.probability(t.from.state(), &t.action).max(MIN_PROB)
+        }).collect()
+    }
+
+    fn update_minibatch<S>(
+        &mut self,
+        batch: &[Transition<S, P::Action>],
+        indices: &[usize],
+        advantages: &[f64],
+        old_probs: &[f64],
+    )
+    where
+        P: DifferentiablePolicy<S>,
+    {
+        let z = indices.len() as f64;
+        let mut grad = Array2::zeros(self.policy.weights_dim());
+
+        // Accumulate the gradient of the surrogate objective over the minibatch
+        // at fixed weights, and only then take a single step:
+        for &i in indices {
+            let t = &batch[i];
+            let s = t.from.state();
+
+            let ratio = self.policy.probability(s, &t.action) / old_probs[i];
+            let adv = advantages[i];
+
+            // The gradient of the clipped objective vanishes whenever the ratio
+            // has been clipped in the direction of improvement:
+            let clipped = (adv > 0.0 && ratio > 1.0 + self.epsilon)
+                || (adv < 0.0 && ratio < 1.0 - self.epsilon);
+
+            if !clipped {
+                grad.scaled_add(ratio * adv / z, &self.policy.grad_log(s, &t.action));
+            }
+        }
+
+        self.policy.update_grad_scaled(&grad.view(), self.alpha);
+    }
+}
+
+impl<S, C, P> BatchLearner<S, P::Action> for PPO<C, P>
+where
+    C: OnlineLearner<S, P::Action> + ValuePredictor<S>,
+    P: DifferentiablePolicy<S>,
+{
+    fn handle_batch(&mut self, batch: &[Transition<S, P::Action>]) {
+        if batch.is_empty() {
+            return;
+        }
+
+        let advantages = gae(batch, &self.critic, self.gamma, self.lambda);
+        let old_probs = self.probabilities(batch);
+
+        let mut rng = thread_rng();
+        let mut indices: Vec<usize> = (0..batch.len()).collect();
+
+        for _ in 0..self.n_epochs {
+            indices.shuffle(&mut rng);
+
+            for mb in indices.chunks(self.minibatch_size) {
+                self.update_minibatch(batch, mb, &advantages, &old_probs);
+            }
+
+            if let Some(max_kl) = self.max_kl {
+                let new_probs = self.probabilities(batch);
+                let kl = old_probs.iter().zip(new_probs.iter()).fold(0.0, |acc, (p, q)| {
+                    acc + (p / q).ln()
+                }) / batch.len() as f64;
+
+                if kl > max_kl {
+                    break;
+                }
+            }
+        }
+
+        for t in batch {
+            self.critic.handle_transition(t);
+
+            if t.terminated() {
+                self.critic.handle_terminal();
+            }
+        }
+    }
+}
+
+impl<S, C, P> ValuePredictor<S> for PPO<C, P>
+where
+    C: ValuePredictor<S>,
+{
+    fn predict_v(&self, s: &S) -> f64 {
+        self.critic.predict_v(s)
+    }
+}
+
+impl<S, C, P: Policy<S>> Controller<S, P::Action> for PPO<C, P> {
+    fn sample_target(&self, rng: &mut impl Rng, s: &S) -> P::Action {
+        self.policy.sample(rng, s)
+    }
+
+    fn sample_behaviour(&self, rng: &mut impl Rng, s: &S) -> P::Action {
+        self.policy.sample(rng, s)
+    }
+}
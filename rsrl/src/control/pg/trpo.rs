@@ -0,0 +1,203 @@
+use crate::{
+    BatchLearner, OnlineLearner,
+    control::Controller,
+    domains::Transition,
+    fa::{Weights, WeightsView, WeightsViewMut, Parameterised},
+    policies::{Policy, DifferentiablePolicy},
+    prediction::ValuePredictor,
+};
+use ndarray::{Array1, Array2};
+use rand::Rng;
+use super::{conjugate_gradient, gae};
+
+const MIN_PROB: f64 = 1e-10;
+
+/// Trust region policy optimisation via natural policy gradients.
+///
+/// The Fisher information matrix is estimated implicitly from the outer
+/// products of the score functions, `∇ ln π(a | s)`, over the batch, and the
+/// natural gradient is found by conjugate gradients using only Fisher-vector
+/// products. The step is scaled such that the quadratic approximation of the
+/// KL divergence equals `max_kl`, and then shrunk by backtracking line search
+/// until the sample-based surrogate improves and the KL constraint holds. With
+/// `n_backtracks = 0` this reduces to a plain natural policy gradient step.
+/// Each batch is treated as a single trajectory, as in `gae`.
+///
+/// # References
+/// - Kakade, S. (2002). A natural policy gradient. In Advances in Neural
+/// Information Processing Systems, pp. 1531–1538.
+/// - Schulman, J., Levine, S., Abbeel, P., Jordan, M., Moritz, P. (2015). Trust
+/// region policy optimization. In Proceedings of the 32nd International
+/// Conference on Machine Learning, pp. 1889–1897.
+#[derive(Parameterised)]
+pub struct TRPO<C, P> {
+    pub critic: C,
+    #[weights] pub policy: P,
+
+    pub gamma: f64,
+    pub lambda: f64,
+    pub max_kl: f64,
+
+    pub damping: f64,
+    pub cg_iters: usize,
+    pub n_backtracks: usize,
+}
+
+impl<C, P> TRPO<C, P> {
+    pub fn new(critic: C, policy: P, gamma: f64, lambda: f64, max_kl: f64) -> Self {
+        TRPO {
+            critic,
+            policy,
+
+            gamma,
+            lambda,
+            max_kl,
+
+            damping: 1e-3,
+            cg_iters: 10,
+            n_backtracks: 10,
+        }
+    }
+
+    /// Set the damping added to the diagonal of the Fisher matrix.
+    pub fn with_damping(mut self, damping: f64) -> Self {
+        self.damping = damping;
+        self
+    }
+
+    /// Set the number of conjugate gradient iterations.
+    pub fn with_cg_iters(mut self, cg_iters: usize) -> Self {
+        self.cg_iters = cg_iters;
+        self
+    }
+
+    /// Set the maximum number of halvings of the step in the line search.
+    pub fn with_backtracks(mut self, n_backtracks: usize) -> Self {
+        self.n_backtracks = n_backtracks;
+        self
+    }
+}
+
+impl<C, P> TRPO<C, P> {
+    // Return the sample-based surrogate advantage and KL divergence relative to
+    // the probabilities `old_probs`.
+    fn surrogate<S>(
+        &self,
+        batch: &[Transition<S, P::Action>],
+        advantages: &[f64],
+        old_probs: &[f64],
+    ) -> (f64, f64)
+    where
+        P: Policy<S>,
+    {
+        let z = batch.len() as f64;
+
+        batch.iter().zip(advantages.iter().zip(old_probs.iter())).fold(
+            (0.0, 0.0),
+            |(l, kl), (t, (adv, p_old))| {
+                let p = self.policy.probability(t.from.state(), &t.action).max(MIN_PROB);
+
+                (l + p / p_old * adv / z, kl + (p_old / p).ln() / z)
+            },
+        )
+    }
+}
+
+impl<S, C, P> BatchLearner<S, P::Action> for TRPO<C, P>
+where
+    C: OnlineLearner<S, P::Action> + ValuePredictor<S>,
+    P: DifferentiablePolicy<S>,
+{
+    fn handle_batch(&mut self, batch: &[Transition<S, P::Action>]) {
+        if batch.is_empty() {
+            return;
+        }
+
+        let n = batch.len();
+        let w_dim = self.policy.weights_dim();
+        let n_weights = w_dim[0] * w_dim[1];
+
+        let advantages = gae(batch, &self.critic, self.gamma, self.lambda);
+        let old_probs: Vec<f64> = batch.iter().map(|t| {
+            self.policy.probability(t.from.state(), &t.action).max(MIN_PROB)
+        }).collect();
+
+        // Stack the score functions, [N, D], and compute the policy gradient:
+        let mut scores = Array2::zeros((n, n_weights));
+
+        for (mut row, t) in scores.outer_iter_mut().zip(batch.iter()) {
+            let gl = self.policy.grad_log(t.from.state(), &t.action);
+
+            row.assign(&gl.into_shape(n_weights).unwrap());
+        }
+
+        let g = scores.t().dot(&Array1::from_vec(advantages.clone())) / n as f64;
+
+        // Solve F x = g using Fisher-vector products, F v = Sᵀ S v / N + c v:
+        let damping = self.damping;
+        let fvp = |v: &Array1<f64>| scores.t().dot(&scores.dot(v)) / n as f64 + damping * v;
+
+        let x = conjugate_gradient(&fvp, &g, self.cg_iters, 1e-10);
+        let shs = x.dot(&fvp(&x));
+
+        if shs > 0.0 {
+            let full_step = (x * (2.0 * self.max_kl / shs).sqrt()).into_shape(w_dim).unwrap();
+
+            if self.n_backtracks == 0 {
+                self.policy.update_grad(&full_step.view());
+            } else {
+                let (l_old, _) = self.surrogate(batch, &advantages, &old_probs);
+
+                let mut factor = 1.0;
+                let mut applied = 0.0;
+
+                for _ in 0..self.n_backtracks {
+                    self.policy.update_grad_scaled(&full_step.view(), factor - applied);
+                    applied = factor;
+
+                    let (l_new, kl) = self.surrogate(batch, &advantages, &old_probs);
+
+                    if kl <= self.max_kl && l_new > l_old {
+                        break;
+                    }
+
+                    factor *= 0.5;
+                }
+
+                // Revert to the original policy if no step was accepted:
+                let (l_new, kl) = self.surrogate(batch, &advantages, &old_probs);
+
+                if kl > self.max_kl || l_new <= l_old {
+                    self.policy.update_grad_scaled(&full_step.view(), -applied);
+                }
+            }
+        }
+
+        for t in batch {
+            self.critic.handle_transition(t);
+
+            if t.terminated() {
+                self.critic.handle_terminal();
+            }
+        }
+    }
+}
+
+impl<S, C, P> ValuePredictor<S> for TRPO<C, P>
+where
+    C: ValuePredictor<S>,
+{
+    fn predict_v(&self, s: &S) -> f64 {
+        self.critic.predict_v(s)
+    }
+}
+
+impl<S, C, P: Policy<S>> Controller<S, P::Action> for TRPO<C, P> {
+    fn sample_target(&self, rng: &mut impl Rng, s: &S) -> P::Action {
+        self.policy.sample(rng, s)
+    }
+
+    fn sample_behaviour(&self, rng: &mut impl Rng, s: &S) -> P::Action {
+        self.policy.sample(rng, s)
+    }
+}
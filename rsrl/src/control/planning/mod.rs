@@ -0,0 +1,3 @@
+import_all!(model);
+
+import_all!(prioritized_sweeping);
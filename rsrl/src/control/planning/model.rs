@@ -0,0 +1,164 @@
+use crate::domains::Transition;
+use std::collections::{HashMap, HashSet};
+
+#[derive(Clone, Debug, Default)]
+struct Outcomes {
+    n_visits: usize,
+    total_reward: f64,
+
+    successors: HashMap<(usize, bool), usize>,
+}
+
+/// Maximum-likelihood model of a finite MDP learned from sampled transitions.
+///
+/// The model tracks the empirical distribution over successor states, the
+/// mean reward of each state-action pair, and the set of predecessors of each
+/// state.
+#[derive(Clone, Debug, Default)]
+pub struct TabularModel {
+    outcomes: HashMap<(usize, usize), Outcomes>,
+    predecessors: HashMap<usize, HashSet<(usize, usize)>>,
+}
+
+impl TabularModel {
+    pub fn new() -> Self { TabularModel::default() }
+
+    /// Incorporate a single observed transition into the model.
+    pub fn handle_transition(&mut self, t: &Transition<usize, usize>) {
+        let s = *t.from.state();
+        let ns = *t.to.state();
+        let terminal = t.terminated();
+
+        let outcomes = self.outcomes.entry((s, t.action)).or_default();
+
+        outcomes.n_visits += 1;
+        outcomes.total_reward += t.reward;
+        *outcomes.successors.entry((ns, terminal)).or_insert(0) += 1;
+
+        if !terminal {
+            self.predecessors.entry(ns).or_default().insert((s, t.action));
+        }
+    }
+
+    /// Returns true if the pair `(s, a)` has been observed at least once.
+    pub fn is_known(&self, s: usize, a: usize) -> bool { self.outcomes.contains_key(&(s, a)) }
+
+    /// Return the number of times that the pair `(s, a)` has been observed.
+    pub fn n_visits(&self, s: usize, a: usize) -> usize {
+        self.outcomes.get(&(s, a)).map_or(0, |o| o.n_visits)
+    }
+
+    /// Return the empirical mean reward of the pair `(s, a)`.
+    pub fn mean_reward(&self, s: usize, a: usize) -> Option<f64> {
+        self.outcomes
+            .get(&(s, a))
+            .map(|o| o.total_reward / o.n_visits as f64)
+    }
+
+    /// Return the empirical successor distribution of the pair `(s, a)` as a
+    /// collection of `(next_state, is_terminal, probability)` triples.
+    pub fn successors(&self, s: usize, a: usize) -> Vec<(usize, bool, f64)> {
+        match self.outcomes.get(&(s, a)) {
+            Some(o) => {
+                let z = o.n_visits as f64;
+
+                o.successors
+                    .iter()
+                    .map(|(&(ns, terminal), &n)| (ns, terminal, n as f64 / z))
+                    .collect()
+            },
+            None => vec![],
+        }
+    }
+
+    /// Return the set of state-action pairs observed to lead into state `s`.
+    pub fn predecessors(&self, s: usize) -> impl Iterator<Item = &(usize, usize)> {
+        self.predecessors.get(&s).into_iter().flat_map(|ps| ps.iter())
+    }
+
+    /// Compute the expected one-step return of the pair `(s, a)` under the
+    /// model, bootstrapping from the state-value estimate `v`.
+    pub fn expected_return(
+        &self,
+        s: usize,
+        a: usize,
+        gamma: f64,
+        v: impl Fn(usize) -> f64,
+    ) -> Option<f64>
+    {
+        self.mean_reward(s, a).map(|r| {
+            self.successors(s, a).into_iter().fold(r, |acc, (ns, terminal, p)| {
+                if terminal { acc } else { acc + gamma * p * v(ns) }
+            })
+        })
+    }
+
+    /// Clear all observations from the model.
+    pub fn reset(&mut self) {
+        self.outcomes.clear();
+        self.predecessors.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domains::{Observation, Transition};
+    use super::TabularModel;
+
+    fn transition(s: usize, a: usize, r: f64, ns: usize, t: bool) -> Transition<usize, usize> {
+        Transition {
+            from: Observation::Full(s),
+            action: a,
+            reward: r,
+            to: if t { Observation::Terminal(ns) } else { Observation::Full(ns) },
+        }
+    }
+
+    #[test]
+    fn test_empirical_estimates() {
+        let mut model = TabularModel::new();
+
+        model.handle_transition(&transition(0, 1, 1.0, 1, false));
+        model.handle_transition(&transition(0, 1, 3.0, 2, false));
+        model.handle_transition(&transition(0, 1, 2.0, 1, false));
+
+        assert_eq!(model.n_visits(0, 1), 3);
+        assert_eq!(model.n_visits(0, 0), 0);
+        assert!((model.mean_reward(0, 1).unwrap() - 2.0).abs() < 1e-7);
+
+        let mut succ = model.successors(0, 1);
+        succ.sort_by_key(|&(ns, _, _)| ns);
+
+        assert_eq!(succ.len(), 2);
+        assert!((succ[0].2 - 2.0 / 3.0).abs() < 1e-7);
+        assert!((succ[1].2 - 1.0 / 3.0).abs() < 1e-7);
+    }
+
+    #[test]
+    fn test_predecessors() {
+        let mut model = TabularModel::new();
+
+        model.handle_transition(&transition(0, 0, 0.0, 2, false));
+        model.handle_transition(&transition(1, 1, 0.0, 2, false));
+        model.handle_transition(&transition(2, 0, 1.0, 3, true));
+
+        let mut preds: Vec<_> = model.predecessors(2).cloned().collect();
+        preds.sort();
+
+        assert_eq!(preds, vec![(0, 0), (1, 1)]);
+        assert_eq!(model.predecessors(3).count(), 0);
+    }
+
+    #[test]
+    fn test_expected_return() {
+        let mut model = TabularModel::new();
+
+        model.handle_transition(&transition(0, 0, 1.0, 1, false));
+        model.handle_transition(&transition(0, 0, 1.0, 2, true));
+
+        let g = model.expected_return(0, 0, 0.5, |_| 4.0).unwrap();
+
+        assert!((g - 2.0).abs() < 1e-7);
+        assert!(model.expected_return(1, 0, 0.5, |_| 4.0).is_none());
+    }
+}
@@ -0,0 +1,291 @@
+use crate::{
+    OnlineLearner, Shared, make_shared,
+    control::{Controller, planning::TabularModel},
+    domains::Transition,
+    fa::{
+        Parameterised, Weights, WeightsView, WeightsViewMut,
+        EnumerableStateActionFunction,
+    },
+    policies::{Policy, EnumerablePolicy},
+    prediction::{ValuePredictor, ActionValuePredictor},
+};
+use rand::Rng;
+use std::{
+    cmp::Ordering,
+    collections::{BinaryHeap, HashMap},
+};
+
+#[derive(Clone, Copy, Debug)]
+struct QueueEntry {
+    priority: f64,
+
+    s: usize,
+    a: usize,
+}
+
+impl PartialEq for QueueEntry {
+    fn eq(&self, other: &QueueEntry) -> bool { self.priority == other.priority }
+}
+
+impl Eq for QueueEntry {}
+
+impl PartialOrd for QueueEntry {
+    fn partial_cmp(&self, other: &QueueEntry) -> Option<Ordering> { Some(self.cmp(other)) }
+}
+
+impl Ord for QueueEntry {
+    fn cmp(&self, other: &QueueEntry) -> Ordering {
+        self.priority.partial_cmp(&other.priority).unwrap_or(Ordering::Equal)
+    }
+}
+
+/// Prioritized sweeping for tabular action-value functions.
+///
+/// After each real transition, the agent performs up to `n_backups` expected
+/// updates using a learned `TabularModel`, visiting state-action pairs in
+/// order of the magnitude of their Bellman error. Predecessors of each updated
+/// state are re-queued whenever their own error exceeds the threshold `theta`.
+///
+/// # References
+/// - Moore, A. W., Atkeson, C. G. (1993). Prioritized sweeping: Reinforcement
+/// learning with less data and less time. Machine Learning, 13(1):103–130.
+/// - Sutton, R. S. and Barto, A. G. (2018). Reinforcement Learning: An
+/// Introduction (2nd ed.). MIT Press, Section 8.4.
+#[derive(Parameterised)]
+pub struct PrioritizedSweeping<Q, P> {
+    #[weights] pub q_func: Q,
+
+    pub policy: P,
+    pub model: TabularModel,
+
+    pub alpha: f64,
+    pub gamma: f64,
+    pub theta: f64,
+    pub n_backups: usize,
+
+    queue: BinaryHeap<QueueEntry>,
+    priorities: HashMap<(usize, usize), f64>,
+}
+
+impl<Q, P> PrioritizedSweeping<Shared<Q>, P> {
+    pub fn new(
+        q_func: Q,
+        policy: P,
+        alpha: f64,
+        gamma: f64,
+        theta: f64,
+        n_backups: usize,
+    ) -> Self {
+        PrioritizedSweeping {
+            q_func: make_shared(q_func),
+
+            policy,
+            model: TabularModel::new(),
+
+            alpha,
+            gamma,
+            theta,
+            n_backups,
+
+            queue: BinaryHeap::new(),
+            priorities: HashMap::new(),
+        }
+    }
+}
+
+impl<Q, P> PrioritizedSweeping<Q, P> {
+    fn enqueue(&mut self, s: usize, a: usize, priority: f64) {
+        if priority <= self.theta {
+            return;
+        }
+
+        let current = self.priorities.entry((s, a)).or_insert(0.0);
+
+        if priority > *current {
+            *current = priority;
+
+            self.queue.push(QueueEntry { priority, s, a, });
+        }
+    }
+
+    fn dequeue(&mut self) -> Option<(usize, usize)> {
+        while let Some(entry) = self.queue.pop() {
+            let key = (entry.s, entry.a);
+
+            // Skip over stale entries that have since been superseded:
+            if self.priorities.get(&key).map_or(false, |&p| p == entry.priority) {
+                self.priorities.remove(&key);
+
+                return Some(key);
+            }
+        }
+
+        None
+    }
+
+    /// Clear the priority queue without discarding the learned model.
+    pub fn clear_queue(&mut self) {
+        self.queue.clear();
+        self.priorities.clear();
+    }
+}
+
+impl<Q: EnumerableStateActionFunction<usize>, P> PrioritizedSweeping<Q, P> {
+    fn bellman_error(&self, s: usize, a: usize) -> Option<f64> {
+        let q_func = &self.q_func;
+
+        self.model
+            .expected_return(s, a, self.gamma, |ns| q_func.find_max(&ns).1)
+            .map(|g| g - q_func.evaluate(&s, &a))
+    }
+
+    fn sweep(&mut self) {
+        for _ in 0..self.n_backups {
+            let (s, a) = match self.dequeue() {
+                Some(key) => key,
+                None => break,
+            };
+
+            if let Some(error) = self.bellman_error(s, a) {
+                self.q_func.update(&s, &a, self.alpha * error);
+            }
+
+            let predecessors: Vec<(usize, usize)> = self.model.predecessors(s).cloned().collect();
+
+            for (ps, pa) in predecessors {
+                if let Some(error) = self.bellman_error(ps, pa) {
+                    self.enqueue(ps, pa, error.abs());
+                }
+            }
+        }
+    }
+}
+
+impl<Q, P> OnlineLearner<usize, P::Action> for PrioritizedSweeping<Q, P>
+where
+    Q: EnumerableStateActionFunction<usize>,
+    P: EnumerablePolicy<usize>,
+{
+    fn handle_transition(&mut self, t: &Transition<usize, P::Action>) {
+        let s = *t.from.state();
+        let qsa = self.q_func.evaluate(&s, &t.action);
+
+        let residual = if t.terminated() {
+            t.reward - qsa
+        } else {
+            t.reward + self.gamma * self.q_func.find_max(t.to.state()).1 - qsa
+        };
+
+        self.model.handle_transition(t);
+        self.enqueue(s, t.action, residual.abs());
+
+        self.sweep();
+    }
+}
+
+impl<Q, P> Controller<usize, P::Action> for PrioritizedSweeping<Q, P>
+where
+    Q: EnumerableStateActionFunction<usize>,
+    P: EnumerablePolicy<usize>,
+{
+    fn sample_target(&self, _: &mut impl Rng, s: &usize) -> P::Action {
+        self.q_func.find_max(s).0
+    }
+
+    fn sample_behaviour(&self, rng: &mut impl Rng, s: &usize) -> P::Action {
+        self.policy.sample(rng, s)
+    }
+}
+
+impl<Q, P> ValuePredictor<usize> for PrioritizedSweeping<Q, P>
+where
+    Q: EnumerableStateActionFunction<usize>,
+    P: Policy<usize>,
+{
+    fn predict_v(&self, s: &usize) -> f64 { self.q_func.find_max(s).1 }
+}
+
+impl<Q, P> ActionValuePredictor<usize, usize> for PrioritizedSweeping<Q, P>
+where
+    Q: EnumerableStateActionFunction<usize>,
+    P: Policy<usize>,
+{
+    fn predict_q(&self, s: &usize, a: &usize) -> f64 { self.q_func.evaluate(s, a) }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        OnlineLearner, Shared,
+        domains::{Observation, Transition},
+        fa::tabular::Tabular,
+        policies::Random,
+        prediction::ActionValuePredictor,
+    };
+    use super::PrioritizedSweeping;
+
+    // Deterministic chain, 0 -> 1 -> 2 -> 3 (terminal), with a unit reward on
+    // the final step.
+    fn chain(agent: &mut impl OnlineLearner<usize, usize>) {
+        for s in 0..3 {
+            agent.handle_transition(&Transition {
+                from: Observation::Full(s),
+                action: 0,
+                reward: if s == 2 { 1.0 } else { 0.0 },
+                to: if s == 2 { Observation::Terminal(3) } else { Observation::Full(s + 1) },
+            });
+        }
+    }
+
+    fn agent(theta: f64, n_backups: usize) -> PrioritizedSweeping<Shared<Tabular>, Random> {
+        PrioritizedSweeping::new(Tabular::zeros([4, 1]), Random::new(1), 1.0, 0.9, theta, n_backups)
+    }
+
+    #[test]
+    fn test_backward_propagation() {
+        let mut ps = agent(1e-6, 10);
+
+        chain(&mut ps);
+
+        assert!((ps.predict_q(&2, &0) - 1.0).abs() < 1e-7);
+        assert!((ps.predict_q(&1, &0) - 0.9).abs() < 1e-7);
+        assert!((ps.predict_q(&0, &0) - 0.81).abs() < 1e-7);
+    }
+
+    #[test]
+    fn test_backup_budget() {
+        let mut ps = agent(1e-6, 2);
+
+        chain(&mut ps);
+
+        assert!((ps.predict_q(&1, &0) - 0.9).abs() < 1e-7);
+        assert_eq!(ps.predict_q(&0, &0), 0.0);
+    }
+
+    #[test]
+    fn test_threshold() {
+        let mut ps = agent(0.85, 10);
+
+        chain(&mut ps);
+
+        // The error at (0, 0), 0.81, falls below theta and is never queued:
+        assert!((ps.predict_q(&1, &0) - 0.9).abs() < 1e-7);
+        assert_eq!(ps.predict_q(&0, &0), 0.0);
+    }
+
+    #[test]
+    fn test_queue_ordering() {
+        let mut ps = agent(0.0, 10);
+
+        ps.enqueue(0, 0, 1.0);
+        ps.enqueue(1, 0, 2.0);
+        ps.enqueue(0, 0, 3.0);
+        ps.enqueue(2, 0, 0.5);
+
+        // The superseded entry for (0, 0) at priority 1 is skipped:
+        assert_eq!(ps.dequeue(), Some((0, 0)));
+        assert_eq!(ps.dequeue(), Some((1, 0)));
+        assert_eq!(ps.dequeue(), Some((2, 0)));
+        assert_eq!(ps.dequeue(), None);
+    }
+}
@@ -0,0 +1,240 @@
+use crate::{
+    OnlineLearner,
+    control::Controller,
+    domains::Transition,
+    fa::{
+        EnsembleFunction, EnumerableStateActionFunction, StateActionFunction,
+        Parameterised, Weights, WeightsView, WeightsViewMut,
+    },
+    policies::{Policy, EnumerablePolicy},
+    prediction::{ValuePredictor, ActionValuePredictor},
+};
+use rand::{Rng, thread_rng};
+
+fn max(values: Vec<f64>) -> f64 {
+    values.into_iter().fold(f64::NEG_INFINITY, f64::max)
+}
+
+/// Q-learning over an ensemble of action-value functions with per-head
+/// targets.
+///
+/// Each head `k` is updated towards its own target `r + γ max_a Q_k(s', a)`,
+/// such that the heads remain independent estimates of the action-values.
+///
+/// # References
+/// - Osband, I., Blundell, C., Pritzel, A., Van Roy, B. (2016). Deep
+/// exploration via bootstrapped DQN. In Advances in Neural Information
+/// Processing Systems, pp. 4026–4034.
+#[derive(Parameterised)]
+pub struct BootstrappedQLearning<Q, P> {
+    #[weights] pub q_func: Q,
+    pub policy: P,
+
+    pub alpha: f64,
+    pub gamma: f64,
+}
+
+impl<Q, P> BootstrappedQLearning<Q, P> {
+    pub fn new(q_func: Q, policy: P, alpha: f64, gamma: f64) -> Self {
+        BootstrappedQLearning {
+            q_func,
+            policy,
+
+            alpha,
+            gamma,
+        }
+    }
+}
+
+impl<S, Q, P> OnlineLearner<S, usize> for BootstrappedQLearning<Q, P>
+where
+    Q: EnsembleFunction<S>,
+    P: EnumerablePolicy<S>,
+{
+    fn handle_transition(&mut self, t: &Transition<S, usize>) {
+        let s = t.from.state();
+        let (alpha, gamma) = (self.alpha, self.gamma);
+
+        self.q_func.update_heads(s, &t.action, |q| {
+            let qsa = q(s)[t.action];
+
+            alpha * if t.terminated() {
+                t.reward - qsa
+            } else {
+                t.reward + gamma * max(q(t.to.state())) - qsa
+            }
+        });
+    }
+
+    fn handle_terminal(&mut self) { self.policy.handle_terminal(); }
+}
+
+impl<S, Q, P> Controller<S, usize> for BootstrappedQLearning<Q, P>
+where
+    Q: EnsembleFunction<S>,
+    P: EnumerablePolicy<S>,
+{
+    fn sample_target(&self, _: &mut impl Rng, s: &S) -> usize {
+        self.q_func.find_max(s).0
+    }
+
+    fn sample_behaviour(&self, rng: &mut impl Rng, s: &S) -> usize {
+        self.policy.sample(rng, s)
+    }
+}
+
+impl<S, Q, P> ValuePredictor<S> for BootstrappedQLearning<Q, P>
+where
+    Q: EnsembleFunction<S>,
+    P: Policy<S>,
+{
+    fn predict_v(&self, s: &S) -> f64 { self.q_func.find_max(s).1 }
+}
+
+impl<S, Q, P> ActionValuePredictor<S, usize> for BootstrappedQLearning<Q, P>
+where
+    Q: EnsembleFunction<S>,
+    P: Policy<S>,
+{
+    fn predict_q(&self, s: &S, a: &usize) -> f64 {
+        self.q_func.evaluate(s, a)
+    }
+}
+
+/// SARSA over an ensemble of action-value functions with per-head targets.
+///
+/// The next action `a'` is sampled once from the policy, and each head `k` is
+/// then updated towards its own target `r + γ Q_k(s', a')`.
+///
+/// # References
+/// - Osband, I., Blundell, C., Pritzel, A., Van Roy, B. (2016). Deep
+/// exploration via bootstrapped DQN. In Advances in Neural Information
+/// Processing Systems, pp. 4026–4034.
+#[derive(Parameterised)]
+pub struct BootstrappedSARSA<Q, P> {
+    #[weights] pub q_func: Q,
+    pub policy: P,
+
+    pub alpha: f64,
+    pub gamma: f64,
+}
+
+impl<Q, P> BootstrappedSARSA<Q, P> {
+    pub fn new(q_func: Q, policy: P, alpha: f64, gamma: f64) -> Self {
+        BootstrappedSARSA {
+            q_func,
+            policy,
+
+            alpha,
+            gamma,
+        }
+    }
+}
+
+impl<S, Q, P> OnlineLearner<S, usize> for BootstrappedSARSA<Q, P>
+where
+    Q: EnsembleFunction<S>,
+    P: Policy<S, Action = usize>,
+{
+    fn handle_transition(&mut self, t: &Transition<S, usize>) {
+        let s = t.from.state();
+        let (alpha, gamma) = (self.alpha, self.gamma);
+        let na = if t.terminated() {
+            None
+        } else {
+            Some(self.policy.sample(&mut thread_rng(), t.to.state()))
+        };
+
+        self.q_func.update_heads(s, &t.action, |q| {
+            let qsa = q(s)[t.action];
+
+            alpha * match na {
+                Some(na) => t.reward + gamma * q(t.to.state())[na] - qsa,
+                None => t.reward - qsa,
+            }
+        });
+    }
+
+    fn handle_terminal(&mut self) { self.policy.handle_terminal(); }
+}
+
+impl<S, Q, P: Policy<S>> Controller<S, P::Action> for BootstrappedSARSA<Q, P> {
+    fn sample_target(&self, rng: &mut impl Rng, s: &S) -> P::Action {
+        self.policy.sample(rng, s)
+    }
+
+    fn sample_behaviour(&self, rng: &mut impl Rng, s: &S) -> P::Action {
+        self.policy.sample(rng, s)
+    }
+}
+
+impl<S, Q, P> ValuePredictor<S> for BootstrappedSARSA<Q, P>
+where
+    Q: EnsembleFunction<S>,
+    P: Policy<S, Action = usize>,
+{
+    fn predict_v(&self, s: &S) -> f64 {
+        self.q_func.evaluate(s, &self.sample_behaviour(&mut thread_rng(), s))
+    }
+}
+
+impl<S, Q, P> ActionValuePredictor<S, usize> for BootstrappedSARSA<Q, P>
+where
+    Q: EnsembleFunction<S>,
+    P: Policy<S>,
+{
+    fn predict_q(&self, s: &S, a: &usize) -> f64 {
+        self.q_func.evaluate(s, a)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        OnlineLearner,
+        domains::{Observation, Transition},
+        fa::{Ensemble, EnumerableStateActionFunction, tabular::Tabular},
+        policies::Greedy,
+    };
+    use super::{BootstrappedQLearning, BootstrappedSARSA};
+
+    fn ensemble() -> Ensemble<Tabular> {
+        Ensemble::new(vec![
+            Tabular::new(vec![vec![0.0, 0.0], vec![1.0, 0.0]]),
+            Tabular::new(vec![vec![0.0, 0.0], vec![0.0, 3.0]]),
+        ], 1.0)
+    }
+
+    fn transition() -> Transition<usize, usize> {
+        Transition {
+            from: Observation::Full(0),
+            action: 0,
+            reward: 1.0,
+            to: Observation::Full(1),
+        }
+    }
+
+    #[test]
+    fn test_q_learning_per_head_targets() {
+        let q = ensemble();
+        let mut agent = BootstrappedQLearning::new(q.clone(), Greedy::new(q), 1.0, 1.0);
+
+        agent.handle_transition(&transition());
+
+        // Head 0 bootstraps from max Q_0(1, .) = 1, head 1 from max Q_1(1, .) = 3:
+        assert_eq!(agent.q_func.heads[0].evaluate_all(&0), vec![2.0, 0.0]);
+        assert_eq!(agent.q_func.heads[1].evaluate_all(&0), vec![4.0, 0.0]);
+    }
+
+    #[test]
+    fn test_sarsa_per_head_targets() {
+        let q = ensemble();
+        let mut agent = BootstrappedSARSA::new(q.clone(), Greedy::new(q), 1.0, 1.0);
+
+        // The greedy policy over the active head (0) selects a' = 0 in state 1:
+        agent.handle_transition(&transition());
+
+        assert_eq!(agent.q_func.heads[0].evaluate_all(&0), vec![2.0, 0.0]);
+        assert_eq!(agent.q_func.heads[1].evaluate_all(&0), vec![1.0, 0.0]);
+    }
+}
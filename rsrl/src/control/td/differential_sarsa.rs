@@ -0,0 +1,106 @@
+use crate::{
+    OnlineLearner,
+    control::Controller,
+    domains::Transition,
+    fa::{
+        Parameterised, Weights, WeightsView, WeightsViewMut,
+        StateActionFunction,
+    },
+    policies::Policy,
+    prediction::{AverageRewardPredictor, ValuePredictor, ActionValuePredictor},
+};
+use rand::{Rng, thread_rng};
+
+/// Differential semi-gradient SARSA for the average reward setting.
+///
+/// Learns differential action-values along with an estimate of the average
+/// reward rate, `rho`, of the behaviour policy, both driven by the
+/// differential TD error `r - rho + Q(s', a') - Q(s, a)`.
+///
+/// # References
+/// - Sutton, R. S. and Barto, A. G. (2018). Reinforcement Learning: An
+/// Introduction (2nd ed.). MIT Press, Section 10.3.
+#[derive(Parameterised)]
+pub struct DifferentialSARSA<Q, P> {
+    #[weights] pub q_func: Q,
+    pub policy: P,
+
+    pub alpha: f64,
+    pub beta: f64,
+
+    pub rho: f64,
+}
+
+impl<Q, P> DifferentialSARSA<Q, P> {
+    pub fn new(q_func: Q, policy: P, alpha: f64, beta: f64) -> Self {
+        DifferentialSARSA {
+            q_func,
+            policy,
+
+            alpha,
+            beta,
+
+            rho: 0.0,
+        }
+    }
+}
+
+impl<S, Q, P> OnlineLearner<S, P::Action> for DifferentialSARSA<Q, P>
+where
+    Q: StateActionFunction<S, P::Action, Output = f64>,
+    P: Policy<S>,
+{
+    fn handle_transition(&mut self, t: &Transition<S, P::Action>) {
+        let s = t.from.state();
+        let qsa = self.q_func.evaluate(s, &t.action);
+
+        let residual = if t.terminated() {
+            t.reward - self.rho - qsa
+        } else {
+            let ns = t.to.state();
+            let na = self.policy.sample(&mut thread_rng(), ns);
+            let nqsna = self.q_func.evaluate(ns, &na);
+
+            t.reward - self.rho + nqsna - qsa
+        };
+
+        self.rho += self.beta * residual;
+        self.q_func.update(s, &t.action, self.alpha * residual);
+    }
+
+    fn handle_terminal(&mut self) { self.policy.handle_terminal(); }
+}
+
+impl<S, Q, P: Policy<S>> Controller<S, P::Action> for DifferentialSARSA<Q, P> {
+    fn sample_target(&self, rng: &mut impl Rng, s: &S) -> P::Action {
+        self.policy.sample(rng, s)
+    }
+
+    fn sample_behaviour(&self, rng: &mut impl Rng, s: &S) -> P::Action {
+        self.policy.sample(rng, s)
+    }
+}
+
+impl<S, Q, P> ValuePredictor<S> for DifferentialSARSA<Q, P>
+where
+    Q: StateActionFunction<S, P::Action, Output = f64>,
+    P: Policy<S>,
+{
+    fn predict_v(&self, s: &S) -> f64 {
+        self.q_func.evaluate(s, &self.sample_behaviour(&mut thread_rng(), s))
+    }
+}
+
+impl<S, Q, P> ActionValuePredictor<S, P::Action> for DifferentialSARSA<Q, P>
+where
+    Q: StateActionFunction<S, P::Action, Output = f64>,
+    P: Policy<S>,
+{
+    fn predict_q(&self, s: &S, a: &P::Action) -> f64 {
+        self.q_func.evaluate(s, a)
+    }
+}
+
+impl<Q, P> AverageRewardPredictor for DifferentialSARSA<Q, P> {
+    fn predict_rho(&self) -> f64 { self.rho }
+}
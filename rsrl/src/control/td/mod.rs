@@ -3,11 +3,20 @@ import_all!(q_learning);
 import_all!(q_lambda);
 import_all!(q_sigma);
 import_all!(pal);
+import_all!(soft_q_learning);
 
 // On-policy:
 import_all!(sarsa);
 import_all!(sarsa_lambda);
 import_all!(expected_sarsa);
+import_all!(soft_expected_sarsa);
+
+// Bootstrapped ensembles:
+import_all!(bootstrapped);
+
+// Average reward:
+import_all!(r_learning);
+import_all!(differential_sarsa);
 
 // TODO:
 // PQ(lambda) - http://proceedings.mlr.press/v32/sutton14.pdf
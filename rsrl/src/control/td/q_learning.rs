@@ -63,6 +63,8 @@ where
 
         self.q_func.update(s, &t.action, self.alpha * residual);
     }
+
+    fn handle_terminal(&mut self) { self.policy.handle_terminal(); }
 }
 
 impl<S, Q, P> Controller<S, P::Action> for QLearning<Q, P>
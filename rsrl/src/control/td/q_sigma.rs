@@ -68,6 +68,9 @@ impl<S> Backup<S> {
 ///     * `0` - `ExpectedSARSA` | `TreeBackup`
 ///     * `1` - `SARSA`
 ///
+/// The target policy defaults to `Greedy`, but any `EnumerablePolicy` may be
+/// used in its place via `with_target`; e.g. `Mellowmax` or `Sparsemax`.
+///
 /// # References
 /// - Sutton, R. S. and Barto, A. G. (2017). Reinforcement Learning: An
 /// Introduction (2nd ed.). Manuscript in preparation.
@@ -75,11 +78,11 @@ impl<S> Backup<S> {
 /// (2017). Multi-step Reinforcement Learning: A Unifying Algorithm. arXiv
 /// preprint arXiv:1703.01327.
 #[derive(Parameterised)]
-pub struct QSigma<S, Q, P> {
+pub struct QSigma<S, Q, P, T = Greedy<Q>> {
     #[weights] pub q_func: Q,
 
     pub policy: P,
-    pub target: Greedy<Q>,
+    pub target: T,
 
     pub alpha: f64,
     pub gamma: f64,
@@ -114,7 +117,25 @@ impl<S, Q, P> QSigma<S, Shared<Q>, P> {
     }
 }
 
-impl<S, Q: EnumerableStateActionFunction<S>, P> QSigma<S, Q, P> {
+impl<S, Q, P, T> QSigma<S, Q, P, T> {
+    /// Replace the target policy, e.g. with one built over `self.q_func`.
+    pub fn with_target<T2>(self, target: T2) -> QSigma<S, Q, P, T2> {
+        QSigma {
+            q_func: self.q_func,
+
+            policy: self.policy,
+            target,
+
+            alpha: self.alpha,
+            gamma: self.gamma,
+            sigma: self.sigma,
+
+            backup: self.backup,
+        }
+    }
+}
+
+impl<S, Q: EnumerableStateActionFunction<S>, P, T> QSigma<S, Q, P, T> {
     fn update_backup(&mut self, entry: BackupEntry<S>) {
         self.backup.push(entry);
 
@@ -132,11 +153,12 @@ impl<S, Q: EnumerableStateActionFunction<S>, P> QSigma<S, Q, P> {
     }
 }
 
-impl<S, Q, P> OnlineLearner<S, P::Action> for QSigma<S, Q, P>
+impl<S, Q, P, T> OnlineLearner<S, P::Action> for QSigma<S, Q, P, T>
 where
     S: Clone,
     Q: EnumerableStateActionFunction<S>,
     P: EnumerablePolicy<S>,
+    T: EnumerablePolicy<S>,
 {
     fn handle_transition(&mut self, t: &Transition<S, P::Action>) {
         let s = t.from.state();
@@ -188,10 +210,11 @@ where
     }
 }
 
-impl<S, Q, P> Controller<S, P::Action> for QSigma<S, Q, P>
+impl<S, Q, P, T> Controller<S, P::Action> for QSigma<S, Q, P, T>
 where
     Q: EnumerableStateActionFunction<S>,
     P: EnumerablePolicy<S>,
+    T: EnumerablePolicy<S>,
 {
     fn sample_target(&self, rng: &mut impl Rng, s: &S) -> P::Action {
         self.target.sample(rng, s)
@@ -202,20 +225,26 @@ where
     }
 }
 
-impl<S, Q, P> ValuePredictor<S> for QSigma<S, Q, P>
+impl<S, Q, P, T> ValuePredictor<S> for QSigma<S, Q, P, T>
 where
     Q: EnumerableStateActionFunction<S>,
     P: EnumerablePolicy<S>,
+    T: EnumerablePolicy<S>,
 {
-    fn predict_v(&self, s: &S) -> f64 { self.predict_q(s, &self.target.mpa(s)) }
+    fn predict_v(&self, s: &S) -> f64 {
+        self.q_func.evaluate_all(s).into_iter()
+            .zip(self.target.probabilities(s).into_iter())
+            .fold(0.0, |acc, (q, p)| acc + q * p)
+    }
 }
 
-impl<S, Q, P> ActionValuePredictor<S, <Greedy<Q> as Policy<S>>::Action> for QSigma<S, Q, P>
+impl<S, Q, P, T> ActionValuePredictor<S, usize> for QSigma<S, Q, P, T>
 where
     Q: EnumerableStateActionFunction<S>,
     P: EnumerablePolicy<S>,
+    T: EnumerablePolicy<S>,
 {
-    fn predict_q(&self, s: &S, a: &<Greedy<Q> as Policy<S>>::Action) -> f64 {
+    fn predict_q(&self, s: &S, a: &usize) -> f64 {
         self.q_func.evaluate(s, a)
     }
 }
@@ -0,0 +1,117 @@
+use crate::{
+    OnlineLearner,
+    control::Controller,
+    domains::Transition,
+    fa::{
+        Parameterised, Weights, WeightsView, WeightsViewMut,
+        EnumerableStateActionFunction,
+    },
+    policies::{Policy, EnumerablePolicy},
+    prediction::{AverageRewardPredictor, ValuePredictor, ActionValuePredictor},
+};
+use rand::Rng;
+
+/// Schwartz's R-learning for the average reward setting.
+///
+/// An off-policy analogue of Q-learning which learns relative action-values
+/// and an estimate of the average reward rate, `rho`, of the greedy policy.
+/// The rate is only updated on transitions in which the greedy action was
+/// taken.
+///
+/// # References
+/// - Schwartz, A. (1993). A reinforcement learning method for maximizing
+/// undiscounted rewards. In Proceedings of the 10th International Conference
+/// on Machine Learning, pp. 298–305.
+/// - Mahadevan, S. (1996). Average reward reinforcement learning: Foundations,
+/// algorithms, and empirical results. Machine Learning, 22(1–3), 159–195.
+#[derive(Parameterised)]
+pub struct RLearning<Q, P> {
+    #[weights] pub q_func: Q,
+    pub policy: P,
+
+    pub alpha: f64,
+    pub beta: f64,
+
+    pub rho: f64,
+}
+
+impl<Q, P> RLearning<Q, P> {
+    pub fn new(q_func: Q, policy: P, alpha: f64, beta: f64) -> Self {
+        RLearning {
+            q_func,
+            policy,
+
+            alpha,
+            beta,
+
+            rho: 0.0,
+        }
+    }
+}
+
+impl<S, Q, P> OnlineLearner<S, P::Action> for RLearning<Q, P>
+where
+    Q: EnumerableStateActionFunction<S>,
+    P: EnumerablePolicy<S>,
+{
+    fn handle_transition(&mut self, t: &Transition<S, P::Action>) {
+        let s = t.from.state();
+        let qs = self.q_func.evaluate_all(s);
+        let qsa = qs[t.action];
+
+        let nqsna = if t.terminated() {
+            0.0
+        } else {
+            self.q_func.find_max(t.to.state()).1
+        };
+        let residual = t.reward - self.rho + nqsna - qsa;
+
+        self.q_func.update(s, &t.action, self.alpha * residual);
+
+        let max_qs = qs.into_iter().fold(::std::f64::MIN, f64::max);
+
+        if qsa >= max_qs {
+            self.rho += self.beta * (t.reward - self.rho + nqsna - max_qs);
+        }
+    }
+
+    fn handle_terminal(&mut self) { self.policy.handle_terminal(); }
+}
+
+impl<S, Q, P> Controller<S, P::Action> for RLearning<Q, P>
+where
+    Q: EnumerableStateActionFunction<S>,
+    P: EnumerablePolicy<S>,
+{
+    fn sample_target(&self, _: &mut impl Rng, s: &S) -> P::Action {
+        self.q_func.find_max(s).0
+    }
+
+    fn sample_behaviour(&self, rng: &mut impl Rng, s: &S) -> P::Action {
+        self.policy.sample(rng, s)
+    }
+}
+
+impl<S, Q, P> ValuePredictor<S> for RLearning<Q, P>
+where
+    Q: EnumerableStateActionFunction<S>,
+    P: EnumerablePolicy<S>,
+{
+    fn predict_v(&self, s: &S) -> f64 {
+        self.q_func.find_max(s).1
+    }
+}
+
+impl<S, Q, P> ActionValuePredictor<S, P::Action> for RLearning<Q, P>
+where
+    Q: EnumerableStateActionFunction<S>,
+    P: EnumerablePolicy<S>,
+{
+    fn predict_q(&self, s: &S, a: &P::Action) -> f64 {
+        self.q_func.evaluate(s, a)
+    }
+}
+
+impl<Q, P> AverageRewardPredictor for RLearning<Q, P> {
+    fn predict_rho(&self) -> f64 { self.rho }
+}
@@ -60,6 +60,8 @@ where
 
         self.q_func.update(s, &t.action, self.alpha * residual);
     }
+
+    fn handle_terminal(&mut self) { self.policy.handle_terminal(); }
 }
 
 impl<S, Q, P: Policy<S>> Controller<S, P::Action> for SARSA<Q, P> {
@@ -0,0 +1,131 @@
+use crate::{
+    OnlineLearner,
+    control::Controller,
+    domains::Transition,
+    fa::{
+        Parameterised, Weights, WeightsView, WeightsViewMut,
+        EnumerableStateActionFunction,
+    },
+    policies::{EnumerablePolicy, Policy},
+    prediction::{ValuePredictor, ActionValuePredictor},
+};
+use rand::Rng;
+
+/// Entropy-regularised variant of expected SARSA.
+///
+/// Bootstraps from the soft value of the policy,
+/// `Σ π(a | s) [Q(s, a) - τ ln π(a | s)]`, such that the learned values include
+/// the (discounted) future entropy of the policy weighted by `tau`.
+///
+/// # References
+/// - van Seijen, H., van Hasselt, H., Whiteson, S., Wiering, M. (2009). A
+/// theoretical and empirical analysis of Expected Sarsa. In Proceedings of the
+/// IEEE Symposium on Adaptive Dynamic Programming and Reinforcement Learning,
+/// pp. 177–184.
+/// - Haarnoja, T., Zhou, A., Abbeel, P., Levine, S. (2018). Soft actor-critic:
+/// Off-policy maximum entropy deep reinforcement learning with a stochastic
+/// actor. In Proceedings of the 35th International Conference on Machine
+/// Learning, pp. 1861–1870.
+#[derive(Parameterised)]
+pub struct SoftExpectedSARSA<Q, P> {
+    #[weights] pub q_func: Q,
+    pub policy: P,
+
+    pub alpha: f64,
+    pub gamma: f64,
+    pub tau: f64,
+}
+
+impl<Q, P> SoftExpectedSARSA<Q, P> {
+    pub fn new(q_func: Q, policy: P, alpha: f64, gamma: f64, tau: f64) -> Self {
+        if tau <= 0.0 {
+            panic!("Tau parameter in SoftExpectedSARSA must be positive.");
+        }
+
+        SoftExpectedSARSA {
+            q_func,
+            policy,
+
+            alpha,
+            gamma,
+            tau,
+        }
+    }
+}
+
+impl<S, Q, P> OnlineLearner<S, P::Action> for SoftExpectedSARSA<Q, P>
+where
+    Q: EnumerableStateActionFunction<S>,
+    P: EnumerablePolicy<S>,
+{
+    fn handle_transition(&mut self, t: &Transition<S, P::Action>) {
+        let s = t.from.state();
+        let qsa = self.q_func.evaluate(s, &t.action);
+
+        let residual = if t.terminated() {
+            t.reward - qsa
+        } else {
+            t.reward + self.gamma * self.predict_v(t.to.state()) - qsa
+        };
+
+        self.q_func.update(s, &t.action, self.alpha * residual);
+    }
+
+    fn handle_terminal(&mut self) { self.policy.handle_terminal(); }
+}
+
+impl<S, Q, P: Policy<S>> Controller<S, P::Action> for SoftExpectedSARSA<Q, P> {
+    fn sample_target(&self, rng: &mut impl Rng, s: &S) -> P::Action {
+        self.policy.sample(rng, s)
+    }
+
+    fn sample_behaviour(&self, rng: &mut impl Rng, s: &S) -> P::Action {
+        self.policy.sample(rng, s)
+    }
+}
+
+impl<S, Q, P> ValuePredictor<S> for SoftExpectedSARSA<Q, P>
+where
+    Q: EnumerableStateActionFunction<S>,
+    P: EnumerablePolicy<S>,
+{
+    fn predict_v(&self, s: &S) -> f64 {
+        self.q_func.evaluate_all(s).into_iter()
+            .zip(self.policy.probabilities(s).into_iter())
+            .filter(|&(_, p)| p > 0.0)
+            .fold(0.0, |acc, (q, p)| acc + p * (q - self.tau * p.ln()))
+    }
+}
+
+impl<S, Q, P> ActionValuePredictor<S, P::Action> for SoftExpectedSARSA<Q, P>
+where
+    Q: EnumerableStateActionFunction<S>,
+    P: EnumerablePolicy<S>,
+{
+    fn predict_q(&self, s: &S, a: &P::Action) -> f64 {
+        self.q_func.evaluate(s, a)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        fa::mocking::MockQ,
+        policies::Random,
+        prediction::ValuePredictor,
+    };
+    use super::SoftExpectedSARSA;
+
+    #[test]
+    fn test_soft_value() {
+        let agent = SoftExpectedSARSA::new(MockQ::new(None), Random::new(2), 1.0, 1.0, 0.5);
+
+        assert!((agent.predict_v(&vec![1.0, 3.0]) - (2.0 + 0.5 * 2.0f64.ln())).abs() < 1e-7);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_invalid_tau() {
+        SoftExpectedSARSA::new(MockQ::new(None), Random::new(2), 1.0, 1.0, 0.0);
+    }
+}
@@ -0,0 +1,134 @@
+use crate::{
+    OnlineLearner,
+    control::Controller,
+    domains::Transition,
+    fa::{
+        Parameterised, Weights, WeightsView, WeightsViewMut,
+        EnumerableStateActionFunction,
+        transforms::{LogSumExp, Transform},
+    },
+    policies::{EnumerablePolicy, Policy, sample_probs_with_rng},
+    prediction::{ValuePredictor, ActionValuePredictor},
+};
+use ndarray::Array1;
+use rand::Rng;
+
+// Compute the soft maximum, `τ log Σ exp(q / τ)`, shifting by the largest value
+// for numerical stability.
+pub(crate) fn soft_max_value(qs: &[f64], tau: f64) -> f64 {
+    let max_q = qs.iter().fold(::std::f64::MIN, |acc, &q| acc.max(q));
+    let scaled: Array1<f64> = qs.iter().map(|q| (q - max_q) / tau).collect();
+
+    max_q + tau * LogSumExp::default().transform(scaled)
+}
+
+/// Soft (entropy-regularised) Q-learning.
+///
+/// Replaces the hard maximum in the Q-learning backup with the log-sum-exp
+/// soft maximum at temperature `tau`, such that the learned values are those
+/// of the optimal maximum-entropy policy, `π(a | s) ∝ exp(Q(s, a) / τ)`.
+///
+/// # References
+/// - Haarnoja, T., Tang, H., Abbeel, P., Levine, S. (2017). Reinforcement
+/// learning with deep energy-based policies. In Proceedings of the 34th
+/// International Conference on Machine Learning, pp. 1352–1361.
+/// - Fox, R., Pakman, A., Tishby, N. (2016). Taming the noise in reinforcement
+/// learning via soft updates. In Proceedings of the 32nd Conference on
+/// Uncertainty in Artificial Intelligence, pp. 202–211.
+#[derive(Parameterised)]
+pub struct SoftQLearning<Q, P> {
+    #[weights] pub q_func: Q,
+    pub policy: P,
+
+    pub alpha: f64,
+    pub gamma: f64,
+    pub tau: f64,
+}
+
+impl<Q, P> SoftQLearning<Q, P> {
+    pub fn new(q_func: Q, policy: P, alpha: f64, gamma: f64, tau: f64) -> Self {
+        if tau <= 0.0 {
+            panic!("Tau parameter in SoftQLearning must be positive.");
+        }
+
+        SoftQLearning {
+            q_func,
+            policy,
+
+            alpha,
+            gamma,
+            tau,
+        }
+    }
+}
+
+impl<S, Q, P> OnlineLearner<S, P::Action> for SoftQLearning<Q, P>
+where
+    Q: EnumerableStateActionFunction<S>,
+    P: EnumerablePolicy<S>,
+{
+    fn handle_transition(&mut self, t: &Transition<S, P::Action>) {
+        let s = t.from.state();
+        let qsa = self.q_func.evaluate(s, &t.action);
+
+        let residual = if t.terminated() {
+            t.reward - qsa
+        } else {
+            t.reward + self.gamma * self.predict_v(t.to.state()) - qsa
+        };
+
+        self.q_func.update(s, &t.action, self.alpha * residual);
+    }
+
+    fn handle_terminal(&mut self) { self.policy.handle_terminal(); }
+}
+
+impl<S, Q, P> Controller<S, P::Action> for SoftQLearning<Q, P>
+where
+    Q: EnumerableStateActionFunction<S>,
+    P: EnumerablePolicy<S>,
+{
+    fn sample_target(&self, rng: &mut impl Rng, s: &S) -> P::Action {
+        let qs = self.q_func.evaluate_all(s);
+        let v = soft_max_value(&qs, self.tau);
+        let ps: Vec<f64> = qs.into_iter().map(|q| ((q - v) / self.tau).exp()).collect();
+
+        sample_probs_with_rng(rng, &ps)
+    }
+
+    fn sample_behaviour(&self, rng: &mut impl Rng, s: &S) -> P::Action {
+        self.policy.sample(rng, s)
+    }
+}
+
+impl<S, Q, P> ValuePredictor<S> for SoftQLearning<Q, P>
+where
+    Q: EnumerableStateActionFunction<S>,
+    P: EnumerablePolicy<S>,
+{
+    fn predict_v(&self, s: &S) -> f64 {
+        soft_max_value(&self.q_func.evaluate_all(s), self.tau)
+    }
+}
+
+impl<S, Q, P> ActionValuePredictor<S, P::Action> for SoftQLearning<Q, P>
+where
+    Q: EnumerableStateActionFunction<S>,
+    P: EnumerablePolicy<S>,
+{
+    fn predict_q(&self, s: &S, a: &P::Action) -> f64 {
+        self.q_func.evaluate(s, a)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::soft_max_value;
+
+    #[test]
+    fn test_soft_max_value() {
+        assert!((soft_max_value(&[0.0, 0.0], 1.0) - 2.0f64.ln()).abs() < 1e-7);
+        assert!((soft_max_value(&[1000.0, 0.0], 1.0) - 1000.0).abs() < 1e-7);
+        assert!((soft_max_value(&[1.0, 2.0], 1e-3) - 2.0).abs() < 1e-7);
+    }
+}
@@ -0,0 +1,51 @@
+//! Exact dynamic programming solvers for finite MDPs.
+use crate::domains::FiniteMDP;
+use ndarray::{Array1, Array2, Axis};
+
+/// Solution to a finite MDP produced by one of the dynamic programming solvers.
+#[derive(Clone, Debug)]
+pub struct Solution {
+    /// State-value function, `v[s]`.
+    pub v: Array1<f64>,
+
+    /// Action-value function, `q[s, a]`.
+    pub q: Array2<f64>,
+
+    /// Deterministic policy greedy with respect to `q`.
+    pub policy: Vec<usize>,
+
+    /// Number of iterations performed by the solver.
+    pub n_iters: usize,
+}
+
+impl Solution {
+    fn from_values(mdp: &FiniteMDP, v: Array1<f64>, n_iters: usize) -> Solution {
+        let q = mdp.backup(v.view());
+        let policy = greedy(&q);
+
+        Solution { v, q, policy, n_iters, }
+    }
+}
+
+/// Return the first maximising action in each row of `q`.
+pub fn greedy(q: &Array2<f64>) -> Vec<usize> {
+    q.outer_iter()
+        .map(|qs| {
+            qs.iter().enumerate().fold((0, qs[0]), |(i, m), (j, &x)| {
+                if x > m { (j, x) } else { (i, m) }
+            }).0
+        })
+        .collect()
+}
+
+fn max_values(q: &Array2<f64>) -> Array1<f64> {
+    q.fold_axis(Axis(1), ::std::f64::MIN, |&m, &x| m.max(x))
+}
+
+fn max_abs_diff(x: &Array1<f64>, y: &Array1<f64>) -> f64 {
+    x.iter().zip(y.iter()).fold(0.0, |acc, (a, b)| acc.max((a - b).abs()))
+}
+
+import_all!(policy_evaluation);
+import_all!(value_iteration);
+import_all!(policy_iteration);
@@ -0,0 +1,62 @@
+use crate::{domains::FiniteMDP, utils::pinv};
+use ndarray::{Array1, Array2};
+use ndarray_linalg::Solve;
+
+/// Evaluate the policy `pi`, `[S, A]`, exactly by solving the linear system
+/// `(I - γP_π) v = r_π`.
+pub fn evaluate_policy(mdp: &FiniteMDP, pi: &Array2<f64>) -> Array1<f64> {
+    let a = Array2::eye(mdp.n_states()) - mdp.gamma * mdp.policy_transitions(pi);
+    let b = mdp.policy_rewards(pi);
+
+    if let Ok(v) = a.solve(&b) {
+        v
+    } else {
+        // Fall back to the pseudo-inverse when the system is singular:
+        pinv(&a).expect("Failed to solve the policy evaluation system.").dot(&b)
+    }
+}
+
+/// Evaluate the policy `pi`, `[S, A]`, approximately via `n_sweeps`
+/// applications of the Bellman expectation operator, starting from `v`.
+pub fn iterate_policy_evaluation(
+    mdp: &FiniteMDP,
+    pi: &Array2<f64>,
+    v: Array1<f64>,
+    n_sweeps: usize,
+) -> Array1<f64>
+{
+    let p_pi = mdp.policy_transitions(pi);
+    let r_pi = mdp.policy_rewards(pi);
+
+    (0..n_sweeps).fold(v, |v, _| &r_pi + &(mdp.gamma * p_pi.dot(&v)))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domains::CliffWalk;
+    use ndarray::Array2;
+    use super::*;
+
+    #[test]
+    fn test_exact_matches_iterative() {
+        let mdp = CliffWalk::default().to_mdp(0.9);
+        let pi = Array2::from_elem((mdp.n_states(), mdp.n_actions()), 0.25);
+
+        let v_exact = evaluate_policy(&mdp, &pi);
+        let v_iter = iterate_policy_evaluation(&mdp, &pi, Array1::zeros(mdp.n_states()), 500);
+
+        for (x, y) in v_exact.iter().zip(v_iter.iter()) {
+            assert!((x - y).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_terminal_values() {
+        let mdp = CliffWalk::default().to_mdp(0.9);
+        let v = evaluate_policy(&mdp, &mdp.policy_matrix(&vec![0; mdp.n_states()]));
+
+        for s in 1..12 {
+            assert_eq!(v[s], 0.0);
+        }
+    }
+}
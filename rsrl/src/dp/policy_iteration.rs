@@ -0,0 +1,96 @@
+use crate::domains::FiniteMDP;
+use ndarray::{Array1, Array2};
+use super::{Solution, evaluate_policy, iterate_policy_evaluation, max_abs_diff, max_values};
+
+fn improve(q: &Array2<f64>, policy: &[usize]) -> Vec<usize> {
+    let greedy = super::greedy(q);
+
+    // Retain the incumbent action on ties to guarantee termination:
+    policy.iter().zip(greedy.into_iter()).enumerate().map(|(s, (&a, g))| {
+        if q[[s, a]] >= q[[s, g]] - 1e-10 { a } else { g }
+    }).collect()
+}
+
+/// Solve `mdp` by (Howard's) policy iteration with exact policy evaluation.
+///
+/// # References
+/// - Howard, R. A. (1960). Dynamic Programming and Markov Processes. MIT
+/// Press.
+pub fn policy_iteration(mdp: &FiniteMDP, max_iters: usize) -> Solution {
+    let mut policy = vec![0; mdp.n_states()];
+    let mut n_iters = 0;
+
+    loop {
+        let v = evaluate_policy(mdp, &mdp.policy_matrix(&policy));
+        let q = mdp.backup(v.view());
+        let new_policy = improve(&q, &policy);
+
+        n_iters += 1;
+
+        if new_policy == policy || n_iters >= max_iters {
+            return Solution { v, q, policy: new_policy, n_iters, };
+        }
+
+        policy = new_policy;
+    }
+}
+
+/// Solve `mdp` by modified policy iteration, performing `n_sweeps` partial
+/// evaluation sweeps after each greedy improvement step.
+///
+/// # References
+/// - Puterman, M. L., Shin, M. C. (1978). Modified policy iteration algorithms
+/// for discounted Markov decision problems. Management Science, 24(11),
+/// 1127–1137.
+pub fn modified_policy_iteration(
+    mdp: &FiniteMDP,
+    n_sweeps: usize,
+    tol: f64,
+    max_iters: usize,
+) -> Solution
+{
+    let mut v = Array1::zeros(mdp.n_states());
+    let mut n_iters = 0;
+
+    while n_iters < max_iters {
+        let q = mdp.backup(v.view());
+        let u = max_values(&q);
+        let delta = max_abs_diff(&u, &v);
+
+        n_iters += 1;
+
+        if delta < tol {
+            v = u;
+
+            break;
+        }
+
+        let pi = mdp.policy_matrix(&super::greedy(&q));
+
+        v = iterate_policy_evaluation(mdp, &pi, u, n_sweeps);
+    }
+
+    Solution::from_values(mdp, v, n_iters)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{domains::CliffWalk, dp::value_iteration};
+    use super::{modified_policy_iteration, policy_iteration};
+
+    #[test]
+    fn test_agrees_with_value_iteration() {
+        let mdp = CliffWalk::default().to_mdp(0.9);
+
+        let vi = value_iteration(&mdp, 1e-10, 1000);
+        let pi = policy_iteration(&mdp, 100);
+        let mpi = modified_policy_iteration(&mdp, 5, 1e-10, 1000);
+
+        for s in 0..mdp.n_states() {
+            assert!((vi.v[s] - pi.v[s]).abs() < 1e-6);
+            assert!((vi.v[s] - mpi.v[s]).abs() < 1e-6);
+        }
+
+        assert!(pi.n_iters < 100);
+    }
+}
@@ -0,0 +1,44 @@
+use crate::domains::FiniteMDP;
+use ndarray::Array1;
+use super::{Solution, max_abs_diff, max_values};
+
+/// Solve `mdp` by value iteration, stopping once the largest change in the
+/// value function falls below `tol` or after `max_iters` sweeps.
+///
+/// # References
+/// - Bellman, R. (1957). Dynamic Programming. Princeton University Press.
+pub fn value_iteration(mdp: &FiniteMDP, tol: f64, max_iters: usize) -> Solution {
+    let mut v = Array1::zeros(mdp.n_states());
+    let mut n_iters = 0;
+
+    while n_iters < max_iters {
+        let nv = max_values(&mdp.backup(v.view()));
+        let delta = max_abs_diff(&nv, &v);
+
+        v = nv;
+        n_iters += 1;
+
+        if delta < tol {
+            break;
+        }
+    }
+
+    Solution::from_values(mdp, v, n_iters)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domains::CliffWalk;
+    use super::value_iteration;
+
+    #[test]
+    fn test_cliff_walk() {
+        let mdp = CliffWalk::default().to_mdp(0.9);
+        let sol = value_iteration(&mdp, 1e-10, 1000);
+
+        // North once, east eleven times, then south into the goal:
+        assert!((sol.v[0] - 50.0 * 0.9f64.powi(12)).abs() < 1e-6);
+        assert_eq!(sol.policy[0], 0);
+        assert_eq!(sol.policy[23], 2);
+    }
+}
@@ -48,6 +48,31 @@ pub fn run(
     }
 }
 
+/// Helper function for running experiments while logging additional
+/// statistics, such as value-error metrics, computed from the agent after
+/// each episode.
+pub fn run_with<'a, C, D, K: KV>(
+    mut experiment: SerialExperiment<'a, C, D>,
+    n_episodes: usize,
+    logger: Option<Logger>,
+    metrics: impl Fn(&C) -> K,
+) -> Vec<(Episode, K)>
+where
+    SerialExperiment<'a, C, D>: Iterator<Item = Episode>,
+{
+    (1..(n_episodes + 1))
+        .filter_map(|i| experiment.next().map(|res| {
+            let stats = metrics(experiment.agent());
+
+            if let Some(ref logger) = logger {
+                info!(logger, "episode {}", i; &res, &stats);
+            }
+
+            (res, stats)
+        }))
+        .collect()
+}
+
 /// Utility for running a single evaluation episode.
 pub struct Evaluation<'a, C: 'a, D> {
     agent: &'a mut C,
@@ -127,6 +152,11 @@ where
     }
 }
 
+impl<'a, C, D> SerialExperiment<'a, C, D> {
+    /// Return a reference to the agent being trained.
+    pub fn agent(&self) -> &C { self.agent }
+}
+
 impl<'a, S: Space, A: Space, C, D> Iterator for SerialExperiment<'a, C, D>
 where
     C: OnlineLearner<S::Value, A::Value> + Controller<S::Value, A::Value>,
@@ -0,0 +1,256 @@
+use crate::{
+    Shared,
+    fa::{EnumerableStateActionFunction, Parameterised, StateActionFunction},
+};
+use rand::{thread_rng, Rng};
+use rstat::{Distribution, univariate::continuous::Normal};
+
+/// Interface for ensembles of enumerable action-value functions with a
+/// single active head.
+pub trait EnsembleFunction<X: ?Sized>: EnumerableStateActionFunction<X> {
+    /// Return the number of heads in the ensemble.
+    fn n_heads(&self) -> usize;
+
+    /// Return the index of the currently active head.
+    fn active_head(&self) -> usize;
+
+    /// Set the currently active head.
+    fn set_active_head(&mut self, head: usize);
+
+    /// Evaluate every action under a specific head.
+    fn evaluate_head(&self, head: usize, state: &X) -> Vec<f64>;
+
+    /// Update the value of `action` in `state` for each (masked) head with an
+    /// error computed from that head's own action-values.
+    ///
+    /// The closure `error` is called once per head, and is passed a function
+    /// that evaluates every action in a given state under that head.
+    fn update_heads(
+        &mut self,
+        state: &X,
+        action: &usize,
+        error: impl Fn(&dyn Fn(&X) -> Vec<f64>) -> f64,
+    );
+}
+
+impl<X: ?Sized, T: EnsembleFunction<X>> EnsembleFunction<X> for Shared<T> {
+    fn n_heads(&self) -> usize { self.borrow().n_heads() }
+
+    fn active_head(&self) -> usize { self.borrow().active_head() }
+
+    fn set_active_head(&mut self, head: usize) { self.borrow_mut().set_active_head(head) }
+
+    fn evaluate_head(&self, head: usize, state: &X) -> Vec<f64> {
+        self.borrow().evaluate_head(head, state)
+    }
+
+    fn update_heads(
+        &mut self,
+        state: &X,
+        action: &usize,
+        error: impl Fn(&dyn Fn(&X) -> Vec<f64>) -> f64,
+    ) {
+        self.borrow_mut().update_heads(state, action, error)
+    }
+}
+
+/// Bootstrapped ensemble of `K` action-value functions.
+///
+/// Evaluation is delegated to the active head. Each update is applied to every
+/// head independently with probability `p_mask`, such that each head is
+/// trained on a different bootstrap resample of the experience.
+///
+/// Updates made through `EnsembleFunction::update_heads` compute a separate
+/// TD target for each head from its own action-values, as in bootstrapped
+/// DQN; see `BootstrappedQLearning` and `BootstrappedSARSA`. Updates made
+/// through the generic `StateActionFunction` interface instead apply the
+/// error computed from the active head to every masked head, in which case
+/// the heads cannot correct their own estimates.
+///
+/// # References
+/// - Osband, I., Blundell, C., Pritzel, A., Van Roy, B. (2016). Deep
+/// exploration via bootstrapped DQN. In Advances in Neural Information
+/// Processing Systems, pp. 4026–4034.
+#[derive(Clone, Debug)]
+pub struct Ensemble<F> {
+    pub heads: Vec<F>,
+    pub p_mask: f64,
+
+    active: usize,
+}
+
+impl<F> Ensemble<F> {
+    pub fn new(heads: Vec<F>, p_mask: f64) -> Self {
+        if heads.is_empty() {
+            panic!("Ensemble requires at least one head.");
+        }
+
+        Ensemble {
+            heads,
+            p_mask,
+
+            active: 0,
+        }
+    }
+
+    /// Construct an ensemble, perturbing the weights of each head with
+    /// independent zero-mean Gaussian noise of scale `stddev`.
+    pub fn randomised(mut heads: Vec<F>, p_mask: f64, stddev: f64) -> Self
+    where F: Parameterised {
+        let mut rng = thread_rng();
+        let noise = Normal::new(0.0, stddev);
+
+        for head in heads.iter_mut() {
+            head.weights_view_mut().mapv_inplace(|w| w + noise.sample(&mut rng));
+        }
+
+        Ensemble::new(heads, p_mask)
+    }
+
+    /// Return the number of heads in the ensemble.
+    pub fn n_heads(&self) -> usize { self.heads.len() }
+
+    /// Return the index of the currently active head.
+    pub fn active_head(&self) -> usize { self.active }
+
+    /// Set the currently active head.
+    pub fn set_active_head(&mut self, head: usize) {
+        if head >= self.heads.len() {
+            panic!("Head index {} out of bounds for ensemble of size {}.", head, self.heads.len());
+        }
+
+        self.active = head;
+    }
+
+    fn masked_heads(&mut self) -> impl Iterator<Item = &mut F> {
+        let mut rng = thread_rng();
+        let p_mask = self.p_mask;
+
+        self.heads.iter_mut().filter(move |_| rng.gen_bool(p_mask))
+    }
+
+    fn masked_heads_with<T>(&mut self, values: Vec<T>) -> impl Iterator<Item = (&mut F, T)> {
+        let mut rng = thread_rng();
+        let p_mask = self.p_mask;
+
+        self.heads.iter_mut().zip(values).filter(move |_| rng.gen_bool(p_mask))
+    }
+}
+
+impl<X: ?Sized, F: StateActionFunction<X, usize>> StateActionFunction<X, usize> for Ensemble<F>
+where F::Output: Clone
+{
+    type Output = F::Output;
+
+    fn evaluate(&self, state: &X, action: &usize) -> F::Output {
+        self.heads[self.active].evaluate(state, action)
+    }
+
+    fn update(&mut self, state: &X, action: &usize, error: F::Output) {
+        for head in self.masked_heads() {
+            head.update(state, action, error.clone());
+        }
+    }
+}
+
+impl<X: ?Sized, F: EnumerableStateActionFunction<X>> EnumerableStateActionFunction<X>
+    for Ensemble<F>
+{
+    fn n_actions(&self) -> usize { self.heads[0].n_actions() }
+
+    fn evaluate_all(&self, state: &X) -> Vec<f64> { self.heads[self.active].evaluate_all(state) }
+
+    fn update_all(&mut self, state: &X, errors: Vec<f64>) {
+        for head in self.masked_heads() {
+            head.update_all(state, errors.clone());
+        }
+    }
+}
+
+impl<X: ?Sized, F: EnumerableStateActionFunction<X>> EnsembleFunction<X> for Ensemble<F> {
+    fn n_heads(&self) -> usize { Ensemble::n_heads(self) }
+
+    fn active_head(&self) -> usize { Ensemble::active_head(self) }
+
+    fn set_active_head(&mut self, head: usize) { Ensemble::set_active_head(self, head) }
+
+    fn evaluate_head(&self, head: usize, state: &X) -> Vec<f64> {
+        self.heads[head].evaluate_all(state)
+    }
+
+    fn update_heads(
+        &mut self,
+        state: &X,
+        action: &usize,
+        error: impl Fn(&dyn Fn(&X) -> Vec<f64>) -> f64,
+    ) {
+        let errors: Vec<f64> = self.heads.iter()
+            .map(|head| error(&|x: &X| head.evaluate_all(x)))
+            .collect();
+
+        for (head, e) in self.masked_heads_with(errors) {
+            head.update(state, action, e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fa::{
+        EnsembleFunction, EnumerableStateActionFunction, StateActionFunction,
+        tabular::Tabular,
+    };
+    use super::Ensemble;
+
+    fn ensemble(p_mask: f64) -> Ensemble<Tabular> {
+        Ensemble::new(vec![
+            Tabular::new(vec![vec![0.0, 1.0], vec![1.0, 0.0]]),
+            Tabular::new(vec![vec![2.0, 0.0], vec![0.0, 2.0]]),
+        ], p_mask)
+    }
+
+    #[test]
+    fn test_active_head() {
+        let mut q = ensemble(1.0);
+
+        assert_eq!(q.evaluate_all(&0), vec![0.0, 1.0]);
+
+        q.set_active_head(1);
+
+        assert_eq!(q.active_head(), 1);
+        assert_eq!(q.evaluate_all(&0), vec![2.0, 0.0]);
+        assert_eq!(q.heads[0].evaluate_all(&1), vec![1.0, 0.0]);
+    }
+
+    #[test]
+    fn test_masks() {
+        let mut q = ensemble(1.0);
+
+        q.update(&0, &0, 1.0);
+
+        assert_eq!(q.heads[0].evaluate_all(&0), vec![1.0, 1.0]);
+        assert_eq!(q.heads[1].evaluate_all(&0), vec![3.0, 0.0]);
+
+        let mut q = ensemble(0.0);
+
+        q.update_all(&0, vec![1.0, 1.0]);
+
+        assert_eq!(q.heads[0].evaluate_all(&0), vec![0.0, 1.0]);
+        assert_eq!(q.heads[1].evaluate_all(&0), vec![2.0, 0.0]);
+    }
+
+    #[test]
+    fn test_update_heads() {
+        let mut q = ensemble(1.0);
+
+        // Each head moves its own estimate of Q(0, 0) towards a target of 1:
+        q.update_heads(&0, &0, |qs| 1.0 - qs(&0)[0]);
+
+        assert_eq!(q.heads[0].evaluate_all(&0), vec![1.0, 1.0]);
+        assert_eq!(q.heads[1].evaluate_all(&0), vec![1.0, 0.0]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_invalid_head() { ensemble(0.5).set_active_head(2); }
+}
@@ -0,0 +1,227 @@
+use crate::{
+    DerefSlice, Shared,
+    fa::{
+        Weights, WeightsView, WeightsViewMut, Parameterised,
+        StateActionFunction, EnumerableStateActionFunction,
+        linear::basis::Projector,
+    },
+};
+use ndarray::{Array1, Array2, Axis};
+use ndarray_linalg::{UPLO, cholesky::Cholesky};
+use rand::Rng;
+use rstat::{Distribution, univariate::continuous::Normal};
+
+const JITTER: f64 = 1e-9;
+
+/// Interface for action-value functions that maintain a posterior distribution
+/// over their predictions.
+pub trait BayesianStateActionFunction<X: ?Sized>: EnumerableStateActionFunction<X> {
+    /// Return the posterior variance of the value of each action.
+    fn evaluate_variances(&self, state: &X) -> Vec<f64>;
+
+    /// Draw a sample of the value of each action from the posterior marginals.
+    fn sample_all<R: Rng + ?Sized>(&self, rng: &mut R, state: &X) -> Vec<f64> {
+        self.evaluate_all(state).into_iter().zip(self.evaluate_variances(state)).map(|(m, v)| {
+            if v > 0.0 { Normal::new(m, v.sqrt()).sample(rng) } else { m }
+        }).collect()
+    }
+}
+
+impl<X: ?Sized, T: BayesianStateActionFunction<X>> BayesianStateActionFunction<X> for Shared<T> {
+    fn evaluate_variances(&self, state: &X) -> Vec<f64> {
+        self.borrow().evaluate_variances(state)
+    }
+
+    fn sample_all<R: Rng + ?Sized>(&self, rng: &mut R, state: &X) -> Vec<f64> {
+        self.borrow().sample_all(rng, state)
+    }
+}
+
+/// Bayesian linear action-value function.
+///
+/// Each action is modelled by an independent Bayesian linear regression with
+/// isotropic Gaussian prior, `w ~ N(0, σ₀² I)`, and Gaussian observation noise
+/// with variance `σ²`. The posterior covariance is maintained recursively via
+/// the Sherman–Morrison identity, such that each update costs `O(n²)` in the
+/// number of features. This is exactly equivalent to the rank-one precision
+/// update `Σ⁻¹ ← Σ⁻¹ + φφᵀ/σ²`, and hence after `n` observations the posterior
+/// matches the batch solution, `Σ⁻¹ = I/σ₀² + ΦᵀΦ/σ²` and `μ = ΣΦᵀy/σ²`.
+///
+/// The `error` passed to `update` is interpreted as the regression residual,
+/// `y - μᵀφ(x)`, rather than a step in parameter space; learning algorithms
+/// should therefore be run with a unit step size. The posterior mean is exposed
+/// as the weights of the approximator.
+///
+/// # References
+/// - Bishop, C. M. (2006). Pattern Recognition and Machine Learning. Springer,
+/// Section 3.3.
+/// - Azizzadenesheli, K., Brunskill, E., Anandkumar, A. (2018). Efficient
+/// exploration through Bayesian deep Q-networks. In Information Theory and
+/// Applications Workshop, pp. 1–9.
+#[derive(Clone, Debug)]
+pub struct BayesianLFA<B> {
+    pub basis: B,
+    pub noise_variance: f64,
+
+    mean: Array2<f64>,
+    covariances: Vec<Array2<f64>>,
+}
+
+impl<B: Projector> BayesianLFA<B> {
+    pub fn new(basis: B, n_actions: usize, prior_variance: f64, noise_variance: f64) -> Self {
+        let n_features = basis.n_features();
+
+        BayesianLFA {
+            basis,
+            noise_variance,
+
+            mean: Array2::zeros((n_features, n_actions)),
+            covariances: vec![Array2::eye(n_features) * prior_variance; n_actions],
+        }
+    }
+
+    fn phi<X: DerefSlice>(&self, state: &X) -> Array1<f64> {
+        self.basis.project(state.deref_slice()).unwrap().expanded()
+    }
+
+    /// Return the posterior covariance of the weights of `action`.
+    pub fn covariance(&self, action: usize) -> &Array2<f64> { &self.covariances[action] }
+
+    /// Return the posterior variance of `Q(x, u)`.
+    pub fn variance<X: DerefSlice>(&self, state: &X, action: usize) -> f64 {
+        let phi = self.phi(state);
+
+        phi.dot(&self.covariances[action].dot(&phi))
+    }
+
+    /// Draw a full set of weights, `[F, A]`, from the posterior.
+    ///
+    /// Falls back to a diagonal approximation of the covariance if it is not
+    /// numerically positive definite.
+    pub fn sample_weights<R: Rng + ?Sized>(&self, rng: &mut R) -> Array2<f64> {
+        let standard = Normal::new(0.0, 1.0);
+        let mut weights = self.mean.clone();
+
+        for (a, cov) in self.covariances.iter().enumerate() {
+            let z = Array1::from_shape_fn(cov.rows(), |_| standard.sample(rng));
+            let jittered = cov + &(Array2::<f64>::eye(cov.rows()) * JITTER);
+
+            let noise = match jittered.cholesky(UPLO::Lower) {
+                Ok(l) => l.dot(&z),
+                Err(_) => cov.diag().mapv(|v| v.max(0.0).sqrt()) * &z,
+            };
+
+            weights.column_mut(a).scaled_add(1.0, &noise);
+        }
+
+        weights
+    }
+
+    fn observe(&mut self, phi: &Array1<f64>, action: usize, residual: f64) {
+        let cov = &mut self.covariances[action];
+
+        let s_phi = cov.dot(phi);
+        let denom = self.noise_variance + phi.dot(&s_phi);
+
+        self.mean.column_mut(action).scaled_add(residual / denom, &s_phi);
+
+        let s_phi = s_phi.insert_axis(Axis(1));
+
+        cov.scaled_add(-1.0 / denom, &s_phi.dot(&s_phi.t()));
+    }
+}
+
+impl<B> Parameterised for BayesianLFA<B> {
+    fn weights(&self) -> Weights { self.mean.clone() }
+
+    fn weights_view(&self) -> WeightsView { self.mean.view() }
+
+    fn weights_view_mut(&mut self) -> WeightsViewMut { self.mean.view_mut() }
+}
+
+// Q(x, u):
+impl<X: DerefSlice, B: Projector> StateActionFunction<X, usize> for BayesianLFA<B> {
+    type Output = f64;
+
+    fn evaluate(&self, state: &X, action: &usize) -> f64 {
+        self.phi(state).dot(&self.mean.column(*action))
+    }
+
+    fn update(&mut self, state: &X, action: &usize, error: f64) {
+        let phi = self.phi(state);
+
+        self.observe(&phi, *action, error);
+    }
+}
+
+impl<X: DerefSlice, B: Projector> EnumerableStateActionFunction<X> for BayesianLFA<B> {
+    fn n_actions(&self) -> usize { self.covariances.len() }
+
+    fn evaluate_all(&self, state: &X) -> Vec<f64> { self.phi(state).dot(&self.mean).into_raw_vec() }
+
+    fn update_all(&mut self, state: &X, errors: Vec<f64>) {
+        let phi = self.phi(state);
+
+        for (a, e) in errors.into_iter().enumerate() {
+            self.observe(&phi, a, e);
+        }
+    }
+}
+
+impl<X: DerefSlice, B: Projector> BayesianStateActionFunction<X> for BayesianLFA<B> {
+    fn evaluate_variances(&self, state: &X) -> Vec<f64> {
+        let phi = self.phi(state);
+
+        self.covariances.iter().map(|cov| phi.dot(&cov.dot(&phi))).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fa::{
+        StateActionFunction,
+        linear::basis::{Polynomial, Projector},
+    };
+    use ndarray::{Array1, Array2};
+    use super::BayesianLFA;
+
+    #[test]
+    fn test_batch_posterior() {
+        let (prior_variance, noise_variance) = (2.0, 0.5);
+        let mut q = BayesianLFA::new(
+            Polynomial::new(1, 1).with_constant(), 1, prior_variance, noise_variance
+        );
+
+        let xs = vec![vec![0.0], vec![0.5], vec![1.0], vec![-1.0]];
+        let ys = vec![1.0, 2.0, 2.5, -0.5];
+
+        let n_features = q.basis.n_features();
+        let mut phi = Array2::<f64>::zeros((xs.len(), n_features));
+
+        for (i, (x, y)) in xs.iter().zip(ys.iter()).enumerate() {
+            phi.row_mut(i).assign(&q.phi(x));
+
+            let residual = y - q.evaluate(x, &0);
+
+            q.update(x, &0, residual);
+        }
+
+        let precision = Array2::<f64>::eye(n_features) / prior_variance
+            + phi.t().dot(&phi) / noise_variance;
+        let identity = q.covariance(0).dot(&precision);
+
+        for ((i, j), v) in identity.indexed_iter() {
+            let expected = if i == j { 1.0 } else { 0.0 };
+
+            assert!((v - expected).abs() < 1e-9);
+        }
+
+        // Σ⁻¹μ = Φᵀy/σ²:
+        let lhs = precision.dot(&q.mean.column(0));
+        let rhs = phi.t().dot(&Array1::from_vec(ys)) / noise_variance;
+
+        for (l, r) in lhs.iter().zip(rhs.iter()) {
+            assert!((l - r).abs() < 1e-9);
+        }
+    }
+}
@@ -10,9 +10,9 @@ use crate::{
             optim::Optimiser,
         }
     },
-    policies::DifferentiablePolicy,
+    policies::{DifferentiablePolicy, DeterministicPolicy},
 };
-use ndarray::Axis;
+use ndarray::{Array1, Array2, Axis};
 
 #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
 #[derive(Clone, Debug, Parameterised)]
@@ -224,3 +224,111 @@ where
         self.approximator.update(&mut self.optimiser, features, error).ok();
     }
 }
+
+/// Compatible linear critic for deterministic policies.
+///
+/// Represents `Q(x, u) = (u - μ(x)) ∇_θ μ(x)ᵀ w + v(x)`, where the first block
+/// of weights, `w`, parameterises the advantage function and the remaining
+/// weights a state-value baseline over the given basis.
+///
+/// # References
+/// - Silver, D., Lever, G., Heess, N., Degris, T., Wierstra, D., Riedmiller,
+/// M. (2014). Deterministic policy gradient algorithms. In Proceedings of the
+/// 31st International Conference on Machine Learning, pp. 387–395.
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, Parameterised)]
+pub struct DeterministicCFA<P, B, O> {
+    pub policy: P,
+    pub basis: B,
+    pub optimiser: O,
+    #[weights] pub approximator: ScalarFunction,
+}
+
+impl<P: Parameterised, B: Projector, O: Optimiser> DeterministicCFA<P, B, O> {
+    pub fn new(policy: P, basis: B, optimiser: O) -> Self {
+        let wd = policy.weights_dim();
+        let bf = basis.n_features();
+        let approximator = ScalarFunction::zeros(wd[0] * wd[1] + bf);
+
+        DeterministicCFA { policy, basis, optimiser, approximator }
+    }
+
+    /// Return the advantage weights, `w`, with the same shape as the policy
+    /// weights.
+    pub fn advantage_weights(&self) -> Array2<f64> {
+        let pw_dim = self.policy.weights_dim();
+        let n_features = pw_dim[0] * pw_dim[1];
+
+        self.weights_view().slice(s![0..n_features, ..]).to_owned().into_shape(pw_dim).unwrap()
+    }
+}
+
+// Q(x, u):
+impl<X, P, B, O> StateActionFunction<X, f64> for DeterministicCFA<P, B, O>
+where
+    X: DerefSlice,
+    P: DeterministicPolicy<X>,
+    B: Projector,
+    O: Optimiser,
+{
+    type Output = f64;
+
+    fn evaluate(&self, state: &X, action: &f64) -> Self::Output {
+        let features = self.features(state, action);
+
+        self.approximator.evaluate(&features).unwrap()
+    }
+
+    fn update(&mut self, state: &X, action: &f64, error: Self::Output) {
+        let features = self.features(state, action);
+
+        self.approximator.update(&mut self.optimiser, &features, error).ok();
+    }
+}
+
+impl<X, P, B, O> DifferentiableStateActionFunction<X, f64> for DeterministicCFA<P, B, O>
+where
+    X: DerefSlice,
+    P: DeterministicPolicy<X>,
+    B: Projector,
+    O: Optimiser,
+{
+    type Gradient = LFAGradient;
+
+    fn grad(&self, state: &X, action: &f64) -> Self::Gradient {
+        LFAGradient::from_features(
+            [self.n_features(), 1], 0,
+            self.features(state, action)
+        )
+    }
+}
+
+impl<X, P, B, O> LinearStateActionFunction<X, f64> for DeterministicCFA<P, B, O>
+where
+    X: DerefSlice,
+    P: DeterministicPolicy<X>,
+    B: Projector,
+    O: Optimiser,
+{
+    fn n_features(&self) -> usize {
+        let [r, c] = self.approximator.weights_dim();
+
+        r * c
+    }
+
+    fn features(&self, state: &X, action: &f64) -> Features {
+        let du = action - self.policy.mpa(state);
+        let jacobian = self.policy.jacobian(state);
+        let f_policy = Features::Dense(jacobian.iter().map(|j| j * du).collect::<Array1<f64>>());
+
+        f_policy.stack(self.basis.project(state.deref_slice()).unwrap())
+    }
+
+    fn evaluate_features(&self, features: &Features, _: &f64) -> f64 {
+        self.approximator.evaluate(features).unwrap()
+    }
+
+    fn update_features(&mut self, features: &Features, _: &f64, error: f64) {
+        self.approximator.update(&mut self.optimiser, features, error).ok();
+    }
+}
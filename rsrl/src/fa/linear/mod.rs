@@ -45,5 +45,6 @@ pub trait LinearStateActionFunction<X: ?Sized, U: ?Sized>:
 
 import_all!(vanilla);
 import_all!(compatible);
+import_all!(bayesian);
 
 import_all!(shared);
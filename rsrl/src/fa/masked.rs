@@ -0,0 +1,104 @@
+use crate::{
+    domains::ActionMask,
+    fa::{
+        EnumerableStateActionFunction, StateActionFunction,
+        Parameterised, Weights, WeightsView, WeightsViewMut,
+    },
+};
+
+/// Value assigned to illegal actions by `MaskedQ`.
+///
+/// A finite sentinel is used rather than negative infinity, such that an
+/// illegal action with zero probability contributes `0 × ILLEGAL_VALUE = 0` to
+/// an expected backup, rather than `NaN`. Any positive probability on an
+/// illegal action, however, lets the sentinel dominate the expectation.
+pub const ILLEGAL_VALUE: f64 = ::std::f64::MIN;
+
+/// Enumerable action-value function restricted to a set of legal actions.
+///
+/// Illegal actions are assigned the value `ILLEGAL_VALUE`, so that greedy
+/// selection, `find_max` and soft-max style backups only ever consider legal
+/// actions. Updates are passed through to the underlying function unchanged.
+///
+/// Policies that place probability mass independently of the values, such as
+/// the uniform component of `EpsilonGreedy`, would still select illegal
+/// actions. With expectation-based learners (e.g. `ExpectedSARSA` or
+/// `QSigma`), such policies must therefore be wrapped in a `Masked` policy
+/// with the same mask.
+#[derive(Clone, Debug, Parameterised)]
+pub struct MaskedQ<Q, M> {
+    #[weights] pub q_func: Q,
+    pub mask: M,
+}
+
+impl<Q, M> MaskedQ<Q, M> {
+    pub fn new(q_func: Q, mask: M) -> Self { MaskedQ { q_func, mask } }
+}
+
+impl<X, Q, M> StateActionFunction<X, usize> for MaskedQ<Q, M>
+where
+    Q: StateActionFunction<X, usize, Output = f64>,
+    M: ActionMask<X>,
+{
+    type Output = f64;
+
+    fn evaluate(&self, state: &X, action: &usize) -> f64 {
+        if self.mask.legal_actions(state)[*action] {
+            self.q_func.evaluate(state, action)
+        } else {
+            ILLEGAL_VALUE
+        }
+    }
+
+    fn update(&mut self, state: &X, action: &usize, error: f64) {
+        self.q_func.update(state, action, error)
+    }
+}
+
+impl<X, Q, M> EnumerableStateActionFunction<X> for MaskedQ<Q, M>
+where
+    Q: EnumerableStateActionFunction<X>,
+    M: ActionMask<X>,
+{
+    fn n_actions(&self) -> usize { self.q_func.n_actions() }
+
+    fn evaluate_all(&self, state: &X) -> Vec<f64> {
+        self.q_func.evaluate_all(state).into_iter()
+            .zip(self.mask.legal_actions(state))
+            .map(|(q, legal)| if legal { q } else { ILLEGAL_VALUE })
+            .collect()
+    }
+
+    fn update_all(&mut self, state: &X, errors: Vec<f64>) { self.q_func.update_all(state, errors) }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        control::td::ExpectedSARSA,
+        fa::{EnumerableStateActionFunction, mocking::MockQ},
+        policies::{EpsilonGreedy, Greedy, Masked, Random},
+        prediction::ValuePredictor,
+    };
+    use super::{MaskedQ, ILLEGAL_VALUE};
+
+    fn mask(_: &Vec<f64>) -> Vec<bool> { vec![true, false, true] }
+
+    #[test]
+    fn test_find_max() {
+        let q = MaskedQ::new(MockQ::new(None), mask);
+
+        assert_eq!(q.evaluate_all(&vec![1.0, 5.0, 2.0]), vec![1.0, ILLEGAL_VALUE, 2.0]);
+        assert_eq!(q.find_max(&vec![1.0, 5.0, 2.0]), (2, 2.0));
+    }
+
+    #[test]
+    fn test_expected_backup() {
+        let q = MaskedQ::new(MockQ::new_shared(Some(vec![1.0, 5.0, 2.0])), mask);
+        let p = EpsilonGreedy::new(Greedy::new(q.clone()), Random::new(3), 0.5);
+        let agent = ExpectedSARSA::new(q, Masked::new(p, mask), 1.0, 1.0);
+
+        // The masked probabilities are [0.2, 0.0, 0.8]:
+        assert!((agent.predict_v(&vec![0.0; 3]) - 1.8).abs() < 1e-7);
+    }
+}
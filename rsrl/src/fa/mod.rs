@@ -9,6 +9,9 @@ pub mod transforms;
 import_all!(transformed);
 
 import_all!(shared);
+import_all!(ensemble);
+import_all!(stacked);
+import_all!(masked);
 
 pub use self::linear::{Parameterised, Weights, WeightsView, WeightsViewMut};
 
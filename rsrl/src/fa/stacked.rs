@@ -0,0 +1,280 @@
+use crate::fa::{Parameterised, Weights, WeightsView, WeightsViewMut};
+use ndarray::{Array2, Axis};
+use std::cell::{Cell, Ref, RefCell};
+
+/// Interface for collections of parameterised components whose weights can be
+/// stacked into a single matrix.
+///
+/// Components with an empty weight matrix are skipped entirely. The stacked
+/// weights are gathered from copies of each component's weights, so mutable
+/// views are only requested to flush writes made through
+/// `Stacked::weights_view_mut`.
+pub trait Stackable {
+    /// Return the weight dimensions of each component.
+    fn component_dims(&self) -> Vec<[usize; 2]>;
+
+    /// Return a copy of the weights of component `i`.
+    fn component_weights(&self, i: usize) -> Weights;
+
+    /// Return a mutable view over the weights of component `i`.
+    fn component_view_mut(&mut self, i: usize) -> WeightsViewMut;
+}
+
+impl<T: Parameterised> Stackable for Vec<T> {
+    fn component_dims(&self) -> Vec<[usize; 2]> {
+        self.iter().map(|c| c.weights_dim()).collect()
+    }
+
+    fn component_weights(&self, i: usize) -> Weights { self[i].weights() }
+
+    fn component_view_mut(&mut self, i: usize) -> WeightsViewMut { self[i].weights_view_mut() }
+}
+
+macro_rules! impl_stackable_tuple {
+    ($($idx:tt => $t:ident),+) => {
+        impl<$($t: Parameterised),+> Stackable for ($($t,)+) {
+            fn component_dims(&self) -> Vec<[usize; 2]> {
+                vec![$(self.$idx.weights_dim()),+]
+            }
+
+            fn component_weights(&self, i: usize) -> Weights {
+                match i {
+                    $($idx => self.$idx.weights(),)+
+                    _ => panic!("Component index {} out of bounds.", i),
+                }
+            }
+
+            fn component_view_mut(&mut self, i: usize) -> WeightsViewMut {
+                match i {
+                    $($idx => self.$idx.weights_view_mut(),)+
+                    _ => panic!("Component index {} out of bounds.", i),
+                }
+            }
+        }
+    };
+}
+
+impl_stackable_tuple!(0 => A, 1 => B);
+impl_stackable_tuple!(0 => A, 1 => B, 2 => C);
+impl_stackable_tuple!(0 => A, 1 => B, 2 => C, 3 => D);
+
+/// Components of a composite model, with their weights stacked along an axis.
+///
+/// Each component owns its own weights, so a contiguous copy of the stacked
+/// matrix is maintained to serve `weights_view` and `weights_view_mut`. Along
+/// `axis`, the components are concatenated; in the other dimension, smaller
+/// components are padded with zeros. Writes through `weights_view_mut` are
+/// flushed back into the components lazily, before they are next accessed.
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug)]
+pub struct Stacked<T> {
+    components: RefCell<T>,
+
+    axis: usize,
+    buffer: Array2<f64>,
+    stale: Cell<bool>,
+}
+
+impl<T: Stackable> Stacked<T> {
+    pub fn new(components: T, axis: Axis) -> Self {
+        assert!(axis.index() < 2, "Weights may only be stacked along axis 0 or 1.");
+
+        let mut stacked = Stacked {
+            components: RefCell::new(components),
+
+            axis: axis.index(),
+            buffer: Array2::zeros((0, 0)),
+            stale: Cell::new(false),
+        };
+
+        stacked.gather();
+        stacked
+    }
+
+    /// Return a reference to the components.
+    pub fn get(&self) -> Ref<T> {
+        self.flush();
+        self.components.borrow()
+    }
+
+    /// Apply a closure to the components, keeping the stacked weights in sync.
+    pub fn modify<R>(&mut self, f: impl FnOnce(&mut T) -> R) -> R {
+        self.flush();
+
+        let out = f(self.components.get_mut());
+
+        self.gather();
+        out
+    }
+
+    /// Apply a closure that modifies only component `i`, such that only its
+    /// slice of the stacked weights needs to be refreshed.
+    ///
+    /// Changes made by `f` to any other component are not reflected in the
+    /// stacked weights. If the dimensions of component `i` change, the whole
+    /// matrix is restacked.
+    pub fn modify_component<R>(&mut self, i: usize, f: impl FnOnce(&mut T) -> R) -> R {
+        self.flush();
+
+        let ranges = self.component_ranges();
+        let out = f(self.components.get_mut());
+        let ([r0, r1], [c0, c1]) = ranges[i];
+
+        if self.components.get_mut().component_dims()[i] != [r1 - r0, c1 - c0] {
+            self.gather();
+        } else if r1 > r0 && c1 > c0 {
+            let weights = self.components.get_mut().component_weights(i);
+
+            self.buffer.slice_mut(s![r0..r1, c0..c1]).assign(&weights);
+        }
+
+        out
+    }
+
+    /// Consume the wrapper and return the components.
+    pub fn into_inner(self) -> T {
+        self.flush();
+        self.components.into_inner()
+    }
+
+    /// Return the range of rows and columns occupied by each component.
+    pub fn component_ranges(&self) -> Vec<([usize; 2], [usize; 2])> {
+        let mut offset = 0;
+
+        self.components.borrow().component_dims().into_iter().map(|[r, c]| {
+            let (start, len) = (offset, if self.axis == 0 { r } else { c });
+
+            offset += len;
+
+            if self.axis == 0 {
+                ([start, start + r], [0, c])
+            } else {
+                ([0, r], [start, start + c])
+            }
+        }).collect()
+    }
+
+    // Copy the weights of every component into the buffer.
+    fn gather(&mut self) {
+        let ranges = self.component_ranges();
+        let dim = ranges.iter().fold([0, 0], |[r, c], &([_, r1], [_, c1])| {
+            [r.max(r1), c.max(c1)]
+        });
+
+        let components = self.components.get_mut();
+        let mut buffer = Array2::zeros(dim);
+
+        for (i, ([r0, r1], [c0, c1])) in ranges.into_iter().enumerate() {
+            if r1 > r0 && c1 > c0 {
+                buffer.slice_mut(s![r0..r1, c0..c1]).assign(&components.component_weights(i));
+            }
+        }
+
+        self.buffer = buffer;
+        self.stale.set(false);
+    }
+
+    // Copy any pending writes from the buffer back into the components.
+    fn flush(&self) {
+        if !self.stale.get() {
+            return;
+        }
+
+        let ranges = self.component_ranges();
+        let mut components = self.components.borrow_mut();
+
+        for (i, ([r0, r1], [c0, c1])) in ranges.into_iter().enumerate() {
+            if r1 > r0 && c1 > c0 {
+                components.component_view_mut(i).assign(&self.buffer.slice(s![r0..r1, c0..c1]));
+            }
+        }
+
+        self.stale.set(false);
+    }
+}
+
+impl<T: Stackable> Parameterised for Stacked<T> {
+    fn weights(&self) -> Weights { self.buffer.clone() }
+
+    fn weights_view(&self) -> WeightsView { self.buffer.view() }
+
+    fn weights_view_mut(&mut self) -> WeightsViewMut {
+        self.flush();
+        self.stale.set(true);
+
+        self.buffer.view_mut()
+    }
+
+    fn weights_dim(&self) -> [usize; 2] {
+        let (r, c) = self.buffer.dim();
+
+        [r, c]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fa::{
+        Parameterised,
+        linear::{LFA, basis::{Polynomial, Projector}, optim::SGD},
+    };
+    use ndarray::Axis;
+    use super::Stacked;
+
+    #[test]
+    fn test_stack_columns() {
+        let basis = Polynomial::new(1, 1).with_constant();
+        let n = basis.n_features();
+
+        let mut s = Stacked::new(vec![
+            LFA::scalar(basis.clone(), SGD(1.0)),
+            LFA::vector(basis, SGD(1.0), 2),
+        ], Axis(1));
+
+        assert_eq!(s.weights_dim(), [n, 3]);
+
+        s.weights_view_mut().fill(1.0);
+
+        let cs = s.get();
+
+        assert!(cs[0].weights().iter().all(|&w| w == 1.0));
+        assert!(cs[1].weights().iter().all(|&w| w == 1.0));
+    }
+
+    #[test]
+    fn test_stack_rows() {
+        let basis = Polynomial::new(1, 1).with_constant();
+        let n = basis.n_features();
+
+        let mut s = Stacked::new((
+            LFA::scalar(basis.clone(), SGD(1.0)),
+            LFA::scalar(basis, SGD(1.0)),
+        ), Axis(0));
+
+        assert_eq!(s.weights_dim(), [2 * n, 1]);
+
+        s.modify(|cs| cs.1.weights_view_mut().fill(2.0));
+
+        let w = s.weights_view().column(0).to_vec();
+
+        assert!(w[..n].iter().all(|&w| w == 0.0));
+        assert!(w[n..].iter().all(|&w| w == 2.0));
+    }
+
+    #[test]
+    fn test_modify_component() {
+        let basis = Polynomial::new(1, 1).with_constant();
+        let n = basis.n_features();
+
+        let mut s = Stacked::new((
+            LFA::scalar(basis.clone(), SGD(1.0)),
+            LFA::vector(basis, SGD(1.0), 2),
+        ), Axis(1));
+
+        s.modify_component(1, |cs| cs.1.weights_view_mut().fill(3.0));
+
+        assert_eq!(s.weights_view().column(0).to_vec(), vec![0.0; n]);
+        assert_eq!(s.weights_view().column(1).to_vec(), vec![3.0; n]);
+        assert_eq!(s.weights_view().column(2).to_vec(), vec![3.0; n]);
+    }
+}
@@ -35,7 +35,11 @@ pub extern crate spaces;
 
 #[macro_use]
 pub mod fa;
+pub mod bandits;
+pub mod bbo;
 pub mod control;
+pub mod dp;
+pub mod metrics;
 pub mod policies;
 pub mod prediction;
 pub mod traces;
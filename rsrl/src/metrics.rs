@@ -0,0 +1,250 @@
+//! Value-error metrics for linear predictors on finite MDPs.
+use crate::{
+    domains::FiniteMDP,
+    dp::evaluate_policy,
+    fa::{Parameterised, linear::LinearStateFunction},
+    utils::pinv,
+};
+use ndarray::{Array1, Array2, ArrayView1, Axis};
+use ndarray_linalg::Solve;
+use slog::{Record, Result as LogResult, Serializer, KV};
+
+const N_POWER_ITERS: usize = 10_000;
+
+/// Construct the feature matrix, `Φ`, whose `i`th row is the feature vector of
+/// `states[i]`.
+pub fn feature_matrix<X, F: LinearStateFunction<X>>(fa: &F, states: &[X]) -> Array2<f64> {
+    let mut phi = Array2::zeros((states.len(), fa.n_features()));
+
+    for (mut row, s) in phi.outer_iter_mut().zip(states.iter()) {
+        row.assign(&fa.features(s).expanded());
+    }
+
+    phi
+}
+
+/// Compute the on-policy state distribution of `pi`, `[S, A]`.
+///
+/// For episodic MDPs this is the normalised expected number of visits to each
+/// non-terminal state, `d0 (I - P_π)^{-1}`; for continuing MDPs it is the
+/// stationary distribution of `P_π`.
+pub fn on_policy_distribution(mdp: &FiniteMDP, pi: &Array2<f64>) -> Array1<f64> {
+    let p_pi = mdp.policy_transitions(pi);
+
+    let mut d = if mdp.terminals.iter().any(|&t| t) {
+        let a = (Array2::eye(mdp.n_states()) - &p_pi).t().to_owned();
+
+        a.solve(&mdp.initial).unwrap_or_else(|_| {
+            pinv(&a).expect("Failed to compute expected visit counts.").dot(&mdp.initial)
+        })
+    } else {
+        // Power iteration on the lazy chain, which shares the stationary
+        // distribution of `P_π` but is guaranteed to be aperiodic:
+        (0..N_POWER_ITERS).fold(mdp.initial.clone(), |d, _| 0.5 * (p_pi.t().dot(&d) + &d))
+    };
+
+    for s in (0..mdp.n_states()).filter(|&s| mdp.terminals[s]) {
+        d[s] = 0.0;
+    }
+
+    let z = d.sum();
+
+    d / z
+}
+
+/// Container for the value-error metrics of a linear predictor.
+#[derive(Debug, Clone, Copy)]
+pub struct ValueErrors {
+    /// Mean squared value error.
+    pub msve: f64,
+
+    /// Mean squared projected Bellman error.
+    pub mspbe: f64,
+
+    /// Mean squared Bellman error.
+    pub msbe: f64,
+}
+
+impl KV for ValueErrors {
+    fn serialize(&self, _: &Record, serializer: &mut dyn Serializer) -> LogResult {
+        serializer.emit_f64("msve", self.msve)?;
+        serializer.emit_f64("mspbe", self.mspbe)?;
+        serializer.emit_f64("msbe", self.msbe)?;
+
+        Ok(())
+    }
+}
+
+/// Evaluator for the value-error metrics of a linear predictor of the policy
+/// `pi` on a finite MDP, weighted by a state distribution `d`.
+///
+/// The distribution may be the on-policy distribution of `pi` or, for
+/// off-policy evaluation, that of some behaviour policy.
+///
+/// # References
+/// - Sutton, R. S., Maei, H. R., Precup, D., Bhatnagar, S., Silver, D.,
+/// Szepesvári, Cs. & Wiewiora, E. (2009). Fast gradient-descent methods for
+/// temporal-difference learning with linear function approximation. In
+/// Proceedings of the 26th Annual International Conference on Machine
+/// Learning, pp. 993–1000.
+pub struct ValueErrorEvaluator {
+    phi: Array2<f64>,
+    d: Array1<f64>,
+    terminals: Vec<bool>,
+
+    gamma: f64,
+    v_pi: Array1<f64>,
+    p_pi: Array2<f64>,
+    r_pi: Array1<f64>,
+
+    // Pseudo-inverse of Φᵀ D Φ:
+    c_inv: Array2<f64>,
+}
+
+impl ValueErrorEvaluator {
+    pub fn new(mdp: &FiniteMDP, pi: &Array2<f64>, phi: Array2<f64>, d: Array1<f64>) -> Self {
+        if phi.rows() != mdp.n_states() || d.len() != mdp.n_states() {
+            panic!("Feature matrix and distribution must cover all {} states.", mdp.n_states());
+        }
+
+        let c = phi.t().dot(&(&phi * &d.view().insert_axis(Axis(1))));
+        let c_inv = pinv(&c).expect("Failed to invert the feature covariance matrix.");
+
+        ValueErrorEvaluator {
+            phi,
+            d,
+            terminals: mdp.terminals.clone(),
+
+            gamma: mdp.gamma,
+            v_pi: evaluate_policy(mdp, pi),
+            p_pi: mdp.policy_transitions(pi),
+            r_pi: mdp.policy_rewards(pi),
+
+            c_inv,
+        }
+    }
+
+    /// Construct an evaluator using the on-policy distribution of `pi`.
+    pub fn on_policy(mdp: &FiniteMDP, pi: &Array2<f64>, phi: Array2<f64>) -> Self {
+        let d = on_policy_distribution(mdp, pi);
+
+        ValueErrorEvaluator::new(mdp, pi, phi, d)
+    }
+
+    /// Return the true value function of the target policy.
+    pub fn true_values(&self) -> ArrayView1<f64> { self.v_pi.view() }
+
+    fn predictions(&self, w: ArrayView1<f64>) -> Array1<f64> {
+        let mut v = self.phi.dot(&w);
+
+        for s in (0..v.len()).filter(|&s| self.terminals[s]) {
+            v[s] = 0.0;
+        }
+
+        v
+    }
+
+    fn bellman_residual(&self, v: &Array1<f64>) -> Array1<f64> {
+        &self.r_pi + &(self.gamma * self.p_pi.dot(v)) - v
+    }
+
+    /// Compute the mean squared value error of the weights `w`.
+    pub fn msve(&self, w: ArrayView1<f64>) -> f64 {
+        let err = &self.v_pi - &self.predictions(w);
+
+        self.d.dot(&(&err * &err))
+    }
+
+    /// Compute the mean squared projected Bellman error of the weights `w`.
+    pub fn mspbe(&self, w: ArrayView1<f64>) -> f64 {
+        let delta = self.bellman_residual(&self.predictions(w));
+        let b = self.phi.t().dot(&(&self.d * &delta));
+
+        b.dot(&self.c_inv.dot(&b))
+    }
+
+    /// Compute the mean squared Bellman error of the weights `w`.
+    pub fn msbe(&self, w: ArrayView1<f64>) -> f64 {
+        let delta = self.bellman_residual(&self.predictions(w));
+
+        self.d.dot(&(&delta * &delta))
+    }
+
+    /// Compute all value-error metrics of the weights `w`.
+    pub fn evaluate(&self, w: ArrayView1<f64>) -> ValueErrors {
+        ValueErrors {
+            msve: self.msve(w),
+            mspbe: self.mspbe(w),
+            msbe: self.msbe(w),
+        }
+    }
+
+    /// Compute all value-error metrics of a linear learner's weights.
+    pub fn evaluate_learner<L: Parameterised>(&self, learner: &L) -> ValueErrors {
+        self.evaluate(learner.weights_view().column(0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domains::FiniteMDP;
+    use ndarray::{Array2, Array3};
+    use super::{ValueErrorEvaluator, on_policy_distribution};
+
+    fn chain() -> FiniteMDP {
+        let mut p = Array3::zeros((3, 1, 3));
+
+        p[[0, 0, 1]] = 1.0;
+        p[[1, 0, 2]] = 1.0;
+        p[[2, 0, 2]] = 1.0;
+
+        FiniteMDP::new(p, array![[1.0], [1.0], [0.0]], 0.5)
+            .with_terminals(vec![false, false, true])
+            .with_initial(array![1.0, 0.0, 0.0])
+    }
+
+    #[test]
+    fn test_on_policy_distribution() {
+        let mdp = chain();
+        let d = on_policy_distribution(&mdp, &Array2::ones((3, 1)));
+
+        assert!((d[0] - 0.5).abs() < 1e-7);
+        assert!((d[1] - 0.5).abs() < 1e-7);
+        assert_eq!(d[2], 0.0);
+    }
+
+    #[test]
+    fn test_tabular_errors() {
+        let mdp = chain();
+        let pi = Array2::ones((3, 1));
+        let ev = ValueErrorEvaluator::on_policy(&mdp, &pi, Array2::eye(3));
+
+        let exact = ev.evaluate(array![1.5, 1.0, 0.0].view());
+
+        assert!(exact.msve < 1e-10);
+        assert!(exact.mspbe < 1e-10);
+        assert!(exact.msbe < 1e-10);
+
+        let errors = ev.evaluate(array![0.0, 0.0, 0.0].view());
+
+        assert!((errors.msve - 0.5 * (1.5f64.powi(2) + 1.0)).abs() < 1e-7);
+        assert!((errors.msbe - 1.0).abs() < 1e-7);
+
+        // With tabular features the projection is the identity:
+        assert!((errors.mspbe - errors.msbe).abs() < 1e-7);
+    }
+
+    #[test]
+    fn test_aggregated_features() {
+        let mdp = chain();
+        let pi = Array2::ones((3, 1));
+        let ev = ValueErrorEvaluator::on_policy(&mdp, &pi, array![[1.0], [1.0], [0.0]]);
+
+        // The TD fixed point minimises the MSPBE, but not the MSVE:
+        let w_td = array![1.0 / 0.75];
+        let errors = ev.evaluate(w_td.view());
+
+        assert!(errors.mspbe < 1e-10);
+        assert!(errors.msve > 0.0);
+    }
+}
@@ -3,44 +3,99 @@ extern crate special_fun;
 use crate::{
     fa::{
         StateFunction, DifferentiableStateFunction,
-        Parameterised, Weights, WeightsView, WeightsViewMut,
+        Parameterised, Stacked, Weights, WeightsView, WeightsViewMut,
     },
-    policies::{DifferentiablePolicy, Policy},
+    policies::{DifferentiablePolicy, Divergence, Entropy, Policy},
 };
 use ndarray::{Array2, ArrayView2, Axis};
 use rand::Rng;
-use rstat::{
-    Distribution, ContinuousDistribution,
-    core::Modes,
-    univariate::{UnivariateMoments, continuous::Beta as BetaDist},
-};
-use std::ops::AddAssign;
+use rstat::{Distribution, ContinuousDistribution, univariate::continuous::Beta as BetaDist};
+use std::{cell::Ref, ops::AddAssign};
 
 const MIN_TOL: f64 = 1.0;
 
+/// Return the mode of a Beta distribution with shape parameters `alpha` and
+/// `beta`.
+///
+/// For `alpha, beta > 1` this is the unique interior mode. Otherwise, the
+/// density is maximised at (or diverges towards) a boundary of the support; if
+/// both boundaries qualify, the one with the stronger singularity is chosen.
+/// The uniform case, `alpha = beta = 1`, returns the midpoint.
+pub fn beta_mode(alpha: f64, beta: f64) -> f64 {
+    if alpha > 1.0 && beta > 1.0 {
+        (alpha - 1.0) / (alpha + beta - 2.0)
+    } else if alpha == 1.0 && beta == 1.0 {
+        0.5
+    } else if alpha < 1.0 && beta < 1.0 {
+        if alpha <= beta { 0.0 } else { 1.0 }
+    } else if beta < 1.0 && alpha >= 1.0 {
+        1.0
+    } else if alpha <= 1.0 {
+        0.0
+    } else {
+        1.0
+    }
+}
+
+/// Return the KL divergence between two Beta distributions, `KL(p || q)`, with
+/// shape parameters `(alpha_p, beta_p)` and `(alpha_q, beta_q)`, respectively.
+pub fn beta_kl(alpha_p: f64, beta_p: f64, alpha_q: f64, beta_q: f64) -> f64 {
+    use special_fun::FloatSpecial;
+
+    let log_beta = |a: f64, b: f64| a.loggamma() + b.loggamma() - (a + b).loggamma();
+
+    log_beta(alpha_q, beta_q) - log_beta(alpha_p, beta_p)
+        + (alpha_p - alpha_q) * alpha_p.digamma()
+        + (beta_p - beta_q) * beta_p.digamma()
+        + (alpha_q - alpha_p + beta_q - beta_p) * (alpha_p + beta_p).digamma()
+}
+
+/// Beta policy for actions bounded in the unit interval.
+///
+/// The shape parameters are given by two state functions, offset by a minimum
+/// tolerance. Their weights are stacked row-wise, `[alpha; beta]`.
+///
+/// The function approximators are held by the stacked weights, rather than
+/// exposed as public fields, so that `weights_view_mut` can be served from a
+/// single matrix. Read them with `alpha()` and `beta()`, and modify them with
+/// `modify`; both must therefore implement `Parameterised`.
 #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
 #[derive(Clone, Debug)]
 pub struct Beta<A, B = A> {
-    pub alpha: A,
-    pub beta: B,
+    params: Stacked<(A, B)>,
 }
 
-impl<A, B> Beta<A, B> {
+impl<A: Parameterised, B: Parameterised> Beta<A, B> {
     pub fn new(alpha: A, beta: B) -> Self {
         Beta {
-            alpha,
-            beta,
+            params: Stacked::new((alpha, beta), Axis(0)),
         }
     }
 
+    /// Return a reference to the function approximator for `alpha`.
+    pub fn alpha(&self) -> Ref<A> { Ref::map(self.params.get(), |p| &p.0) }
+
+    /// Return a reference to the function approximator for `beta`.
+    pub fn beta(&self) -> Ref<B> { Ref::map(self.params.get(), |p| &p.1) }
+
+    /// Apply a closure to the function approximators for `alpha` and `beta`,
+    /// keeping the stacked weights in sync.
+    pub fn modify<R>(&mut self, f: impl FnOnce(&mut A, &mut B) -> R) -> R {
+        self.params.modify(|p| f(&mut p.0, &mut p.1))
+    }
+
+    /// Consume the policy and return the function approximators for `alpha`
+    /// and `beta`.
+    pub fn into_inner(self) -> (A, B) { self.params.into_inner() }
+
     #[inline]
     pub fn compute_alpha<S>(&self, s: &S) -> f64 where A: StateFunction<S, Output = f64> {
-        self.alpha.evaluate(s) + MIN_TOL
+        self.alpha().evaluate(s) + MIN_TOL
     }
 
     #[inline]
     pub fn compute_beta<S>(&self, s: &S) -> f64 where B: StateFunction<S, Output = f64> {
-        self.beta.evaluate(s) + MIN_TOL
+        self.beta().evaluate(s) + MIN_TOL
     }
 
     #[inline]
@@ -70,8 +125,8 @@ impl<A, B> Beta<A, B> {
 
 impl<S, A, B> Policy<S> for Beta<A, B>
 where
-    A: StateFunction<S, Output = f64>,
-    B: StateFunction<S, Output = f64>,
+    A: StateFunction<S, Output = f64> + Parameterised,
+    B: StateFunction<S, Output = f64> + Parameterised,
 {
     type Action = f64;
 
@@ -80,36 +135,64 @@ where
     }
 
     fn mpa(&self, input: &S) -> f64 {
-        let d = self.dist(input);
-        let modes = d.modes();
-
-        if modes.len() == 0 { d.mean() } else { modes[0] }
+        beta_mode(self.compute_alpha(input), self.compute_beta(input))
     }
 
     fn probability(&self, input: &S, a: &f64) -> f64 {
         self.dist(input).pdf(*a)
     }
-}
 
-impl<A: Parameterised, B: Parameterised> Parameterised for Beta<A, B> {
-    fn weights(&self) -> Weights {
-        stack![Axis(0), self.alpha.weights(), self.beta.weights()]
+    fn log_probability(&self, input: &S, a: &f64) -> f64 {
+        use special_fun::FloatSpecial;
+
+        let alpha = self.compute_alpha(input);
+        let beta = self.compute_beta(input);
+
+        (alpha - 1.0) * a.ln() + (beta - 1.0) * (1.0 - a).ln()
+            - alpha.loggamma() - beta.loggamma() + (alpha + beta).loggamma()
     }
+}
 
-    fn weights_view(&self) -> WeightsView {
-        unimplemented!()
+impl<S, A, B> Entropy<S> for Beta<A, B>
+where
+    A: StateFunction<S, Output = f64> + Parameterised,
+    B: StateFunction<S, Output = f64> + Parameterised,
+{
+    fn entropy(&self, input: &S) -> f64 {
+        use special_fun::FloatSpecial;
+
+        let alpha = self.compute_alpha(input);
+        let beta = self.compute_beta(input);
+        let apb = alpha + beta;
+
+        alpha.loggamma() + beta.loggamma() - apb.loggamma()
+            - (alpha - 1.0) * alpha.digamma()
+            - (beta - 1.0) * beta.digamma()
+            + (apb - 2.0) * apb.digamma()
     }
+}
 
-    fn weights_view_mut(&mut self) -> WeightsViewMut {
-        unimplemented!()
+impl<S, A, B> Divergence<S> for Beta<A, B>
+where
+    A: StateFunction<S, Output = f64> + Parameterised,
+    B: StateFunction<S, Output = f64> + Parameterised,
+{
+    fn kl_divergence(&self, other: &Self, input: &S) -> f64 {
+        beta_kl(
+            self.compute_alpha(input), self.compute_beta(input),
+            other.compute_alpha(input), other.compute_beta(input),
+        )
     }
+}
 
-    fn weights_dim(&self) -> [usize; 2] {
-        let [ra, _] = self.alpha.weights_dim();
-        let [rb, _] = self.beta.weights_dim();
+impl<A: Parameterised, B: Parameterised> Parameterised for Beta<A, B> {
+    fn weights(&self) -> Weights { self.params.weights() }
 
-        [ra + rb, 1]
-    }
+    fn weights_view(&self) -> WeightsView { self.params.weights_view() }
+
+    fn weights_view_mut(&mut self) -> WeightsViewMut { self.params.weights_view_mut() }
+
+    fn weights_dim(&self) -> [usize; 2] { self.params.weights_dim() }
 }
 
 impl<S, A, B> DifferentiablePolicy<S> for Beta<A, B>
@@ -118,60 +201,130 @@ where
     B: DifferentiableStateFunction<S, Output = f64> + Parameterised,
 {
     fn update(&mut self, state: &S, a: &f64, error: f64) {
-        let val_alpha = self.alpha.evaluate(state) + MIN_TOL;
-        let val_beta = self.beta.evaluate(state) + MIN_TOL;
+        let val_alpha = self.compute_alpha(state);
+        let val_beta = self.compute_beta(state);
 
         let [gl_alpha, gl_beta] = self.gl_partial(val_alpha, val_beta, *a);
 
-        self.alpha.update(state, gl_alpha * error);
-        self.beta.update(state, gl_beta * error);
+        self.params.modify(|p| {
+            p.0.update(state, gl_alpha * error);
+            p.1.update(state, gl_beta * error);
+        })
     }
 
     fn update_grad(&mut self, grad: &ArrayView2<f64>) {
-        match self.alpha.weights_dim() {
-            [r, _] if r > 0 => {
-                let grad_alpha = grad.slice(s![0..r, ..]);
-                self.alpha.weights_view_mut().add_assign(&grad_alpha);
-            },
-            _ => {},
-        }
-
-        match self.beta.weights_dim() {
-            [r, _] if r > 0 => {
-                let grad_beta = grad.slice(s![self.beta.weights_dim()[0].., ..]);
-                self.beta.weights_view_mut().add_assign(&grad_beta);
-            },
-            _ => {},
-        }
+        self.params.weights_view_mut().add_assign(grad);
     }
 
     fn update_grad_scaled(&mut self, grad: &ArrayView2<f64>, factor: f64) {
-        match self.alpha.weights_dim() {
-            [r, _] if r > 0 => {
-                let grad_alpha = grad.slice(s![0..self.alpha.weights_dim()[0], ..]);
-                self.alpha.weights_view_mut().scaled_add(factor, &grad_alpha);
-            },
-            _ => {},
-        }
-
-        match self.beta.weights_dim() {
-            [r, _] if r > 0 => {
-                let grad_beta = grad.slice(s![self.beta.weights_dim()[0].., ..]);
-                self.beta.weights_view_mut().scaled_add(factor, &grad_beta);
-            },
-            _ => {},
-        }
+        self.params.weights_view_mut().scaled_add(factor, grad);
     }
 
     fn grad_log(&self, state: &S, a: &f64) -> Array2<f64> {
-        let val_alpha = self.alpha.evaluate(state) + MIN_TOL;
-        let grad_alpha: Array2<f64> = self.alpha.grad(state).into();
-
-        let val_beta = self.beta.evaluate(state) + MIN_TOL;
-        let grad_beta: Array2<f64> = self.beta.grad(state).into();
+        let val_alpha = self.compute_alpha(state);
+        let val_beta = self.compute_beta(state);
 
         let [gl_alpha, gl_beta] = self.gl_partial(val_alpha, val_beta, *a);
 
+        let p = self.params.get();
+        let grad_alpha: Array2<f64> = p.0.grad(state).into();
+        let grad_beta: Array2<f64> = p.1.grad(state).into();
+
         stack![Axis(0), gl_alpha * grad_alpha, gl_beta * grad_beta]
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        fa::{Parameterised, linear::{LFA, basis::{Polynomial, Projector}, optim::SGD}},
+        policies::{DifferentiablePolicy, Policy},
+        utils::compare_floats,
+    };
+    use super::{Beta, beta_kl, beta_mode};
+
+    fn basis() -> Polynomial { Polynomial::new(1, 1).with_constant() }
+
+    macro_rules! make_policy {
+        () => {
+            Beta::new(LFA::scalar(basis(), SGD(1.0)), LFA::scalar(basis(), SGD(1.0)))
+        };
+    }
+
+    #[test]
+    fn test_mode_interior() {
+        assert_eq!(beta_mode(2.0, 2.0), 0.5);
+        assert!((beta_mode(3.0, 2.0) - 2.0 / 3.0).abs() < 1e-7);
+        assert_eq!(beta_mode(1.0, 1.0), 0.5);
+    }
+
+    #[test]
+    fn test_mode_boundary() {
+        assert_eq!(beta_mode(1.0, 3.0), 0.0);
+        assert_eq!(beta_mode(0.5, 1.0), 0.0);
+        assert_eq!(beta_mode(3.0, 1.0), 1.0);
+        assert_eq!(beta_mode(1.0, 0.5), 1.0);
+        assert_eq!(beta_mode(0.3, 0.5), 0.0);
+        assert_eq!(beta_mode(0.5, 0.3), 1.0);
+    }
+
+    #[test]
+    fn test_stacked_weights() {
+        let n = basis().n_features();
+        let mut p = make_policy!();
+
+        assert_eq!(p.weights_dim(), [2 * n, 1]);
+
+        p.weights_view_mut().slice_mut(s![..n, ..]).fill(1.0);
+
+        assert!(p.alpha().weights().iter().all(|&w| w == 1.0));
+        assert!(p.beta().weights().iter().all(|&w| w == 0.0));
+
+        // Beta(2, 1) has its mode on the upper boundary:
+        assert_eq!(p.compute_alpha(&vec![0.0]), 2.0);
+        assert_eq!(p.mpa(&vec![0.0]), 1.0);
+
+        // Changes made through the components are reflected in the stack:
+        p.modify(|_, beta| beta.weights_view_mut().fill(2.0));
+
+        assert!(p.weights().slice(s![n.., ..]).iter().all(|&w| w == 2.0));
+        assert_eq!(p.compute_beta(&vec![0.0]), 3.0);
+
+        let (alpha, _) = p.into_inner();
+
+        assert!(alpha.weights().iter().all(|&w| w == 1.0));
+    }
+
+    #[test]
+    fn test_update_grad() {
+        let n = basis().n_features();
+        let s = vec![1.0];
+
+        let mut p = make_policy!();
+        let gl = p.grad_log(&s, &0.25);
+
+        assert_eq!(gl.dim(), (2 * n, 1));
+
+        p.update_grad(&gl.view());
+
+        assert_eq!(p.weights(), gl);
+        assert_eq!(p.alpha().weights().column(0).to_vec(), gl.slice(s![..n, 0]).to_vec());
+        assert_eq!(p.beta().weights().column(0).to_vec(), gl.slice(s![n.., 0]).to_vec());
+
+        let mut p = make_policy!();
+        let lp = p.log_probability(&s, &0.25);
+
+        p.update_grad_scaled(&gl.view(), 0.1);
+
+        assert!(compare_floats(p.weights().iter(), (gl * 0.1).iter(), 1e-10));
+        assert!(p.log_probability(&s, &0.25) > lp);
+    }
+
+    #[test]
+    fn test_kl() {
+        assert!(beta_kl(2.0, 3.0, 2.0, 3.0).abs() < 1e-7);
+
+        // KL(B(1, 1) || B(2, 2)) = ln(1/6) + 2 (ψ(2) - ψ(1)) = 2 - ln(6):
+        assert!((beta_kl(1.0, 1.0, 2.0, 2.0) - (2.0 - 6.0f64.ln())).abs() < 1e-7);
+    }
+}
@@ -0,0 +1,130 @@
+use crate::{
+    fa::{
+        Weights, WeightsView, WeightsViewMut, Parameterised,
+        StateFunction, DifferentiableStateActionFunction,
+    },
+    policies::{
+        entropy_from_probs,
+        kl_from_probs,
+        sample_probs_with_rng,
+        softmax::softmax_stable,
+        DifferentiablePolicy,
+        Divergence,
+        Entropy,
+        EnumerablePolicy,
+        Policy,
+    },
+    utils::argmaxima,
+};
+use ndarray::Array2;
+use rand::Rng;
+
+/// Categorical policy over the logits of a vector-valued state function.
+///
+/// The probability of each action is given by the softmax of the logits,
+/// `π(a | s) ∝ exp(f_a(s))`. Unlike `Softmax`, the logits are not interpreted
+/// as action-values and are parameterised directly. The gradient of each
+/// logit with respect to the weights is taken from the function's
+/// `DifferentiableStateActionFunction` implementation.
+#[derive(Parameterised)]
+pub struct Categorical<F> {
+    #[weights] pub fa: F,
+}
+
+impl<F> Categorical<F> {
+    pub fn new(fa: F) -> Self { Categorical { fa } }
+}
+
+impl<S, F> Policy<S> for Categorical<F>
+where
+    F: StateFunction<S, Output = Vec<f64>> + Parameterised,
+{
+    type Action = usize;
+
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R, s: &S) -> usize {
+        sample_probs_with_rng(rng, &self.probabilities(s))
+    }
+
+    fn mpa(&self, s: &S) -> usize { argmaxima(&self.fa.evaluate(s)).1[0] }
+
+    fn probability(&self, s: &S, a: &usize) -> f64 { self.probabilities(s)[*a] }
+}
+
+impl<S, F> Entropy<S> for Categorical<F>
+where
+    F: StateFunction<S, Output = Vec<f64>> + Parameterised,
+{
+    fn entropy(&self, s: &S) -> f64 { entropy_from_probs(&self.probabilities(s)) }
+}
+
+impl<S, F> Divergence<S> for Categorical<F>
+where
+    F: StateFunction<S, Output = Vec<f64>> + Parameterised,
+{
+    fn kl_divergence(&self, other: &Self, s: &S) -> f64 {
+        kl_from_probs(&self.probabilities(s), &other.probabilities(s))
+    }
+}
+
+impl<S, F> EnumerablePolicy<S> for Categorical<F>
+where
+    F: StateFunction<S, Output = Vec<f64>> + Parameterised,
+{
+    fn n_actions(&self) -> usize { self.fa.weights_dim()[1] }
+
+    fn probabilities(&self, s: &S) -> Vec<f64> { softmax_stable(&self.fa.evaluate(s), 1.0) }
+}
+
+impl<S, F> DifferentiablePolicy<S> for Categorical<F>
+where
+    F: StateFunction<S, Output = Vec<f64>> + DifferentiableStateActionFunction<S, usize>,
+{
+    fn update(&mut self, s: &S, a: &usize, error: f64) {
+        let gl = self.grad_log(s, a);
+
+        self.fa.update_grad_scaled(&gl, error);
+    }
+
+    fn grad_log(&self, s: &S, a: &usize) -> Array2<f64> {
+        // ∇ ln π(a | s) = ∇f_a(s) - Σ_b π(b | s) ∇f_b(s):
+        let mut gl: Array2<f64> = self.fa.grad(s, a).into();
+
+        for (b, p) in self.probabilities(s).into_iter().enumerate() {
+            gl.scaled_add(-p, &self.fa.grad(s, &b).into());
+        }
+
+        gl
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        fa::linear::{LFA, basis::{Polynomial, Projector}, optim::SGD},
+        policies::{DifferentiablePolicy, Entropy, EnumerablePolicy, Policy},
+        utils::compare_floats,
+    };
+    use super::Categorical;
+
+    #[test]
+    fn test_uniform() {
+        let p = Categorical::new(LFA::vector(Polynomial::new(1, 1).with_constant(), SGD(1.0), 4));
+
+        assert!(compare_floats(p.probabilities(&vec![0.5]), &[0.25; 4], 1e-7));
+        assert!((p.entropy(&vec![0.5]) - 4.0f64.ln()).abs() < 1e-7);
+    }
+
+    #[test]
+    fn test_update() {
+        let mut p = Categorical::new(
+            LFA::vector(Polynomial::new(1, 1).with_constant(), SGD(1.0), 3)
+        );
+
+        p.update(&vec![1.0], &1, 1.0);
+
+        let ps = p.probabilities(&vec![1.0]);
+
+        assert!(ps[1] > ps[0] && ps[1] > ps[2]);
+        assert_eq!(p.mpa(&vec![1.0]), 1);
+    }
+}
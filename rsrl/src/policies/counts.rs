@@ -0,0 +1,154 @@
+use crate::Shared;
+use std::{
+    collections::{HashMap, hash_map::DefaultHasher},
+    hash::{Hash, Hasher},
+};
+
+/// Interface for state-action visitation counters.
+pub trait VisitCounter<S> {
+    /// Return the number of visits to the pair `(s, a)`.
+    fn count(&self, s: &S, a: usize) -> usize;
+
+    /// Return the number of visits to state `s`, summed over actions.
+    fn state_count(&self, s: &S) -> usize;
+
+    /// Record a visit to the pair `(s, a)`.
+    fn increment(&mut self, s: &S, a: usize);
+}
+
+impl<S, T: VisitCounter<S>> VisitCounter<S> for Shared<T> {
+    fn count(&self, s: &S, a: usize) -> usize { self.borrow().count(s, a) }
+
+    fn state_count(&self, s: &S) -> usize { self.borrow().state_count(s) }
+
+    fn increment(&mut self, s: &S, a: usize) { self.borrow_mut().increment(s, a) }
+}
+
+/// Dense visitation counter for finite state spaces indexed by `usize`.
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug)]
+pub struct TabularCounter {
+    n_actions: usize,
+    counts: Vec<usize>,
+}
+
+impl TabularCounter {
+    pub fn new(n_states: usize, n_actions: usize) -> Self {
+        TabularCounter {
+            n_actions,
+            counts: vec![0; n_states * n_actions],
+        }
+    }
+}
+
+impl VisitCounter<usize> for TabularCounter {
+    fn count(&self, s: &usize, a: usize) -> usize { self.counts[s * self.n_actions + a] }
+
+    fn state_count(&self, s: &usize) -> usize {
+        let i = s * self.n_actions;
+
+        self.counts[i..(i + self.n_actions)].iter().sum()
+    }
+
+    fn increment(&mut self, s: &usize, a: usize) { self.counts[s * self.n_actions + a] += 1; }
+}
+
+/// Return the default hash of a state, for use with `HashedCounter`.
+pub fn hash_state<S: Hash>(s: &S) -> u64 {
+    let mut hasher = DefaultHasher::new();
+
+    s.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Sparse visitation counter over hashed state representations.
+///
+/// States are mapped to keys using the function `hasher`; any two states with
+/// the same key share counts. For discrete states `hash_state` gives exact
+/// counts, whereas for continuous states a locality-sensitive hash (e.g.
+/// discretisation or SimHash) yields pseudo-counts.
+///
+/// # References
+/// - Tang, H., Houthooft, R., Foote, D., Stooke, A., Chen, X., Duan, Y.,
+/// Schulman, J., De Turck, F., Abbeel, P. (2017). #Exploration: A study of
+/// count-based exploration for deep reinforcement learning. In Advances in
+/// Neural Information Processing Systems, pp. 2753–2762.
+#[derive(Clone, Debug)]
+pub struct HashedCounter<H> {
+    hasher: H,
+    n_actions: usize,
+
+    counts: HashMap<u64, Vec<usize>>,
+}
+
+impl<H> HashedCounter<H> {
+    pub fn new(n_actions: usize, hasher: H) -> Self {
+        HashedCounter {
+            hasher,
+            n_actions,
+
+            counts: HashMap::new(),
+        }
+    }
+}
+
+impl<S, H: Fn(&S) -> u64> VisitCounter<S> for HashedCounter<H> {
+    fn count(&self, s: &S, a: usize) -> usize {
+        self.counts.get(&(self.hasher)(s)).map_or(0, |cs| cs[a])
+    }
+
+    fn state_count(&self, s: &S) -> usize {
+        self.counts.get(&(self.hasher)(s)).map_or(0, |cs| cs.iter().sum())
+    }
+
+    fn increment(&mut self, s: &S, a: usize) {
+        let n_actions = self.n_actions;
+        let key = (self.hasher)(s);
+
+        self.counts.entry(key).or_insert_with(|| vec![0; n_actions])[a] += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{HashedCounter, TabularCounter, VisitCounter, hash_state};
+
+    #[test]
+    fn test_tabular() {
+        let mut counter = TabularCounter::new(3, 2);
+
+        counter.increment(&1, 0);
+        counter.increment(&1, 1);
+        counter.increment(&1, 1);
+
+        assert_eq!(counter.count(&1, 1), 2);
+        assert_eq!(counter.count(&0, 1), 0);
+        assert_eq!(counter.state_count(&1), 3);
+        assert_eq!(counter.state_count(&2), 0);
+    }
+
+    #[test]
+    fn test_hashed() {
+        let mut counter = HashedCounter::new(2, hash_state::<[usize; 2]>);
+
+        counter.increment(&[0, 1], 0);
+        counter.increment(&[0, 1], 0);
+        counter.increment(&[1, 0], 1);
+
+        assert_eq!(counter.count(&[0, 1], 0), 2);
+        assert_eq!(counter.count(&[1, 0], 0), 0);
+        assert_eq!(counter.state_count(&[1, 0]), 1);
+        assert_eq!(counter.state_count(&[5, 5]), 0);
+    }
+
+    #[test]
+    fn test_discretised_hash() {
+        let mut counter = HashedCounter::new(1, |s: &f64| (s * 10.0).floor() as u64);
+
+        counter.increment(&0.11, 0);
+        counter.increment(&0.19, 0);
+
+        assert_eq!(counter.count(&0.15, 0), 2);
+        assert_eq!(counter.count(&0.25, 0), 0);
+    }
+}
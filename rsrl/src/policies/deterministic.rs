@@ -0,0 +1,49 @@
+use crate::{
+    fa::{
+        Parameterised, Weights, WeightsView, WeightsViewMut,
+        StateFunction, DifferentiableStateFunction,
+    },
+    policies::Policy,
+};
+use ndarray::Array2;
+use rand::Rng;
+
+/// Trait for deterministic policies, `u = μ(x)`, over a scalar action space.
+pub trait DeterministicPolicy<S>: Policy<S, Action = f64> + Parameterised {
+    /// Return the Jacobian of the action with respect to the policy weights,
+    /// `∇_θ μ(x)`, with the same shape as the weights.
+    fn jacobian(&self, state: &S) -> Array2<f64>;
+}
+
+/// Deterministic policy with action given directly by a differentiable
+/// function approximator.
+///
+/// The action is the output of the approximator; exploration must therefore be
+/// provided by a separate behaviour policy.
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, Parameterised)]
+pub struct Deterministic<F>(pub F);
+
+impl<F> Deterministic<F> {
+    pub fn new(f: F) -> Self { Deterministic(f) }
+}
+
+impl<S, F> Policy<S> for Deterministic<F>
+where F: StateFunction<S, Output = f64>
+{
+    type Action = f64;
+
+    fn sample<R: Rng + ?Sized>(&self, _: &mut R, s: &S) -> f64 { self.0.evaluate(s) }
+
+    fn mpa(&self, s: &S) -> f64 { self.0.evaluate(s) }
+
+    fn probability(&self, s: &S, a: &f64) -> f64 {
+        if (self.0.evaluate(s) - a).abs() < 1e-7 { 1.0 } else { 0.0 }
+    }
+}
+
+impl<S, F> DeterministicPolicy<S> for Deterministic<F>
+where F: DifferentiableStateFunction<S, Output = f64>
+{
+    fn jacobian(&self, s: &S) -> Array2<f64> { self.0.grad(s).into() }
+}
@@ -5,7 +5,7 @@ use crate::{
         Weights, WeightsView, WeightsViewMut, Parameterised,
         StateFunction, DifferentiableStateFunction,
     },
-    policies::{DifferentiablePolicy, Policy},
+    policies::{DifferentiablePolicy, Divergence, Entropy, Policy},
 };
 use ndarray::{Array2, ArrayView2};
 use rand::Rng;
@@ -71,6 +71,53 @@ where
     fn probability(&self, input: &S, a: &Vec<f64>) -> f64 {
         self.dist(input).pdf(a.clone())
     }
+
+    fn log_probability(&self, input: &S, a: &Vec<f64>) -> f64 {
+        let alphas = self.compute_alphas(input);
+        let sum_alphas: f64 = alphas.iter().sum();
+
+        alphas.iter().zip(a.iter()).fold(sum_alphas.loggamma(), |acc, (alpha, x)| {
+            acc + (alpha - 1.0) * x.ln() - alpha.loggamma()
+        })
+    }
+}
+
+impl<S, F> Entropy<S> for Dirichlet<F>
+where
+    F: StateFunction<S, Output = Vec<f64>>,
+{
+    fn entropy(&self, input: &S) -> f64 {
+        let alphas = self.compute_alphas(input);
+        let k = alphas.len() as f64;
+        let sum_alphas: f64 = alphas.iter().sum();
+
+        let log_beta = alphas.iter().fold(-sum_alphas.loggamma(), |acc, a| acc + a.loggamma());
+
+        alphas.iter().fold(log_beta + (sum_alphas - k) * sum_alphas.digamma(), |acc, a| {
+            acc - (a - 1.0) * a.digamma()
+        })
+    }
+}
+
+impl<S, F> Divergence<S> for Dirichlet<F>
+where
+    F: StateFunction<S, Output = Vec<f64>>,
+{
+    fn kl_divergence(&self, other: &Self, input: &S) -> f64 {
+        let alphas_p = self.compute_alphas(input);
+        let alphas_q = other.compute_alphas(input);
+
+        let sum_p: f64 = alphas_p.iter().sum();
+        let sum_q: f64 = alphas_q.iter().sum();
+        let digamma_sum_p = sum_p.digamma();
+
+        alphas_p.iter().zip(alphas_q.iter()).fold(
+            sum_p.loggamma() - sum_q.loggamma(),
+            |acc, (ap, aq)| {
+                acc - ap.loggamma() + aq.loggamma() + (ap - aq) * (ap.digamma() - digamma_sum_p)
+            }
+        )
+    }
 }
 
 impl<S, F> DifferentiablePolicy<S> for Dirichlet<F>
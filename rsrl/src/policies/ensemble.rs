@@ -0,0 +1,133 @@
+use crate::{
+    fa::EnsembleFunction,
+    policies::{EnumerablePolicy, Policy},
+    utils::argmaxima,
+};
+use rand::{thread_rng, Rng};
+
+/// Thompson-like exploration over the heads of a bootstrapped ensemble.
+///
+/// Acts greedily with respect to the active head of the ensemble, which is
+/// resampled uniformly at random at the end of every episode. Committing to a
+/// single head for the duration of an episode yields temporally-consistent,
+/// "deep" exploration.
+///
+/// # References
+/// - Osband, I., Blundell, C., Pritzel, A., Van Roy, B. (2016). Deep
+/// exploration via bootstrapped DQN. In Advances in Neural Information
+/// Processing Systems, pp. 4026–4034.
+#[derive(Clone, Debug)]
+pub struct EnsembleThompson<Q>(Q);
+
+impl<Q> EnsembleThompson<Q> {
+    pub fn new(q_func: Q) -> Self { EnsembleThompson(q_func) }
+
+    /// Return the fraction of heads for which each action is greedy in state
+    /// `s`.
+    pub fn votes<S>(&self, s: &S) -> Vec<f64> where Q: EnsembleFunction<S> {
+        let n_heads = self.0.n_heads();
+        let mut votes = vec![0.0; self.0.n_actions()];
+
+        for head in 0..n_heads {
+            let (_, maxima) = argmaxima(&self.0.evaluate_head(head, s));
+            let v = 1.0 / (maxima.len() * n_heads) as f64;
+
+            for i in maxima {
+                votes[i] += v;
+            }
+        }
+
+        votes
+    }
+}
+
+impl<S, Q: EnsembleFunction<S>> Policy<S> for EnsembleThompson<Q> {
+    type Action = usize;
+
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R, s: &S) -> usize {
+        let (_, maxima) = argmaxima(&self.0.evaluate_all(s));
+
+        maxima[rng.gen_range(0, maxima.len())]
+    }
+
+    fn mpa(&self, s: &S) -> usize { argmaxima(&self.0.evaluate_all(s)).1[0] }
+
+    fn probability(&self, s: &S, a: &usize) -> f64 { self.probabilities(s)[*a] }
+
+    fn handle_terminal(&mut self) {
+        let head = thread_rng().gen_range(0, self.0.n_heads());
+
+        self.0.set_active_head(head);
+    }
+}
+
+impl<S, Q: EnsembleFunction<S>> EnumerablePolicy<S> for EnsembleThompson<Q> {
+    fn n_actions(&self) -> usize { self.0.n_actions() }
+
+    fn probabilities(&self, s: &S) -> Vec<f64> {
+        let qs = self.0.evaluate_all(s);
+        let mut ps = vec![0.0; qs.len()];
+
+        let (_, maxima) = argmaxima(&qs);
+
+        let p = 1.0 / maxima.len() as f64;
+        for i in maxima {
+            ps[i] = p;
+        }
+
+        ps
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        make_shared,
+        fa::{Ensemble, tabular::Tabular},
+        utils::compare_floats,
+    };
+    use super::{EnsembleThompson, EnumerablePolicy, Policy};
+
+    fn ensemble() -> Ensemble<Tabular> {
+        Ensemble::new(vec![
+            Tabular::new(vec![vec![0.0], vec![1.0]]),
+            Tabular::new(vec![vec![1.0], vec![0.0]]),
+            Tabular::new(vec![vec![1.0], vec![1.0]]),
+        ], 0.5)
+    }
+
+    #[test]
+    fn test_active_head() {
+        let q = make_shared(ensemble());
+        let p = EnsembleThompson::new(q.clone());
+
+        assert_eq!(p.mpa(&0), 1);
+        assert!(compare_floats(p.probabilities(&0), &[0.0, 1.0], 1e-7));
+
+        q.borrow_mut().set_active_head(1);
+
+        assert_eq!(p.mpa(&0), 0);
+        assert!(compare_floats(p.probabilities(&0), &[1.0, 0.0], 1e-7));
+    }
+
+    #[test]
+    fn test_votes() {
+        let p = EnsembleThompson::new(ensemble());
+
+        assert!(compare_floats(p.votes(&0), &[0.5, 0.5], 1e-7));
+    }
+
+    #[test]
+    fn test_resampling() {
+        let q = make_shared(ensemble());
+        let mut p = EnsembleThompson::new(q.clone());
+        let mut seen = [false; 3];
+
+        for _ in 0..1000 {
+            p.handle_terminal();
+            seen[q.borrow().active_head()] = true;
+        }
+
+        assert!(seen.iter().all(|&s| s));
+    }
+}
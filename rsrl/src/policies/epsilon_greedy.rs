@@ -1,6 +1,15 @@
 use crate::{
     fa::EnumerableStateActionFunction,
-    policies::{EnumerablePolicy, Greedy, Policy, Random}
+    policies::{
+        entropy_from_probs,
+        kl_from_probs,
+        Divergence,
+        Entropy,
+        EnumerablePolicy,
+        Greedy,
+        Policy,
+        Random,
+    },
 };
 use rand::Rng;
 
@@ -46,6 +55,16 @@ impl<S, Q: EnumerableStateActionFunction<S>> Policy<S> for EpsilonGreedy<Q> {
     fn probability(&self, s: &S, a: &usize) -> f64 { self.probabilities(s)[*a] }
 }
 
+impl<S, Q: EnumerableStateActionFunction<S>> Entropy<S> for EpsilonGreedy<Q> {
+    fn entropy(&self, s: &S) -> f64 { entropy_from_probs(&self.probabilities(s)) }
+}
+
+impl<S, Q: EnumerableStateActionFunction<S>> Divergence<S> for EpsilonGreedy<Q> {
+    fn kl_divergence(&self, other: &Self, s: &S) -> f64 {
+        kl_from_probs(&self.probabilities(s), &other.probabilities(s))
+    }
+}
+
 impl<S, Q: EnumerableStateActionFunction<S>> EnumerablePolicy<S> for EpsilonGreedy<Q> {
     fn n_actions(&self) -> usize { self.greedy.n_actions() }
 
@@ -0,0 +1,78 @@
+use crate::{
+    fa::EnumerableStateActionFunction,
+    policies::{
+        entropy_from_probs,
+        sample_probs_with_rng,
+        Entropy,
+        EnumerablePolicy,
+        Policy,
+        Softmax,
+    },
+};
+use rand::Rng;
+
+/// Mixture of a `Softmax` policy with the uniform distribution.
+///
+/// With probability `epsilon` an action is chosen uniformly at random,
+/// otherwise it is drawn from a Boltzmann distribution with temperature `tau`.
+/// This bounds the probability of every action below by `epsilon / n`.
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug)]
+pub struct EpsilonSoftmax<Q> {
+    softmax: Softmax<Q>,
+
+    pub epsilon: f64,
+}
+
+impl<Q> EpsilonSoftmax<Q> {
+    pub fn new(q_func: Q, tau: f64, epsilon: f64) -> Self {
+        EpsilonSoftmax {
+            softmax: Softmax::new(q_func, tau),
+
+            epsilon,
+        }
+    }
+}
+
+impl<S, Q: EnumerableStateActionFunction<S>> Policy<S> for EpsilonSoftmax<Q> {
+    type Action = usize;
+
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R, s: &S) -> usize {
+        sample_probs_with_rng(rng, &self.probabilities(s))
+    }
+
+    fn mpa(&self, s: &S) -> usize { self.softmax.mpa(s) }
+
+    fn probability(&self, s: &S, a: &usize) -> f64 { self.probabilities(s)[*a] }
+
+    fn handle_terminal(&mut self) { self.softmax.handle_terminal() }
+}
+
+impl<S, Q: EnumerableStateActionFunction<S>> Entropy<S> for EpsilonSoftmax<Q> {
+    fn entropy(&self, s: &S) -> f64 { entropy_from_probs(&self.probabilities(s)) }
+}
+
+impl<S, Q: EnumerableStateActionFunction<S>> EnumerablePolicy<S> for EpsilonSoftmax<Q> {
+    fn n_actions(&self) -> usize { self.softmax.n_actions() }
+
+    fn probabilities(&self, s: &S) -> Vec<f64> {
+        let prs = self.softmax.probabilities(s);
+        let pr = self.epsilon / prs.len() as f64;
+
+        prs.into_iter().map(|p| pr + p * (1.0 - self.epsilon)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{fa::mocking::MockQ, utils::compare_floats};
+    use super::{EnumerablePolicy, EpsilonSoftmax};
+
+    #[test]
+    fn test_probabilities() {
+        let p = EpsilonSoftmax::new(MockQ::new_shared(None), 1.0, 0.5);
+
+        assert!(compare_floats(p.probabilities(&vec![0.0, 0.0]), &[0.5, 0.5], 1e-7));
+        assert!(compare_floats(p.probabilities(&vec![0.0, 1000.0]), &[0.25, 0.75], 1e-7));
+    }
+}
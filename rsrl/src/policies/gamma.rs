@@ -3,44 +3,85 @@ extern crate special_fun;
 use crate::{
     fa::{
         StateFunction, DifferentiableStateFunction,
-        Parameterised, Weights, WeightsView, WeightsViewMut,
+        Parameterised, Stacked, Weights, WeightsView, WeightsViewMut,
     },
-    policies::{DifferentiablePolicy, Policy},
+    policies::{DifferentiablePolicy, Divergence, Entropy, Policy},
 };
 use ndarray::{Array2, ArrayView2, Axis};
 use rand::Rng;
-use rstat::{
-    Distribution, ContinuousDistribution,
-    univariate::{UnivariateMoments, continuous::Gamma as GammaDist},
-};
-use std::ops::AddAssign;
+use rstat::{Distribution, ContinuousDistribution, univariate::continuous::Gamma as GammaDist};
+use std::{cell::Ref, ops::AddAssign};
 
 const MIN_TOL: f64 = 0.1;
 
+/// Return the mode of a Gamma distribution with shape `alpha` and scale
+/// `theta`; the density is maximised at the origin when `alpha < 1`.
+pub fn gamma_mode(alpha: f64, theta: f64) -> f64 {
+    if alpha >= 1.0 { (alpha - 1.0) * theta } else { 0.0 }
+}
+
+/// Return the KL divergence between two Gamma distributions, `KL(p || q)`,
+/// with shape-scale parameters `(alpha_p, theta_p)` and `(alpha_q, theta_q)`,
+/// respectively.
+pub fn gamma_kl(alpha_p: f64, theta_p: f64, alpha_q: f64, theta_q: f64) -> f64 {
+    use special_fun::FloatSpecial;
+
+    (alpha_p - alpha_q) * alpha_p.digamma() - alpha_p.loggamma() + alpha_q.loggamma()
+        + alpha_q * (theta_q / theta_p).ln()
+        + alpha_p * (theta_p - theta_q) / theta_q
+}
+
+/// Gamma policy for non-negative actions.
+///
+/// The shape and scale parameters are given by two state functions, bounded
+/// below by a minimum tolerance. Their weights are stacked row-wise, `[alpha;
+/// theta]`.
+///
+/// The function approximators are held by the stacked weights, rather than
+/// exposed as public fields, so that `weights_view_mut` can be served from a
+/// single matrix. Read them with `alpha()` and `theta()`, and modify them with
+/// `modify`; both must therefore implement `Parameterised`.
 #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
 #[derive(Clone, Debug)]
 pub struct Gamma<A, T = A> {
-    pub alpha: A,
-    pub theta: T,
+    params: Stacked<(A, T)>,
 }
 
-impl<A, T> Gamma<A, T> {
+impl<A: Parameterised, T: Parameterised> Gamma<A, T> {
     pub fn new(alpha: A, theta: T) -> Self {
-        Gamma { alpha, theta, }
+        Gamma {
+            params: Stacked::new((alpha, theta), Axis(0)),
+        }
+    }
+
+    /// Return a reference to the function approximator for `alpha`.
+    pub fn alpha(&self) -> Ref<A> { Ref::map(self.params.get(), |p| &p.0) }
+
+    /// Return a reference to the function approximator for `theta`.
+    pub fn theta(&self) -> Ref<T> { Ref::map(self.params.get(), |p| &p.1) }
+
+    /// Apply a closure to the function approximators for `alpha` and `theta`,
+    /// keeping the stacked weights in sync.
+    pub fn modify<R>(&mut self, f: impl FnOnce(&mut A, &mut T) -> R) -> R {
+        self.params.modify(|p| f(&mut p.0, &mut p.1))
     }
 
+    /// Consume the policy and return the function approximators for `alpha`
+    /// and `theta`.
+    pub fn into_inner(self) -> (A, T) { self.params.into_inner() }
+
     #[inline]
     pub fn compute_alpha<S>(&self, s: &S) -> f64
         where A: StateFunction<S, Output = f64>,
     {
-        self.alpha.evaluate(s).max(MIN_TOL)
+        self.alpha().evaluate(s).max(MIN_TOL)
     }
 
     #[inline]
     pub fn compute_theta<S>(&self, s: &S) -> f64
         where T: StateFunction<S, Output = f64>,
     {
-        self.theta.evaluate(s).max(MIN_TOL)
+        self.theta().evaluate(s).max(MIN_TOL)
     }
 
     #[inline]
@@ -61,8 +102,8 @@ impl<A, T> Gamma<A, T> {
 
 impl<S, A, T> Policy<S> for Gamma<A, T>
 where
-    A: StateFunction<S, Output = f64>,
-    T: StateFunction<S, Output = f64>,
+    A: StateFunction<S, Output = f64> + Parameterised,
+    T: StateFunction<S, Output = f64> + Parameterised,
 {
     type Action = f64;
 
@@ -71,12 +112,49 @@ where
     }
 
     fn mpa(&self, input: &S) -> f64 {
-        self.dist(input).mean()
+        gamma_mode(self.compute_alpha(input), self.compute_theta(input))
     }
 
     fn probability(&self, input: &S, a: &f64) -> f64 {
         self.dist(input).pdf(*a)
     }
+
+    fn log_probability(&self, input: &S, a: &f64) -> f64 {
+        use special_fun::FloatSpecial;
+
+        let alpha = self.compute_alpha(input);
+        let theta = self.compute_theta(input);
+
+        (alpha - 1.0) * a.ln() - a / theta - alpha.loggamma() - alpha * theta.ln()
+    }
+}
+
+impl<S, A, T> Entropy<S> for Gamma<A, T>
+where
+    A: StateFunction<S, Output = f64> + Parameterised,
+    T: StateFunction<S, Output = f64> + Parameterised,
+{
+    fn entropy(&self, input: &S) -> f64 {
+        use special_fun::FloatSpecial;
+
+        let alpha = self.compute_alpha(input);
+        let theta = self.compute_theta(input);
+
+        alpha + theta.ln() + alpha.loggamma() + (1.0 - alpha) * alpha.digamma()
+    }
+}
+
+impl<S, A, T> Divergence<S> for Gamma<A, T>
+where
+    A: StateFunction<S, Output = f64> + Parameterised,
+    T: StateFunction<S, Output = f64> + Parameterised,
+{
+    fn kl_divergence(&self, other: &Self, input: &S) -> f64 {
+        gamma_kl(
+            self.compute_alpha(input), self.compute_theta(input),
+            other.compute_alpha(input), other.compute_theta(input),
+        )
+    }
 }
 
 impl<S, A, T> DifferentiablePolicy<S> for Gamma<A, T>
@@ -90,72 +168,123 @@ where
 
         let [gl_alpha, gl_theta] = self.gl_partial(val_alpha, val_theta, *a);
 
-        self.alpha.update(state, gl_alpha * error);
-        self.theta.update(state, gl_theta * error);
+        self.params.modify(|p| {
+            p.0.update(state, gl_alpha * error);
+            p.1.update(state, gl_theta * error);
+        })
     }
 
     fn update_grad(&mut self, grad: &ArrayView2<f64>) {
-        match self.alpha.weights_dim() {
-            [r, _] if r > 0 => {
-                let grad_k = grad.slice(s![0..r, ..]);
-                self.alpha.weights_view_mut().add_assign(&grad_k);
-            },
-            _ => {},
-        }
-
-        match self.theta.weights_dim() {
-            [r, _] if r > 0 => {
-                let grad_theta = grad.slice(s![self.theta.weights_dim()[0].., ..]);
-                self.theta.weights_view_mut().add_assign(&grad_theta);
-            },
-            _ => {},
-        }
+        self.params.weights_view_mut().add_assign(grad);
     }
 
     fn update_grad_scaled(&mut self, grad: &ArrayView2<f64>, factor: f64) {
-        match self.alpha.weights_dim() {
-            [r, _] if r > 0 => {
-                let grad_k = grad.slice(s![0..self.alpha.weights_dim()[0], ..]);
-                self.alpha.weights_view_mut().scaled_add(factor, &grad_k);
-            },
-            _ => {},
-        }
-
-        match self.theta.weights_dim() {
-            [r, _] if r > 0 => {
-                let grad_theta = grad.slice(s![self.theta.weights_dim()[0].., ..]);
-                self.theta.weights_view_mut().scaled_add(factor, &grad_theta);
-            },
-            _ => {},
-        }
+        self.params.weights_view_mut().scaled_add(factor, grad);
     }
 
     fn grad_log(&self, state: &S, a: &f64) -> Array2<f64> {
-        let val_alpha = self.compute_theta(state);
-        let grad_alpha: Array2<f64> = self.alpha.grad(state).into();
-
+        let val_alpha = self.compute_alpha(state);
         let val_theta = self.compute_theta(state);
-        let grad_theta: Array2<f64> = self.theta.grad(state).into();
 
         let [gl_alpha, gl_theta] = self.gl_partial(val_alpha, val_theta, *a);
 
+        let p = self.params.get();
+        let grad_alpha: Array2<f64> = p.0.grad(state).into();
+        let grad_theta: Array2<f64> = p.1.grad(state).into();
+
         stack![Axis(0), gl_alpha * grad_alpha, gl_theta * grad_theta]
     }
 }
 
 impl<A: Parameterised, T: Parameterised> Parameterised for Gamma<A, T> {
-    fn weights(&self) -> Weights {
-        stack![Axis(0), self.alpha.weights(), self.theta.weights()]
+    fn weights(&self) -> Weights { self.params.weights() }
+
+    fn weights_view(&self) -> WeightsView { self.params.weights_view() }
+
+    fn weights_view_mut(&mut self) -> WeightsViewMut { self.params.weights_view_mut() }
+
+    fn weights_dim(&self) -> [usize; 2] { self.params.weights_dim() }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        fa::{Parameterised, linear::{LFA, basis::{Polynomial, Projector}, optim::SGD}},
+        policies::{DifferentiablePolicy, Policy},
+        utils::compare_floats,
+    };
+    use super::{Gamma, gamma_kl, gamma_mode};
+
+    fn basis() -> Polynomial { Polynomial::new(1, 1).with_constant() }
+
+    macro_rules! make_policy {
+        () => {
+            Gamma::new(LFA::scalar(basis(), SGD(1.0)), LFA::scalar(basis(), SGD(1.0)))
+        };
+    }
+
+    #[test]
+    fn test_mode() {
+        assert_eq!(gamma_mode(3.0, 2.0), 4.0);
+        assert_eq!(gamma_mode(1.0, 2.0), 0.0);
+        assert_eq!(gamma_mode(0.5, 2.0), 0.0);
     }
 
-    fn weights_view(&self) -> WeightsView { unimplemented!() }
+    #[test]
+    fn test_stacked_weights() {
+        let n = basis().n_features();
+        let mut p = make_policy!();
 
-    fn weights_view_mut(&mut self) -> WeightsViewMut { unimplemented!() }
+        assert_eq!(p.weights_dim(), [2 * n, 1]);
+
+        p.weights_view_mut().slice_mut(s![..n, ..]).fill(1.5);
+        p.weights_view_mut().slice_mut(s![n.., ..]).fill(2.0);
+
+        assert!(p.alpha().weights().iter().all(|&w| w == 1.5));
+        assert!(p.theta().weights().iter().all(|&w| w == 2.0));
+
+        assert_eq!(p.compute_alpha(&vec![0.0]), 1.5);
+        assert_eq!(p.compute_theta(&vec![0.0]), 2.0);
+        assert_eq!(p.mpa(&vec![0.0]), 1.0);
+    }
+
+    #[test]
+    fn test_update_grad() {
+        let n = basis().n_features();
+        let s = vec![0.0];
+
+        let mut p = make_policy!();
+
+        p.weights_view_mut().fill(1.0);
+
+        let w = p.weights();
+        let gl = p.grad_log(&s, &1.0);
+        let lp = p.log_probability(&s, &1.0);
+
+        assert_eq!(gl.dim(), (2 * n, 1));
+
+        p.update_grad_scaled(&gl.view(), 0.1);
+
+        assert!(compare_floats(p.weights().iter(), (&w + &(&gl * 0.1)).iter(), 1e-10));
+        assert!(p.log_probability(&s, &1.0) > lp);
+
+        p.update_grad(&gl.view());
+
+        let expected = &w + &(&gl * 1.1);
+
+        assert!(compare_floats(
+            p.alpha().weights().iter(), expected.slice(s![..n, ..]).iter(), 1e-10
+        ));
+        assert!(compare_floats(
+            p.theta().weights().iter(), expected.slice(s![n.., ..]).iter(), 1e-10
+        ));
+    }
 
-    fn weights_dim(&self) -> [usize; 2] {
-        let [ra, _] = self.alpha.weights_dim();
-        let [rb, _] = self.theta.weights_dim();
+    #[test]
+    fn test_kl() {
+        assert!(gamma_kl(2.0, 3.0, 2.0, 3.0).abs() < 1e-7);
 
-        [ra + rb, 1]
+        // Exponential distributions with rates 1 and 2, ln(1/2) + 2 - 1:
+        assert!((gamma_kl(1.0, 1.0, 1.0, 0.5) - (1.0 - 2.0f64.ln())).abs() < 1e-7);
     }
 }
@@ -1,15 +1,43 @@
 use ndarray::{Array1, Array2};
+use ndarray_linalg::{UPLO, cholesky::Cholesky, solve::Inverse};
 use rstat::{
     Distribution, ContinuousDistribution,
     univariate::continuous::Normal,
     multivariate::continuous::{BivariateNormal, MultivariateNormal},
 };
-use std::fmt::Debug;
+use std::{f64::consts::{E, PI}, fmt::Debug};
+
+// Entropy of a univariate normal distribution with variance `var`.
+fn normal_entropy(var: f64) -> f64 { 0.5 * (2.0 * PI * E * var).ln() }
+
+// Log density of a univariate normal distribution with mean `m` and variance
+// `var`, evaluated at `x`.
+fn normal_log_pdf(m: f64, var: f64, x: f64) -> f64 {
+    -0.5 * ((x - m).powi(2) / var + (2.0 * PI * var).ln())
+}
+
+// KL divergence between two univariate normal distributions with means `mp`
+// and `mq`, and variances `vp` and `vq`, respectively.
+fn normal_kl(mp: f64, vp: f64, mq: f64, vq: f64) -> f64 {
+    0.5 * ((vq / vp).ln() + (vp + (mp - mq).powi(2)) / vq - 1.0)
+}
 
 pub trait DistBuilder<M: Debug + Clone, S: Debug + Clone> {
     type Distribution: ContinuousDistribution;
 
     fn build(mean: M, stddev: S) -> Self::Distribution;
+
+    /// Return the log density of the distribution produced by `build(mean,
+    /// stddev)` at `x`.
+    fn log_density(mean: &M, stddev: &S, x: &M) -> f64;
+
+    /// Return the differential entropy of the distribution produced by
+    /// `build(mean, stddev)`.
+    fn entropy(mean: &M, stddev: &S) -> f64;
+
+    /// Return the KL divergence between the distributions produced by
+    /// `build(mean_p, stddev_p)` and `build(mean_q, stddev_q)`.
+    fn kl_divergence(mean_p: &M, stddev_p: &S, mean_q: &M, stddev_q: &S) -> f64;
 }
 
 pub struct GB;
@@ -24,6 +52,16 @@ impl DistBuilder<f64, f64> for GB {
     fn build(mean: f64, stddev: f64) -> Normal {
         Normal::new(mean, stddev)
     }
+
+    fn log_density(mean: &f64, stddev: &f64, x: &f64) -> f64 {
+        normal_log_pdf(*mean, stddev * stddev, *x)
+    }
+
+    fn entropy(_: &f64, stddev: &f64) -> f64 { normal_entropy(stddev * stddev) }
+
+    fn kl_divergence(mp: &f64, sp: &f64, mq: &f64, sq: &f64) -> f64 {
+        normal_kl(*mp, sp * sp, *mq, sq * sq)
+    }
 }
 
 impl DistBuilder<[f64; 2], f64> for GB {
@@ -32,6 +70,17 @@ impl DistBuilder<[f64; 2], f64> for GB {
     fn build(mean: [f64; 2], stddev: f64) -> BivariateNormal {
         BivariateNormal::isotropic(mean, stddev)
     }
+
+    fn log_density(mean: &[f64; 2], stddev: &f64, x: &[f64; 2]) -> f64 {
+        normal_log_pdf(mean[0], stddev * stddev, x[0])
+            + normal_log_pdf(mean[1], stddev * stddev, x[1])
+    }
+
+    fn entropy(_: &[f64; 2], stddev: &f64) -> f64 { 2.0 * normal_entropy(stddev * stddev) }
+
+    fn kl_divergence(mp: &[f64; 2], sp: &f64, mq: &[f64; 2], sq: &f64) -> f64 {
+        normal_kl(mp[0], sp * sp, mq[0], sq * sq) + normal_kl(mp[1], sp * sp, mq[1], sq * sq)
+    }
 }
 
 impl DistBuilder<[f64; 2], [f64; 2]> for GB {
@@ -40,6 +89,20 @@ impl DistBuilder<[f64; 2], [f64; 2]> for GB {
     fn build(mean: [f64; 2], stddev: [f64; 2]) -> BivariateNormal {
         BivariateNormal::independent(mean, stddev)
     }
+
+    fn log_density(mean: &[f64; 2], stddev: &[f64; 2], x: &[f64; 2]) -> f64 {
+        normal_log_pdf(mean[0], stddev[0] * stddev[0], x[0])
+            + normal_log_pdf(mean[1], stddev[1] * stddev[1], x[1])
+    }
+
+    fn entropy(_: &[f64; 2], stddev: &[f64; 2]) -> f64 {
+        normal_entropy(stddev[0] * stddev[0]) + normal_entropy(stddev[1] * stddev[1])
+    }
+
+    fn kl_divergence(mp: &[f64; 2], sp: &[f64; 2], mq: &[f64; 2], sq: &[f64; 2]) -> f64 {
+        normal_kl(mp[0], sp[0] * sp[0], mq[0], sq[0] * sq[0])
+            + normal_kl(mp[1], sp[1] * sp[1], mq[1], sq[1] * sq[1])
+    }
 }
 
 impl DistBuilder<Array1<f64>, f64> for GB {
@@ -48,6 +111,18 @@ impl DistBuilder<Array1<f64>, f64> for GB {
     fn build(mean: Array1<f64>, stddev: f64) -> MultivariateNormal {
         MultivariateNormal::isotropic(mean, stddev)
     }
+
+    fn log_density(mean: &Array1<f64>, stddev: &f64, x: &Array1<f64>) -> f64 {
+        mean.iter().zip(x.iter()).map(|(m, x)| normal_log_pdf(*m, stddev * stddev, *x)).sum()
+    }
+
+    fn entropy(mean: &Array1<f64>, stddev: &f64) -> f64 {
+        mean.len() as f64 * normal_entropy(stddev * stddev)
+    }
+
+    fn kl_divergence(mp: &Array1<f64>, sp: &f64, mq: &Array1<f64>, sq: &f64) -> f64 {
+        mp.iter().zip(mq.iter()).map(|(a, b)| normal_kl(*a, sp * sp, *b, sq * sq)).sum()
+    }
 }
 
 impl DistBuilder<Array1<f64>, Array1<f64>> for GB {
@@ -59,6 +134,26 @@ impl DistBuilder<Array1<f64>, Array1<f64>> for GB {
 
         MultivariateNormal::new(mean, sigma)
     }
+
+    // Note: consistent with `build`, the diagonal entries are taken to be the
+    // variances of the distribution.
+    fn log_density(mean: &Array1<f64>, stddev: &Array1<f64>, x: &Array1<f64>) -> f64 {
+        (0..mean.len()).map(|i| normal_log_pdf(mean[i], stddev[i], x[i])).sum()
+    }
+
+    // Note: consistent with `build`, the diagonal entries are taken to be the
+    // variances of the distribution.
+    fn entropy(_: &Array1<f64>, stddev: &Array1<f64>) -> f64 {
+        stddev.iter().map(|&v| normal_entropy(v)).sum()
+    }
+
+    fn kl_divergence(
+        mp: &Array1<f64>, vp: &Array1<f64>,
+        mq: &Array1<f64>, vq: &Array1<f64>,
+    ) -> f64
+    {
+        (0..mp.len()).map(|i| normal_kl(mp[i], vp[i], mq[i], vq[i])).sum()
+    }
 }
 
 impl DistBuilder<Array1<f64>, Array2<f64>> for GB {
@@ -67,4 +162,68 @@ impl DistBuilder<Array1<f64>, Array2<f64>> for GB {
     fn build(mean: Array1<f64>, sigma: Array2<f64>) -> MultivariateNormal {
         MultivariateNormal::new(mean, sigma)
     }
+
+    fn log_density(mean: &Array1<f64>, sigma: &Array2<f64>, x: &Array1<f64>) -> f64 {
+        let l = sigma.cholesky(UPLO::Lower).expect("Covariance must be positive definite.");
+        let log_det = 2.0 * l.diag().fold(0.0, |acc, d| acc + d.ln());
+
+        let prec = sigma.inv().expect("Covariance must be positive definite.");
+        let diff = x - mean;
+
+        -0.5 * (diff.dot(&prec.dot(&diff)) + mean.len() as f64 * (2.0 * PI).ln() + log_det)
+    }
+
+    fn entropy(mean: &Array1<f64>, sigma: &Array2<f64>) -> f64 {
+        let l = sigma.cholesky(UPLO::Lower).expect("Covariance must be positive definite.");
+        let log_det = 2.0 * l.diag().fold(0.0, |acc, d| acc + d.ln());
+
+        0.5 * (mean.len() as f64 * (2.0 * PI * E).ln() + log_det)
+    }
+
+    fn kl_divergence(
+        mp: &Array1<f64>, sigma_p: &Array2<f64>,
+        mq: &Array1<f64>, sigma_q: &Array2<f64>,
+    ) -> f64
+    {
+        let log_det = |sigma: &Array2<f64>| {
+            let l = sigma.cholesky(UPLO::Lower).expect("Covariance must be positive definite.");
+
+            2.0 * l.diag().fold(0.0, |acc, d| acc + d.ln())
+        };
+
+        let prec_q = sigma_q.inv().expect("Covariance must be positive definite.");
+        let diff = mq - mp;
+
+        let trace = prec_q.dot(sigma_p).diag().sum();
+        let mahalanobis = diff.dot(&prec_q.dot(&diff));
+
+        0.5 * (trace + mahalanobis - mp.len() as f64 + log_det(sigma_q) - log_det(sigma_p))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ndarray::Array1;
+    use rstat::ContinuousDistribution;
+    use super::{DistBuilder, GB};
+
+    #[test]
+    fn test_log_density_univariate() {
+        let expected = <GB as DistBuilder<f64, f64>>::build(1.0, 2.0).pdf(0.5).ln();
+        let log_density = <GB as DistBuilder<f64, f64>>::log_density(&1.0, &2.0, &0.5);
+
+        assert!((log_density - expected).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_log_density_full() {
+        let mean: Array1<f64> = array![0.0, 1.0];
+        let sigma = array![[4.0, 1.0], [1.0, 1.25]];
+        let x: Array1<f64> = array![1.0, 2.0];
+
+        // With |Σ| = 4 and (x - μ)ᵀ Σ⁻¹ (x - μ) = 0.8125:
+        let expected = -0.5 * 0.8125 - (2.0 * ::std::f64::consts::PI * 2.0).ln();
+
+        assert!((GB::log_density(&mean, &sigma, &x) - expected).abs() < 1e-10);
+    }
 }
@@ -1,6 +1,6 @@
 use crate::{
     fa::{Parameterised, StateFunction, Weights, WeightsView, WeightsViewMut},
-    policies::{DifferentiablePolicy, Policy},
+    policies::{DifferentiablePolicy, Divergence, Entropy, Policy},
     spaces::Space,
 };
 use ndarray::{Array2, ArrayView2, Axis};
@@ -15,6 +15,7 @@ pub mod stddev;
 use self::stddev::StdDev;
 
 import_all!(dbuilder);
+import_all!(multivariate);
 
 #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
 #[derive(Clone, Debug)]
@@ -73,6 +74,46 @@ where
     where Self::Action: Clone {
         GB::build(self.compute_mean(input), self.compute_stddev(input)).pdf(a.clone())
     }
+
+    fn log_probability(&self, input: &I, a: &Self::Action) -> f64 {
+        GB::log_density(&self.compute_mean(input), &self.compute_stddev(input), a)
+    }
+}
+
+impl<I, M, S> Entropy<I> for Gaussian<M, S>
+where
+    M: Mean<I, <S as StateFunction<I>>::Output>,
+    M::Output: Clone + Debug,
+    S: StdDev<I, <M as StateFunction<I>>::Output>,
+    S::Output: Clone + Debug,
+    GB: DistBuilder<M::Output, S::Output>,
+    GBSupport<M::Output, S::Output>: Space<Value = M::Output>,
+{
+    fn entropy(&self, input: &I) -> f64 {
+        <GB as DistBuilder<M::Output, S::Output>>::entropy(
+            &self.compute_mean(input),
+            &self.compute_stddev(input),
+        )
+    }
+}
+
+impl<I, M, S> Divergence<I> for Gaussian<M, S>
+where
+    M: Mean<I, <S as StateFunction<I>>::Output>,
+    M::Output: Clone + Debug,
+    S: StdDev<I, <M as StateFunction<I>>::Output>,
+    S::Output: Clone + Debug,
+    GB: DistBuilder<M::Output, S::Output>,
+    GBSupport<M::Output, S::Output>: Space<Value = M::Output>,
+{
+    fn kl_divergence(&self, other: &Self, input: &I) -> f64 {
+        <GB as DistBuilder<M::Output, S::Output>>::kl_divergence(
+            &self.compute_mean(input),
+            &self.compute_stddev(input),
+            &other.compute_mean(input),
+            &other.compute_stddev(input),
+        )
+    }
 }
 
 impl<I, M, S> DifferentiablePolicy<I> for Gaussian<M, S>
@@ -0,0 +1,323 @@
+use crate::{
+    DerefSlice,
+    fa::{Parameterised, Weights, WeightsView, WeightsViewMut, linear::basis::Projector},
+    policies::{DifferentiablePolicy, Entropy, Policy},
+};
+use ndarray::{Array1, Array2, ArrayView2};
+use rand::Rng;
+use rstat::{Distribution, univariate::continuous::Normal};
+use std::f64::consts::{E, PI};
+
+// Solve L x = b for lower-triangular L by forward substitution.
+fn solve_lower(l: &Array2<f64>, b: &Array1<f64>) -> Array1<f64> {
+    let mut x = Array1::zeros(b.len());
+
+    for i in 0..b.len() {
+        let s = (0..i).fold(b[i], |acc, j| acc - l[[i, j]] * x[j]);
+
+        x[i] = s / l[[i, i]];
+    }
+
+    x
+}
+
+// Solve Lᵀ x = b for lower-triangular L by backward substitution.
+fn solve_upper_t(l: &Array2<f64>, b: &Array1<f64>) -> Array1<f64> {
+    let n = b.len();
+    let mut x = Array1::zeros(n);
+
+    for i in (0..n).rev() {
+        let s = ((i + 1)..n).fold(b[i], |acc, j| acc - l[[j, i]] * x[j]);
+
+        x[i] = s / l[[i, i]];
+    }
+
+    x
+}
+
+/// Multivariate Gaussian policy with a learned full covariance matrix.
+///
+/// The mean is linear in the features of the state, `μ(s) = Wᵀφ(s)`, and the
+/// covariance is parameterised by its Cholesky factor, `Σ = L Lᵀ`. The
+/// diagonal of `L` is stored in log-space to guarantee positive definiteness,
+/// and the factor may either be state-independent or linear in `φ(s)`.
+///
+/// The weights are stacked row-wise, `[W; C]`, where `C` holds one column for
+/// each of the `d(d + 1) / 2` entries of the lower triangle of `L` (in
+/// row-major order). Unused entries of the stacked matrix are zero padding.
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug)]
+pub struct MultivariateGaussian<B> {
+    pub basis: B,
+
+    n_dims: usize,
+    state_dependent: bool,
+    weights: Array2<f64>,
+}
+
+impl<B: Projector> MultivariateGaussian<B> {
+    fn build(basis: B, n_dims: usize, state_dependent: bool) -> Self {
+        let n_features = basis.n_features();
+        let n_tril = n_dims * (n_dims + 1) / 2;
+        let n_cov_rows = if state_dependent { n_features } else { 1 };
+
+        MultivariateGaussian {
+            basis,
+
+            n_dims,
+            state_dependent,
+            weights: Array2::zeros((n_features + n_cov_rows, n_dims.max(n_tril))),
+        }
+    }
+
+    /// Construct a policy with a state-independent covariance, initialised to
+    /// the identity.
+    pub fn new(basis: B, n_dims: usize) -> Self {
+        MultivariateGaussian::build(basis, n_dims, false)
+    }
+
+    /// Construct a policy with a state-dependent covariance, initialised to the
+    /// identity.
+    pub fn state_dependent(basis: B, n_dims: usize) -> Self {
+        MultivariateGaussian::build(basis, n_dims, true)
+    }
+
+    /// Set the initial covariance from its (lower-triangular) Cholesky factor.
+    ///
+    /// For a state-dependent covariance, the factor is written to the weights
+    /// of the first feature only. The initial covariance is therefore only `L
+    /// Lᵀ` in every state if the first feature of the basis is constant and
+    /// equal to one; with any other basis, the weights must be initialised
+    /// directly via `weights_view_mut`.
+    pub fn with_cholesky(mut self, l: Array2<f64>) -> Self {
+        let offset = self.basis.n_features();
+
+        for (k, (i, j)) in self.tril_indices().into_iter().enumerate() {
+            self.weights[[offset, k]] = if i == j { l[[i, i]].ln() } else { l[[i, j]] };
+        }
+
+        self
+    }
+
+    fn tril_indices(&self) -> Vec<(usize, usize)> {
+        (0..self.n_dims).flat_map(|i| (0..=i).map(move |j| (i, j))).collect()
+    }
+
+    fn phi<I: DerefSlice>(&self, input: &I) -> Array1<f64> {
+        self.basis.project(input.deref_slice()).unwrap().expanded()
+    }
+
+    // Features driving the Cholesky factor.
+    fn psi(&self, phi: &Array1<f64>) -> Array1<f64> {
+        if self.state_dependent { phi.clone() } else { Array1::ones(1) }
+    }
+
+    fn mean_from_phi(&self, phi: &Array1<f64>) -> Array1<f64> {
+        let n = self.basis.n_features();
+
+        self.weights.slice(s![0..n, 0..self.n_dims]).t().dot(phi)
+    }
+
+    fn cholesky_from_phi(&self, phi: &Array1<f64>) -> Array2<f64> {
+        let n = self.basis.n_features();
+        let raw = self.weights.slice(s![n.., ..]).t().dot(&self.psi(phi));
+
+        let mut l = Array2::zeros((self.n_dims, self.n_dims));
+
+        for (k, (i, j)) in self.tril_indices().into_iter().enumerate() {
+            l[[i, j]] = if i == j { raw[k].exp() } else { raw[k] };
+        }
+
+        l
+    }
+
+    /// Return the mean action in state `input`.
+    pub fn compute_mean<I: DerefSlice>(&self, input: &I) -> Array1<f64> {
+        self.mean_from_phi(&self.phi(input))
+    }
+
+    /// Return the Cholesky factor of the covariance in state `input`.
+    pub fn compute_cholesky<I: DerefSlice>(&self, input: &I) -> Array2<f64> {
+        self.cholesky_from_phi(&self.phi(input))
+    }
+
+    /// Return the covariance of the action distribution in state `input`.
+    pub fn compute_covariance<I: DerefSlice>(&self, input: &I) -> Array2<f64> {
+        let l = self.compute_cholesky(input);
+
+        l.dot(&l.t())
+    }
+}
+
+impl<I: DerefSlice, B: Projector> Policy<I> for MultivariateGaussian<B> {
+    type Action = Vec<f64>;
+
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R, input: &I) -> Vec<f64> {
+        let standard = Normal::new(0.0, 1.0);
+
+        let phi = self.phi(input);
+        let l = self.cholesky_from_phi(&phi);
+        let z = Array1::from_shape_fn(self.n_dims, |_| standard.sample(rng));
+
+        (self.mean_from_phi(&phi) + l.dot(&z)).into_raw_vec()
+    }
+
+    fn mpa(&self, input: &I) -> Vec<f64> { self.compute_mean(input).into_raw_vec() }
+
+    fn probability(&self, input: &I, a: &Vec<f64>) -> f64 {
+        self.log_probability(input, a).exp()
+    }
+
+    fn log_probability(&self, input: &I, a: &Vec<f64>) -> f64 {
+        let phi = self.phi(input);
+        let l = self.cholesky_from_phi(&phi);
+        let r = Array1::from_vec(a.to_vec()) - self.mean_from_phi(&phi);
+        let y = solve_lower(&l, &r);
+
+        let log_det = l.diag().fold(0.0, |acc, d| acc + d.ln());
+
+        -0.5 * (y.dot(&y) + self.n_dims as f64 * (2.0 * PI).ln()) - log_det
+    }
+}
+
+impl<I: DerefSlice, B: Projector> Entropy<I> for MultivariateGaussian<B> {
+    fn entropy(&self, input: &I) -> f64 {
+        let l = self.compute_cholesky(input);
+
+        0.5 * self.n_dims as f64 * (2.0 * PI * E).ln() + l.diag().fold(0.0, |acc, d| acc + d.ln())
+    }
+}
+
+impl<B: Projector> Parameterised for MultivariateGaussian<B> {
+    fn weights(&self) -> Weights { self.weights.clone() }
+
+    fn weights_view(&self) -> WeightsView { self.weights.view() }
+
+    fn weights_view_mut(&mut self) -> WeightsViewMut { self.weights.view_mut() }
+}
+
+impl<I: DerefSlice, B: Projector> DifferentiablePolicy<I> for MultivariateGaussian<B> {
+    fn update(&mut self, input: &I, a: &Vec<f64>, error: f64) {
+        let grad = self.grad_log(input, a);
+
+        self.weights.scaled_add(error, &grad);
+    }
+
+    fn update_grad(&mut self, grad: &ArrayView2<f64>) {
+        self.weights.scaled_add(1.0, grad);
+    }
+
+    fn update_grad_scaled(&mut self, grad: &ArrayView2<f64>, factor: f64) {
+        self.weights.scaled_add(factor, grad);
+    }
+
+    fn grad_log(&self, input: &I, a: &Vec<f64>) -> Array2<f64> {
+        let n = self.basis.n_features();
+
+        let phi = self.phi(input);
+        let psi = self.psi(&phi);
+        let l = self.cholesky_from_phi(&phi);
+
+        // y = L⁻¹ (a - μ) and z = L⁻ᵀ y = Σ⁻¹ (a - μ):
+        let r = Array1::from_vec(a.clone()) - self.mean_from_phi(&phi);
+        let y = solve_lower(&l, &r);
+        let z = solve_upper_t(&l, &y);
+
+        let mut grad = Array2::zeros(self.weights.dim());
+
+        // ∂ ln π / ∂μ = Σ⁻¹ (a - μ):
+        for j in 0..self.n_dims {
+            grad.slice_mut(s![0..n, j]).scaled_add(z[j], &phi);
+        }
+
+        // ∂ ln π / ∂L = tril(z yᵀ) - diag(1 / L), with the chain rule through
+        // the log-space diagonal:
+        for (k, (i, j)) in self.tril_indices().into_iter().enumerate() {
+            let g = if i == j {
+                z[i] * y[i] * l[[i, i]] - 1.0
+            } else {
+                z[i] * y[j]
+            };
+
+            grad.slice_mut(s![n.., k]).scaled_add(g, &psi);
+        }
+
+        grad
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        fa::{Parameterised, linear::basis::{Polynomial, Projector}},
+        policies::{DifferentiablePolicy, Policy},
+    };
+    use std::f64::consts::PI;
+    use super::MultivariateGaussian;
+
+    fn cholesky() -> ndarray::Array2<f64> { array![[2.0, 0.0], [0.5, 1.0]] }
+
+    #[test]
+    fn test_density() {
+        let p = MultivariateGaussian::new(Polynomial::new(1, 1).with_constant(), 2)
+            .with_cholesky(cholesky());
+
+        // Σ = [[4, 1], [1, 1.25]], such that |Σ| = 4 and Σ⁻¹ = [[1.25, -1], [-1, 4]] / 4:
+        let sigma = p.compute_covariance(&vec![0.0]);
+
+        assert!((sigma[[0, 0]] - 4.0).abs() < 1e-10);
+        assert!((sigma[[0, 1]] - 1.0).abs() < 1e-10);
+        assert!((sigma[[1, 1]] - 1.25).abs() < 1e-10);
+
+        let a = vec![1.0, 1.0];
+        let mahalanobis: f64 = (1.25 - 2.0 + 4.0) / 4.0;
+        let expected = (-0.5 * mahalanobis).exp() / (2.0 * PI * 4.0f64.sqrt());
+
+        assert!((p.probability(&vec![0.0], &a) - expected).abs() < 1e-10);
+        assert!((p.log_probability(&vec![0.0], &a) - expected.ln()).abs() < 1e-10);
+    }
+
+    fn check_grad_log<B: Projector>(mut p: MultivariateGaussian<B>) {
+        const EPS: f64 = 1e-6;
+
+        let s = vec![0.5];
+        let a = vec![1.0, -0.5];
+
+        p.weights_view_mut().slice_mut(s![0..2, 0..2]).assign(&array![[0.3, -0.2], [0.1, 0.4]]);
+
+        let grad = p.grad_log(&s, &a);
+        let (n_rows, n_cols) = grad.dim();
+
+        for i in 0..n_rows {
+            for j in 0..n_cols {
+                p.weights_view_mut()[[i, j]] += EPS;
+                let lp_plus = p.log_probability(&s, &a);
+
+                p.weights_view_mut()[[i, j]] -= 2.0 * EPS;
+                let lp_minus = p.log_probability(&s, &a);
+
+                p.weights_view_mut()[[i, j]] += EPS;
+
+                let fd = (lp_plus - lp_minus) / (2.0 * EPS);
+
+                assert!((grad[[i, j]] - fd).abs() < 1e-5, "∂/∂w[{}, {}]", i, j);
+            }
+        }
+    }
+
+    #[test]
+    fn test_grad_log() {
+        check_grad_log(
+            MultivariateGaussian::new(Polynomial::new(1, 1).with_constant(), 2)
+                .with_cholesky(cholesky())
+        );
+    }
+
+    #[test]
+    fn test_grad_log_state_dependent() {
+        check_grad_log(
+            MultivariateGaussian::state_dependent(Polynomial::new(1, 1).with_constant(), 2)
+                .with_cholesky(cholesky())
+        );
+    }
+}
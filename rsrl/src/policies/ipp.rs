@@ -1,138 +1,343 @@
 use crate::{
-    fa::{Parameterised, Weights, WeightsView, WeightsViewMut},
-    policies::{DifferentiablePolicy, Policy},
+    fa::{Parameterised, Stackable, Stacked, Weights, WeightsView, WeightsViewMut},
+    policies::{DifferentiablePolicy, Entropy, Policy},
 };
 use ndarray::{Array2, ArrayView2, Axis};
 use rand::Rng;
+use std::cell::Ref;
 
-/// Independent Policy Pair (IPP).
+/// Independent Product Policy (IPP).
+///
+/// Joint policy over a product of action spaces in which each component is
+/// sampled independently from its own sub-policy; i.e. `π(a | s) = ∏ πᵢ(aᵢ |
+/// s)`. The components may be given as a tuple of between two and four
+/// (possibly distinct) policies, or as a `Vec` of homogeneous policies. The
+/// weights of the sub-policies are stacked column-wise, with fewer rows padded
+/// by zeros. Sub-policies without contiguous weights, such as `Gaussian`, are
+/// supported, except for writes made directly through `weights_view_mut`.
 #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
 #[derive(Clone, Debug)]
-pub struct IPP<P1, P2>(pub P1, pub P2);
+pub struct IPP<C>(Stacked<C>);
 
-impl<P1, P2> IPP<P1, P2> {
-    pub fn new(p1: P1, p2: P2) -> Self { IPP(p1, p2) }
+impl<C: Stackable> IPP<C> {
+    /// Construct a joint policy from a tuple or `Vec` of sub-policies.
+    pub fn from_policies(policies: C) -> Self { IPP(Stacked::new(policies, Axis(1))) }
+
+    /// Return a reference to the sub-policies.
+    pub fn policies(&self) -> Ref<C> { self.0.get() }
+
+    /// Apply a closure to the sub-policies, keeping the stacked weights in sync.
+    pub fn modify<R>(&mut self, f: impl FnOnce(&mut C) -> R) -> R { self.0.modify(f) }
+
+    /// Consume the policy and return the sub-policies.
+    pub fn into_inner(self) -> C { self.0.into_inner() }
+
+    // Stack the gradients of each sub-policy into the layout of the weights.
+    fn stack_grads(&self, grads: Vec<Array2<f64>>) -> Array2<f64> {
+        let mut grad = Array2::zeros(self.0.weights_dim());
+
+        for (g, ([r0, r1], [c0, c1])) in grads.into_iter().zip(self.0.component_ranges()) {
+            if r1 > r0 && c1 > c0 {
+                grad.slice_mut(s![r0..r1, c0..c1]).assign(&g);
+            }
+        }
+
+        grad
+    }
+
+    // Apply `f` to each sub-policy with its slice of the stacked gradient.
+    fn apply_grads(
+        &mut self,
+        grad: &ArrayView2<f64>,
+        f: impl Fn(&mut C, usize, &ArrayView2<f64>),
+    ) {
+        let ranges = self.0.component_ranges();
+
+        for (i, ([r0, r1], [c0, c1])) in ranges.into_iter().enumerate() {
+            if r1 > r0 && c1 > c0 {
+                self.0.modify_component(i, |ps| f(ps, i, &grad.slice(s![r0..r1, c0..c1])));
+            }
+        }
+    }
+}
+
+impl<P1: Parameterised, P2: Parameterised> IPP<(P1, P2)> {
+    pub fn new(p1: P1, p2: P2) -> Self { IPP::from_policies((p1, p2)) }
+
+    /// Return a reference to the first sub-policy.
+    pub fn first(&self) -> Ref<P1> { Ref::map(self.policies(), |ps| &ps.0) }
+
+    /// Return a reference to the second sub-policy.
+    pub fn second(&self) -> Ref<P2> { Ref::map(self.policies(), |ps| &ps.1) }
+}
+
+macro_rules! impl_ipp_tuple {
+    ($($idx:tt => $t:ident),+) => {
+        impl<S, $($t),+> Policy<S> for IPP<($($t,)+)>
+        where
+            $($t: Policy<S> + Parameterised),+
+        {
+            type Action = ($($t::Action,)+);
+
+            fn sample<R: Rng + ?Sized>(&self, rng: &mut R, s: &S) -> Self::Action {
+                let ps = self.policies();
+
+                ($(ps.$idx.sample(rng, s),)+)
+            }
+
+            fn mpa(&self, s: &S) -> Self::Action {
+                let ps = self.policies();
+
+                ($(ps.$idx.mpa(s),)+)
+            }
+
+            fn probability(&self, s: &S, a: &Self::Action) -> f64 {
+                let ps = self.policies();
+
+                1.0 $(* ps.$idx.probability(s, &a.$idx))+
+            }
+
+            fn log_probability(&self, s: &S, a: &Self::Action) -> f64 {
+                let ps = self.policies();
+
+                0.0 $(+ ps.$idx.log_probability(s, &a.$idx))+
+            }
+
+            fn handle_terminal(&mut self) {
+                self.0.modify(|ps| { $(ps.$idx.handle_terminal();)+ })
+            }
+        }
+
+        impl<S, $($t),+> Entropy<S> for IPP<($($t,)+)>
+        where
+            $($t: Entropy<S> + Parameterised),+
+        {
+            fn entropy(&self, s: &S) -> f64 {
+                let ps = self.policies();
+
+                0.0 $(+ ps.$idx.entropy(s))+
+            }
+        }
+
+        impl<S, $($t),+> DifferentiablePolicy<S> for IPP<($($t,)+)>
+        where
+            $($t: DifferentiablePolicy<S>),+
+        {
+            fn update(&mut self, input: &S, a: &Self::Action, error: f64) {
+                $(self.0.modify_component($idx, |ps| ps.$idx.update(input, &a.$idx, error));)+
+            }
+
+            fn update_grad(&mut self, grad: &ArrayView2<f64>) {
+                self.apply_grads(grad, |ps, i, g| match i {
+                    $($idx => ps.$idx.update_grad(g),)+
+                    _ => unreachable!(),
+                })
+            }
+
+            fn update_grad_scaled(&mut self, grad: &ArrayView2<f64>, factor: f64) {
+                self.apply_grads(grad, |ps, i, g| match i {
+                    $($idx => ps.$idx.update_grad_scaled(g, factor),)+
+                    _ => unreachable!(),
+                })
+            }
+
+            fn grad_log(&self, input: &S, a: &Self::Action) -> Array2<f64> {
+                let grads = {
+                    let ps = self.policies();
+
+                    vec![$(ps.$idx.grad_log(input, &a.$idx)),+]
+                };
+
+                self.stack_grads(grads)
+            }
+        }
+    };
 }
 
-impl<S, P1, P2> Policy<S> for IPP<P1, P2>
+impl_ipp_tuple!(0 => A, 1 => B);
+impl_ipp_tuple!(0 => A, 1 => B, 2 => C);
+impl_ipp_tuple!(0 => A, 1 => B, 2 => C, 3 => D);
+
+impl<S, P> Policy<S> for IPP<Vec<P>>
 where
-    P1: Policy<S>,
-    P2: Policy<S>,
+    P: Policy<S> + Parameterised,
 {
-    type Action = (P1::Action, P2::Action);
+    type Action = Vec<P::Action>;
+
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R, s: &S) -> Vec<P::Action> {
+        self.policies().iter().map(|p| p.sample(rng, s)).collect()
+    }
+
+    fn mpa(&self, s: &S) -> Vec<P::Action> {
+        self.policies().iter().map(|p| p.mpa(s)).collect()
+    }
 
-    fn sample<R: Rng + ?Sized>(&self, rng: &mut R, s: &S) -> (P1::Action, P2::Action) {
-        (self.0.sample(rng, s), self.1.sample(rng, s))
+    fn probability(&self, s: &S, a: &Vec<P::Action>) -> f64 {
+        self.policies().iter().zip(a.iter()).map(|(p, a)| p.probability(s, a)).product()
     }
 
-    fn mpa(&self, s: &S) -> (P1::Action, P2::Action) {
-        (self.0.mpa(s), self.1.mpa(s))
+    fn log_probability(&self, s: &S, a: &Vec<P::Action>) -> f64 {
+        self.policies().iter().zip(a.iter()).map(|(p, a)| p.log_probability(s, a)).sum()
     }
 
-    fn probability(&self, s: &S, a: &(P1::Action, P2:: Action)) -> f64 {
-        self.0.probability(s, &a.0) * self.1.probability(s, &a.1)
+    fn handle_terminal(&mut self) {
+        self.0.modify(|ps| ps.iter_mut().for_each(|p| p.handle_terminal()))
     }
 }
 
-impl<S, P1, P2> DifferentiablePolicy<S> for IPP<P1, P2>
+impl<S, P> Entropy<S> for IPP<Vec<P>>
 where
-    P1: DifferentiablePolicy<S>,
-    P2: DifferentiablePolicy<S>,
+    P: Entropy<S> + Parameterised,
 {
-    fn update(&mut self, input: &S, a: &Self::Action, error: f64) {
-        self.0.update(input, &a.0, error);
-        self.1.update(input, &a.1, error);
+    fn entropy(&self, s: &S) -> f64 {
+        self.policies().iter().map(|p| p.entropy(s)).sum()
     }
+}
 
-    fn update_grad(&mut self, grad: &ArrayView2<f64>) {
-        let w_0 = self.0.weights_dim();
+impl<S, P> DifferentiablePolicy<S> for IPP<Vec<P>>
+where
+    P: DifferentiablePolicy<S>,
+{
+    fn update(&mut self, input: &S, a: &Vec<P::Action>, error: f64) {
+        for (i, a) in a.iter().enumerate() {
+            self.0.modify_component(i, |ps| ps[i].update(input, a, error));
+        }
+    }
 
-        match w_0 {
-            [r, c] if r > 0 => {
-                let grad_0 = grad.slice(s![0..r, 0..c]);
+    fn update_grad(&mut self, grad: &ArrayView2<f64>) {
+        self.apply_grads(grad, |ps, i, g| ps[i].update_grad(g))
+    }
 
-                self.0.update_grad(&grad_0);
-            },
-            _ => {},
-        }
+    fn update_grad_scaled(&mut self, grad: &ArrayView2<f64>, factor: f64) {
+        self.apply_grads(grad, |ps, i, g| ps[i].update_grad_scaled(g, factor))
+    }
 
-        match self.1.weights_dim() {
-            [r, c] if r > 0 => {
-                let grad_1 = grad.slice(s![0..r, w_0[1]..(w_0[1] + c)]);
+    fn grad_log(&self, input: &S, a: &Vec<P::Action>) -> Array2<f64> {
+        let grads = self.policies().iter().zip(a.iter()).map(|(p, a)| {
+            p.grad_log(input, a)
+        }).collect();
 
-                self.1.update_grad(&grad_1);
-            },
-            _ => {},
-        }
+        self.stack_grads(grads)
     }
+}
 
-    fn update_grad_scaled(&mut self, grad: &ArrayView2<f64>, factor: f64) {
-        let w_0 = self.0.weights_dim();
+impl<C: Stackable> Parameterised for IPP<C> {
+    fn weights(&self) -> Weights { self.0.weights() }
 
-        match w_0 {
-            [r, c] if r > 0 => {
-                let grad_0 = grad.slice(s![0..r, 0..c]);
+    fn weights_view(&self) -> WeightsView { self.0.weights_view() }
 
-                self.0.update_grad_scaled(&grad_0, factor);
-            },
-            _ => {},
-        }
+    fn weights_view_mut(&mut self) -> WeightsViewMut { self.0.weights_view_mut() }
 
-        match self.1.weights_dim() {
-            [r, c] if r > 0 => {
-                let grad_1 = grad.slice(s![0..r, w_0[1]..(w_0[1] + c)]);
+    fn weights_dim(&self) -> [usize; 2] { self.0.weights_dim() }
+}
 
-                self.1.update_grad_scaled(&grad_1, factor);
-            },
-            _ => {},
-        }
-    }
+#[cfg(test)]
+mod tests {
+    use crate::{
+        fa::{Parameterised, linear::{LFA, basis::{Polynomial, Projector}, optim::SGD}},
+        policies::{DifferentiablePolicy, Entropy, Gibbs, Policy, gaussian::{self, Gaussian}},
+    };
+    use super::IPP;
 
-    fn grad_log(&self, input: &S, a: &Self::Action) -> Array2<f64> {
-        let mut gl_0 = self.0.grad_log(input, &a.0);
-        let nr_0 = gl_0.rows();
+    macro_rules! make_policy {
+        ($n_actions:expr) => {
+            Gibbs::standard(LFA::vector(
+                Polynomial::new(1, 1).with_constant(), SGD(1.0), $n_actions
+            ))
+        };
+    }
 
-        let mut gl_1 = self.1.grad_log(input, &a.1);
-        let nr_1 = gl_1.rows();
+    macro_rules! make_gaussian {
+        () => {
+            Gaussian::new(
+                gaussian::mean::Scalar(
+                    LFA::scalar(Polynomial::new(1, 1).with_constant(), SGD(1.0))
+                ),
+                gaussian::stddev::Constant(1.0),
+            )
+        };
+    }
 
-        fn resize(gl: Array2<f64>, n_rows: usize) -> Array2<f64> {
-            let gl_rows = gl.rows();
+    #[test]
+    fn test_probability() {
+        let p = IPP::from_policies(vec![make_policy!(2), make_policy!(4)]);
 
-            let mut new_gl = unsafe { Array2::uninitialized((n_rows, gl.cols())) };
+        assert!((p.probability(&vec![0.0], &vec![0, 3]) - 0.125).abs() < 1e-7);
+        assert!((p.entropy(&vec![0.0]) - 8.0f64.ln()).abs() < 1e-7);
+    }
 
-            new_gl.slice_mut(s![0..gl_rows, ..]).assign(&gl);
-            new_gl.slice_mut(s![gl_rows.., ..]).fill(0.0);
+    #[test]
+    fn test_weights_view() {
+        let n = Polynomial::new(1, 1).with_constant().n_features();
+        let mut p = IPP::new(make_policy!(2), make_policy!(3));
 
-            new_gl
-        }
+        assert_eq!(p.weights_dim(), [n, 5]);
 
-        if nr_0 > nr_1 {
-            gl_1 = resize(gl_1, nr_0);
-        } else if nr_0 < nr_1 {
-            gl_0 = resize(gl_0, nr_1);
-        }
+        p.weights_view_mut().column_mut(4).fill(10.0);
 
-        stack![Axis(1), gl_0, gl_1]
+        assert_eq!(p.mpa(&vec![1.0]), (0, 2));
+        assert_eq!(p.second().weights().column(2).to_vec(), vec![10.0; n]);
     }
-}
 
-impl<P1: Parameterised, P2: Parameterised> Parameterised for IPP<P1, P2> {
-    fn weights(&self) -> Weights {
-        stack![Axis(1), self.0.weights(), self.1.weights()]
-    }
+    #[test]
+    fn test_triple() {
+        let mut p = IPP::from_policies((make_policy!(2), make_policy!(3), make_policy!(4)));
+
+        let s = vec![1.0];
+        let a = (1, 2, 3);
 
-    fn weights_view(&self) -> WeightsView {
-        unimplemented!()
+        assert!((p.probability(&s, &a) - 1.0 / 24.0).abs() < 1e-7);
+        assert!((p.log_probability(&s, &a) + 24.0f64.ln()).abs() < 1e-7);
+        assert!((p.entropy(&s) - 24.0f64.ln()).abs() < 1e-7);
+
+        let gl = p.grad_log(&s, &a);
+
+        assert_eq!(gl.dim(), (p.weights_dim()[0], 9));
+
+        p.update_grad(&gl.view());
+
+        assert_eq!(p.mpa(&s), a);
+        assert_eq!(p.weights(), gl);
+        assert_eq!(p.policies().2.weights(), gl.slice(s![.., 5..9]));
     }
 
-    fn weights_view_mut(&mut self) -> WeightsViewMut {
-        unimplemented!()
+    #[test]
+    fn test_update_grad() {
+        let mut p = IPP::from_policies(vec![make_policy!(2), make_policy!(2)]);
+
+        let s = vec![1.0];
+        let gl = p.grad_log(&s, &vec![1, 0]);
+
+        assert_eq!(gl.dim(), (p.weights_dim()[0], 4));
+
+        p.update_grad(&gl.view());
+
+        assert!(p.probability(&s, &vec![1, 0]) > 0.25);
+        assert_eq!(p.weights(), gl);
     }
 
-    fn weights_dim(&self) -> [usize; 2] {
-        let d0 = self.0.weights_dim();
-        let d1 = self.1.weights_dim();
+    #[test]
+    fn test_gaussians() {
+        let n = Polynomial::new(1, 1).with_constant().n_features();
+        let mut p = IPP::new(make_gaussian!(), make_gaussian!());
+
+        let s = vec![1.0];
+        let a = (1.0, -1.0);
+
+        assert_eq!(p.weights_dim(), [n, 2]);
+        assert_eq!(p.mpa(&s), (0.0, 0.0));
+
+        let gl = p.grad_log(&s, &a);
+
+        p.update_grad_scaled(&gl.view(), 0.1);
+
+        let (m1, m2) = p.mpa(&s);
+
+        assert!(m1 > 0.0 && m2 < 0.0);
+        assert_eq!(p.weights(), &gl * 0.1);
+
+        let lp = p.first().log_probability(&s, &a.0) + p.second().log_probability(&s, &a.1);
 
-        [d0[0].max(d1[0]), d0[1] + d1[1]]
+        assert!((p.log_probability(&s, &a) - lp).abs() < 1e-10);
     }
 }
@@ -0,0 +1,127 @@
+use crate::{
+    domains::ActionMask,
+    policies::{sample_probs_with_rng, EnumerablePolicy, Policy},
+    utils::argmaxima,
+};
+use rand::Rng;
+
+/// Enumerable policy restricted to a state-dependent set of legal actions.
+///
+/// The probabilities of the base policy are zeroed for illegal actions and
+/// renormalised over the remaining legal actions. If the base policy places no
+/// mass on any legal action (e.g. a `Greedy` policy whose maximiser is
+/// illegal), the distribution falls back to uniform over the legal actions. In
+/// states without any legal actions (e.g. terminal states), the distribution
+/// of the base policy is returned unchanged.
+/// For greedy selection among legal values, wrap the action-value function in
+/// a `MaskedQ` instead.
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug)]
+pub struct Masked<P, M> {
+    pub policy: P,
+    pub mask: M,
+}
+
+impl<P, M> Masked<P, M> {
+    pub fn new(policy: P, mask: M) -> Self { Masked { policy, mask } }
+}
+
+impl<S, P, M> Policy<S> for Masked<P, M>
+where
+    P: EnumerablePolicy<S>,
+    M: ActionMask<S>,
+{
+    type Action = usize;
+
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R, s: &S) -> usize {
+        sample_probs_with_rng(rng, &self.probabilities(s))
+    }
+
+    fn mpa(&self, s: &S) -> usize { argmaxima(&self.probabilities(s)).1[0] }
+
+    fn probability(&self, s: &S, a: &usize) -> f64 { self.probabilities(s)[*a] }
+
+    fn handle_terminal(&mut self) { self.policy.handle_terminal() }
+}
+
+impl<S, P, M> EnumerablePolicy<S> for Masked<P, M>
+where
+    P: EnumerablePolicy<S>,
+    M: ActionMask<S>,
+{
+    fn n_actions(&self) -> usize { self.policy.n_actions() }
+
+    fn probabilities(&self, s: &S) -> Vec<f64> {
+        let legal = self.mask.legal_actions(s);
+        let n_legal = legal.iter().filter(|&&l| l).count();
+
+        if n_legal == 0 {
+            return self.policy.probabilities(s);
+        }
+
+        let ps: Vec<f64> = self.policy.probabilities(s).into_iter()
+            .zip(legal.iter())
+            .map(|(p, &l)| if l { p } else { 0.0 })
+            .collect();
+        let z: f64 = ps.iter().sum();
+
+        if z > 0.0 {
+            ps.into_iter().map(|p| p / z).collect()
+        } else {
+            legal.into_iter().map(|l| if l { 1.0 / n_legal as f64 } else { 0.0 }).collect()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        fa::{MaskedQ, mocking::MockQ},
+        policies::{EnumerablePolicy, EpsilonGreedy, Greedy, Policy, Random},
+        utils::compare_floats,
+    };
+    use rand::thread_rng;
+    use super::Masked;
+
+    fn mask(_: &Vec<f64>) -> Vec<bool> { vec![true, false, true, false] }
+
+    #[test]
+    fn test_random() {
+        let p = Masked::new(Random::new(4), mask);
+        let mut rng = thread_rng();
+
+        assert!(compare_floats(p.probabilities(&vec![0.0; 4]), &[0.5, 0.0, 0.5, 0.0], 1e-7));
+
+        for _ in 0..100 {
+            assert!(p.sample(&mut rng, &vec![0.0; 4]) % 2 == 0);
+        }
+    }
+
+    #[test]
+    fn test_greedy_fallback() {
+        let p = Masked::new(Greedy::new(MockQ::new_shared(None)), mask);
+
+        assert!(compare_floats(
+            p.probabilities(&vec![0.0, 1.0, 0.0, 0.0]),
+            &[0.5, 0.0, 0.5, 0.0],
+            1e-7
+        ));
+    }
+
+    #[test]
+    fn test_epsilon_greedy_masked_q() {
+        let q = MaskedQ::new(MockQ::new_shared(Some(vec![1.0, 5.0, 2.0, 0.0])), mask);
+        let p = Masked::new(EpsilonGreedy::new(Greedy::new(q), Random::new(4), 0.4), mask);
+        let s = vec![0.0; 4];
+
+        assert_eq!(p.mpa(&s), 2);
+        assert!(compare_floats(p.probabilities(&s), &[0.125, 0.0, 0.875, 0.0], 1e-7));
+    }
+
+    #[test]
+    fn test_no_legal_actions() {
+        let p = Masked::new(Random::new(2), |_: &Vec<f64>| vec![false, false]);
+
+        assert!(compare_floats(p.probabilities(&vec![0.0; 2]), &[0.5, 0.5], 1e-7));
+    }
+}
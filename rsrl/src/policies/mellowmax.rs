@@ -0,0 +1,155 @@
+use crate::{
+    fa::EnumerableStateActionFunction,
+    policies::{
+        entropy_from_probs,
+        sample_probs_with_rng,
+        softmax::softmax_stable,
+        Entropy,
+        EnumerablePolicy,
+        Policy,
+    },
+    utils::argmaxima,
+};
+use rand::Rng;
+use std::f64;
+
+const ROOT_TOL: f64 = 1e-10;
+const MAX_ITERS: usize = 200;
+
+/// Compute the mellowmax of a set of values, `log(Σ_i exp(ω x_i) / n) / ω`.
+pub fn mellowmax(values: &[f64], omega: f64) -> f64 {
+    let max_v = values.iter().fold(f64::NAN, |acc, &v| f64::max(acc, v));
+    let sum = values.iter().fold(0.0, |acc, v| acc + (omega * (v - max_v)).exp());
+
+    max_v + (sum / values.len() as f64).ln() / omega
+}
+
+// Solve Σ_i exp(β (x_i - mm)) (x_i - mm) = 0 for the inverse temperature, β.
+//
+// The left-hand side is non-decreasing in β and non-positive at β = 0, so the
+// root is bracketed by doubling an upper bound and then found by bisection.
+// All terms are rescaled by the (positive) factor exp(-β (max_i x_i - mm)).
+fn inverse_temperature(values: &[f64], mm: f64) -> f64 {
+    let max_v = values.iter().fold(f64::NAN, |acc, &v| f64::max(acc, v));
+    let f = |beta: f64| values.iter().fold(0.0, |acc, v| {
+        acc + (beta * (v - max_v)).exp() * (v - mm)
+    });
+
+    if max_v - mm < ROOT_TOL { return 0.0; }
+
+    let mut lb = 0.0;
+    let mut ub = 1.0;
+
+    for _ in 0..MAX_ITERS {
+        if f(ub) >= 0.0 { break; }
+
+        lb = ub;
+        ub *= 2.0;
+    }
+
+    for _ in 0..MAX_ITERS {
+        let mid = 0.5 * (lb + ub);
+
+        if f(mid) < 0.0 { lb = mid; } else { ub = mid; }
+        if ub - lb < ROOT_TOL { break; }
+    }
+
+    0.5 * (lb + ub)
+}
+
+/// Maximum entropy mellowmax policy.
+///
+/// The Boltzmann distribution whose expected value matches the mellowmax of
+/// the action-values, `mm_ω(q)`. Unlike `Softmax`, the mellowmax operator is
+/// a non-expansion, so it is a safe choice of target policy in
+/// `ExpectedSARSA` and `QSigma`. The inverse temperature is found per-state
+/// by root-finding.
+///
+/// # References
+/// - Asadi, K., & Littman, M. L. (2017). An alternative softmax operator for
+/// reinforcement learning. In Proceedings of the 34th International
+/// Conference on Machine Learning (pp. 243-252).
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug)]
+pub struct Mellowmax<Q> {
+    q_func: Q,
+
+    pub omega: f64,
+}
+
+impl<Q> Mellowmax<Q> {
+    pub fn new(q_func: Q, omega: f64) -> Self {
+        if omega <= 0.0 {
+            panic!("Omega parameter in Mellowmax must be positive.");
+        }
+
+        Mellowmax { q_func, omega, }
+    }
+
+    /// Return the maximum entropy distribution for a set of action-values.
+    pub fn probabilities_from_qs(&self, qs: &[f64]) -> Vec<f64> {
+        let beta = inverse_temperature(qs, mellowmax(qs, self.omega));
+
+        if beta < ROOT_TOL {
+            vec![1.0 / qs.len() as f64; qs.len()]
+        } else {
+            softmax_stable(qs, 1.0 / beta)
+        }
+    }
+}
+
+impl<S, Q: EnumerableStateActionFunction<S>> Policy<S> for Mellowmax<Q> {
+    type Action = usize;
+
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R, s: &S) -> usize {
+        sample_probs_with_rng(rng, &self.probabilities(s))
+    }
+
+    fn mpa(&self, s: &S) -> usize { argmaxima(&self.q_func.evaluate_all(s)).1[0] }
+
+    fn probability(&self, s: &S, a: &usize) -> f64 { self.probabilities(s)[*a] }
+}
+
+impl<S, Q: EnumerableStateActionFunction<S>> Entropy<S> for Mellowmax<Q> {
+    fn entropy(&self, s: &S) -> f64 { entropy_from_probs(&self.probabilities(s)) }
+}
+
+impl<S, Q: EnumerableStateActionFunction<S>> EnumerablePolicy<S> for Mellowmax<Q> {
+    fn n_actions(&self) -> usize { self.q_func.n_actions() }
+
+    fn probabilities(&self, s: &S) -> Vec<f64> {
+        self.probabilities_from_qs(&self.q_func.evaluate_all(s))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fa::mocking::MockQ;
+    use super::{mellowmax, EnumerablePolicy, Mellowmax};
+
+    #[test]
+    fn test_operator() {
+        assert!((mellowmax(&[1.0, 1.0, 1.0], 5.0) - 1.0).abs() < 1e-7);
+        assert!((mellowmax(&[0.0, 1.0], 1000.0) - 1.0).abs() < 1e-2);
+        assert!((mellowmax(&[0.0, 1.0], 1e-6) - 0.5).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_expected_value() {
+        let p = Mellowmax::new(MockQ::new_shared(None), 2.0);
+        let qs = vec![0.0, 0.5, 2.0];
+
+        let ps = p.probabilities(&qs);
+        let ev: f64 = ps.iter().zip(qs.iter()).map(|(p, q)| p * q).sum();
+
+        assert!((ps.iter().sum::<f64>() - 1.0).abs() < 1e-7);
+        assert!((ev - mellowmax(&qs, 2.0)).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_uniform() {
+        let p = Mellowmax::new(MockQ::new_shared(None), 2.0);
+
+        assert_eq!(p.probabilities(&vec![1.0, 1.0]), vec![0.5, 0.5]);
+    }
+}
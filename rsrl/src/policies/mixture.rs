@@ -0,0 +1,251 @@
+use crate::{
+    fa::{Parameterised, Stackable, Stacked, Weights, WeightsView, WeightsViewMut},
+    policies::{
+        sample_probs_with_rng,
+        softmax::softmax_stable,
+        DifferentiablePolicy,
+        Policy,
+    },
+    utils::argmaxima,
+};
+use ndarray::{Array2, ArrayView2, Axis};
+use rand::Rng;
+use std::cell::Ref;
+
+// Mixture components followed by a [K, 1] column of mixing logits.
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug)]
+struct Components<P> {
+    policies: Vec<P>,
+    logits: Array2<f64>,
+}
+
+impl<P: Parameterised> Stackable for Components<P> {
+    fn component_dims(&self) -> Vec<[usize; 2]> {
+        let mut dims: Vec<_> = self.policies.iter().map(|p| p.weights_dim()).collect();
+
+        dims.push([self.logits.rows(), 1]);
+        dims
+    }
+
+    fn component_weights(&self, i: usize) -> Weights {
+        if i < self.policies.len() { self.policies[i].weights() } else { self.logits.clone() }
+    }
+
+    fn component_view_mut(&mut self, i: usize) -> WeightsViewMut {
+        if i < self.policies.len() {
+            self.policies[i].weights_view_mut()
+        } else {
+            self.logits.view_mut()
+        }
+    }
+}
+
+/// Mixture of policies with learnable, state-independent mixing weights.
+///
+/// Actions are sampled by first drawing a component `k` with probability
+/// `w_k = softmax(l)_k`, and then sampling from `π_k(· | s)`; the density is
+/// `π(a | s) = Σ_k w_k π_k(a | s)`. The components may be any differentiable
+/// policies sharing an action type, discrete or continuous. The weights of the
+/// components are stacked column-wise, followed by a single column holding the
+/// mixing logits, `l`.
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug)]
+pub struct Mixture<P> {
+    params: Stacked<Components<P>>,
+}
+
+impl<P: Parameterised> Mixture<P> {
+    /// Construct a mixture with uniform mixing weights.
+    pub fn new(policies: Vec<P>) -> Self {
+        assert!(!policies.is_empty(), "A mixture requires at least one component.");
+
+        let logits = Array2::zeros((policies.len(), 1));
+
+        Mixture {
+            params: Stacked::new(Components { policies, logits, }, Axis(1)),
+        }
+    }
+
+    /// Return a reference to the component policies.
+    pub fn policies(&self) -> Ref<Vec<P>> { Ref::map(self.params.get(), |c| &c.policies) }
+
+    /// Return the current mixing weights.
+    pub fn mixing_weights(&self) -> Vec<f64> {
+        softmax_stable(self.params.get().logits.as_slice_memory_order().unwrap(), 1.0)
+    }
+
+    // Return the posterior probability of each component having generated `a`.
+    fn responsibilities<S>(&self, s: &S, a: &P::Action) -> (Vec<f64>, Vec<f64>)
+    where P: Policy<S> {
+        let ws = self.mixing_weights();
+        let joint: Vec<f64> = self.policies().iter().zip(ws.iter()).map(|(p, w)| {
+            w * p.probability(s, a)
+        }).collect();
+        let z: f64 = joint.iter().sum();
+
+        let rs = if z > 0.0 { joint.iter().map(|j| j / z).collect() } else { ws.clone() };
+
+        (ws, rs)
+    }
+}
+
+impl<S, P: Policy<S> + Parameterised> Policy<S> for Mixture<P> {
+    type Action = P::Action;
+
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R, s: &S) -> P::Action {
+        let k = sample_probs_with_rng(rng, &self.mixing_weights());
+
+        self.policies()[k].sample(rng, s)
+    }
+
+    /// Return the most probable action of the component with the largest
+    /// mixing weight.
+    fn mpa(&self, s: &S) -> P::Action {
+        let k = argmaxima(&self.mixing_weights()).1[0];
+
+        self.policies()[k].mpa(s)
+    }
+
+    fn probability(&self, s: &S, a: &P::Action) -> f64 {
+        self.policies().iter()
+            .zip(self.mixing_weights())
+            .map(|(p, w)| w * p.probability(s, a))
+            .sum()
+    }
+
+    fn handle_terminal(&mut self) {
+        self.params.modify(|c| c.policies.iter_mut().for_each(|p| p.handle_terminal()))
+    }
+}
+
+impl<P: Parameterised> Parameterised for Mixture<P> {
+    fn weights(&self) -> Weights { self.params.weights() }
+
+    fn weights_view(&self) -> WeightsView { self.params.weights_view() }
+
+    fn weights_view_mut(&mut self) -> WeightsViewMut { self.params.weights_view_mut() }
+
+    fn weights_dim(&self) -> [usize; 2] { self.params.weights_dim() }
+}
+
+impl<S, P: DifferentiablePolicy<S>> DifferentiablePolicy<S> for Mixture<P> {
+    fn update(&mut self, s: &S, a: &P::Action, error: f64) {
+        let gl = self.grad_log(s, a);
+
+        self.update_grad_scaled(&gl.view(), error);
+    }
+
+    fn update_grad(&mut self, grad: &ArrayView2<f64>) { self.update_grad_scaled(grad, 1.0) }
+
+    // Each component is updated through its own update_grad_scaled, so that
+    // components without contiguous weights, e.g. `Gaussian`, are supported.
+    fn update_grad_scaled(&mut self, grad: &ArrayView2<f64>, factor: f64) {
+        let ranges = self.params.component_ranges();
+        let n = ranges.len() - 1;
+
+        for (k, ([r0, r1], [c0, c1])) in ranges.into_iter().enumerate() {
+            if r1 > r0 && c1 > c0 {
+                let g = grad.slice(s![r0..r1, c0..c1]);
+
+                self.params.modify_component(k, |c| if k < n {
+                    c.policies[k].update_grad_scaled(&g, factor)
+                } else {
+                    c.logits.scaled_add(factor, &g)
+                });
+            }
+        }
+    }
+
+    fn grad_log(&self, s: &S, a: &P::Action) -> Array2<f64> {
+        let (ws, rs) = self.responsibilities(s, a);
+        let ranges = self.params.component_ranges();
+        let n = ws.len();
+
+        let mut grad = Array2::zeros(self.params.weights_dim());
+
+        // Components, ∇ ln π = Σ_k r_k ∇ ln π_k:
+        for (k, p) in self.policies().iter().enumerate() {
+            let ([r0, r1], [c0, c1]) = ranges[k];
+
+            if r1 > r0 && c1 > c0 && rs[k] > 0.0 {
+                grad.slice_mut(s![r0..r1, c0..c1]).scaled_add(rs[k], &p.grad_log(s, a));
+            }
+        }
+
+        // Mixing logits, ∂ ln π / ∂l_k = r_k - w_k:
+        let ([r0, _], [c0, _]) = ranges[n];
+
+        for k in 0..n {
+            grad[[r0 + k, c0]] = rs[k] - ws[k];
+        }
+
+        grad
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        fa::{Parameterised, linear::{LFA, basis::{Polynomial, Projector}, optim::SGD}},
+        policies::{Categorical, DifferentiablePolicy, Policy, gaussian::{self, Gaussian}},
+    };
+    use super::Mixture;
+
+    macro_rules! make_policy {
+        () => {
+            Categorical::new(LFA::vector(Polynomial::new(1, 1).with_constant(), SGD(1.0), 3))
+        };
+    }
+
+    macro_rules! make_gaussian {
+        () => {
+            Gaussian::new(
+                gaussian::mean::Scalar(
+                    LFA::scalar(Polynomial::new(1, 1).with_constant(), SGD(1.0))
+                ),
+                gaussian::stddev::Constant(1.0),
+            )
+        };
+    }
+
+    #[test]
+    fn test_probability() {
+        let p = Mixture::new(vec![make_policy!(), make_policy!()]);
+        let total: f64 = (0..3).map(|a| p.probability(&vec![0.5], &a)).sum();
+
+        assert_eq!(p.mixing_weights(), vec![0.5, 0.5]);
+        assert!((total - 1.0).abs() < 1e-7);
+    }
+
+    #[test]
+    fn test_update() {
+        let mut p = Mixture::new(vec![make_policy!(), make_policy!()]);
+        let s = vec![1.0];
+
+        // Make component 1 prefer action 2; training on action 2 should then
+        // shift the mixing weights towards it.
+        p.weights_view_mut()[[0, 5]] = 5.0;
+        p.update(&s, &2, 1.0);
+
+        let ws = p.mixing_weights();
+
+        assert!(ws[1] > ws[0]);
+        assert!(p.probability(&s, &2) > 1.0 / 3.0);
+    }
+
+    #[test]
+    fn test_gaussians() {
+        let mut p = Mixture::new(vec![make_gaussian!(), make_gaussian!()]);
+        let s = vec![1.0];
+
+        let total: f64 = (-1000..1000).map(|i| 0.01 * p.probability(&s, &(i as f64 * 0.01))).sum();
+
+        assert!((total - 1.0).abs() < 1e-3);
+
+        p.update(&s, &1.0, 0.1);
+
+        assert_eq!(p.mixing_weights(), vec![0.5, 0.5]);
+        assert!(p.policies().iter().all(|c| c.mpa(&s) > 0.0));
+    }
+}
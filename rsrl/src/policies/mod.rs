@@ -10,7 +10,10 @@
 //! of stochastic policies in which all probability mass is placed on a single
 //! action _u'_ for any given state _x_. For continuous policies, this can be
 //! seen as a dirac delta distribution, _δ(u' - u)_.
-use crate::fa::Parameterised;
+use crate::{
+    fa::Parameterised,
+    spaces::{BoundedSpace, real::Interval},
+};
 use ndarray::{Array2, ArrayView2};
 use rand::{thread_rng, Rng};
 use std::ops::AddAssign;
@@ -21,13 +24,27 @@ import_all!(random);
 import_all!(greedy);
 import_all!(epsilon_greedy);
 import_all!(softmax);
+import_all!(epsilon_softmax);
+import_all!(mellowmax);
+import_all!(sparsemax);
 import_all!(beta);
 import_all!(dirichlet);
 import_all!(gamma);
+import_all!(squashed);
+import_all!(truncated);
+import_all!(deterministic);
+
+import_all!(counts);
+import_all!(ucb);
+import_all!(ensemble);
+import_all!(posterior_sampling);
 
 import_all!(ipp);
+import_all!(masked);
+import_all!(categorical);
+import_all!(mixture);
 import_all!(shared);
-// import_all!(perturbation);
+import_all!(perturbation);
 
 #[allow(dead_code)]
 #[inline]
@@ -36,7 +53,7 @@ pub(self) fn sample_probs(probabilities: &[f64]) -> usize {
 }
 
 #[inline]
-pub(self) fn sample_probs_with_rng<R: Rng + ?Sized>(rng: &mut R, probabilities: &[f64]) -> usize {
+pub(crate) fn sample_probs_with_rng<R: Rng + ?Sized>(rng: &mut R, probabilities: &[f64]) -> usize {
     let r = rng.gen::<f64>();
 
     match probabilities
@@ -53,6 +70,52 @@ pub(self) fn sample_probs_with_rng<R: Rng + ?Sized>(rng: &mut R, probabilities:
     }
 }
 
+/// Compute the entropy of a discrete distribution.
+pub(crate) fn entropy_from_probs(probabilities: &[f64]) -> f64 {
+    probabilities.iter().filter(|&&p| p > 0.0).fold(0.0, |acc, p| acc - p * p.ln())
+}
+
+/// Compute the KL divergence between two discrete distributions, `KL(p || q)`.
+pub(crate) fn kl_from_probs(ps: &[f64], qs: &[f64]) -> f64 {
+    ps.iter().zip(qs.iter()).filter(|&(&p, _)| p > 0.0).fold(0.0, |acc, (p, q)| {
+        acc + p * (p / q).ln()
+    })
+}
+
+/// Per-sample bonus added to the error of a policy-gradient update.
+pub trait PolicyBonus<S, P: Policy<S>> {
+    /// Return the bonus for taking action `a` in state `s` under `policy`.
+    fn bonus(&self, policy: &P, s: &S, a: &P::Action) -> f64;
+}
+
+impl<S, P: Policy<S>> PolicyBonus<S, P> for () {
+    fn bonus(&self, _: &P, _: &S, _: &P::Action) -> f64 { 0.0 }
+}
+
+/// Entropy bonus with weight `tau`.
+///
+/// The per-sample bonus, `-τ (ln π(a | s) + H(π(·|s)))`, has an expected
+/// product with the score function equal to `τ ∇H(π(·|s))`. The entropy acts
+/// as a baseline for `-ln π(a | s)`, reducing the variance of the estimate
+/// without introducing bias.
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[derive(Clone, Copy, Debug)]
+pub struct EntropyBonus(pub f64);
+
+impl<S, P: Entropy<S>> PolicyBonus<S, P> for EntropyBonus {
+    fn bonus(&self, policy: &P, s: &S, a: &P::Action) -> f64 {
+        -self.0 * (policy.log_probability(s, a) + policy.entropy(s))
+    }
+}
+
+/// Return the lower and upper bounds of a compact interval.
+pub(crate) fn interval_bounds(space: &Interval) -> (f64, f64) {
+    match (space.inf(), space.sup()) {
+        (Some(lb), Some(ub)) if lb < ub => (lb, ub),
+        _ => panic!("Bounded policies require a non-empty, compact action interval."),
+    }
+}
+
 /// Policy trait for functions that define a probability distribution over
 /// actions.
 pub trait Policy<S> {
@@ -68,6 +131,15 @@ pub trait Policy<S> {
 
     /// Return the probability of selecting an action for a given `state`.
     fn probability(&self, state: &S, a: &Self::Action) -> f64;
+
+    /// Return the log probability (or log density) of selecting an action for
+    /// a given `state`.
+    fn log_probability(&self, state: &S, a: &Self::Action) -> f64 {
+        self.probability(state, a).ln()
+    }
+
+    /// Handle the end of an episode, e.g. to resample any per-episode state.
+    fn handle_terminal(&mut self) {}
 }
 
 /// Trait for policies that are defined on an enumerable action space.
@@ -79,6 +151,53 @@ pub trait EnumerablePolicy<S>: Policy<S, Action = usize> {
     fn probabilities(&self, state: &S) -> Vec<f64>;
 }
 
+/// Trait for policies with a closed-form (differential) entropy.
+pub trait Entropy<S>: Policy<S> {
+    /// Return the entropy of the policy distribution for a given `state`.
+    fn entropy(&self, state: &S) -> f64;
+}
+
+/// Trait for policies with a closed-form divergence between two instances.
+pub trait Divergence<S>: Policy<S> {
+    /// Return the KL divergence, `KL(π(·|s) || π'(·|s))`, between the policy
+    /// distributions of `self` and `other` for a given `state`.
+    fn kl_divergence(&self, other: &Self, state: &S) -> f64;
+}
+
+/// Estimate the entropy of a policy distribution for a given `state` from
+/// `n_samples` Monte-Carlo samples.
+pub fn entropy_mc<S, P, R>(policy: &P, rng: &mut R, state: &S, n_samples: usize) -> f64
+where
+    P: Policy<S>,
+    R: Rng + ?Sized,
+{
+    let sum = (0..n_samples).fold(0.0, |acc, _| {
+        let a = policy.sample(rng, state);
+
+        acc - policy.log_probability(state, &a)
+    });
+
+    sum / n_samples as f64
+}
+
+/// Estimate the KL divergence, `KL(p(·|s) || q(·|s))`, between two policy
+/// distributions for a given `state` from `n_samples` Monte-Carlo samples
+/// drawn from `p`.
+pub fn kl_divergence_mc<S, P, Q, R>(p: &P, q: &Q, rng: &mut R, state: &S, n_samples: usize) -> f64
+where
+    P: Policy<S>,
+    Q: Policy<S, Action = P::Action>,
+    R: Rng + ?Sized,
+{
+    let sum = (0..n_samples).fold(0.0, |acc, _| {
+        let a = p.sample(rng, state);
+
+        acc + p.log_probability(state, &a) - q.log_probability(state, &a)
+    });
+
+    sum / n_samples as f64
+}
+
 /// Trait for policies with a representation that is differentiable wrt its
 /// parameters.
 pub trait DifferentiablePolicy<S>: Policy<S> + Parameterised {
@@ -106,3 +225,23 @@ pub trait DifferentiablePolicy<S>: Policy<S> + Parameterised {
         self.grad(state, a) * p
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::fa::mocking::MockQ;
+    use super::{EntropyBonus, EnumerablePolicy, PolicyBonus, Softmax};
+
+    #[test]
+    fn test_entropy_bonus_baseline() {
+        let p = Softmax::new(MockQ::new_shared(None), 1.0);
+        let s = vec![0.0, 1.0, 2.0];
+
+        // The entropy baseline makes the expected bonus zero:
+        let expected = p.probabilities(&s).into_iter().enumerate().fold(0.0, |acc, (a, pr)| {
+            acc + pr * EntropyBonus(0.5).bonus(&p, &s, &a)
+        });
+
+        assert!(expected.abs() < 1e-10);
+        assert_eq!(().bonus(&p, &s, &0), 0.0);
+    }
+}
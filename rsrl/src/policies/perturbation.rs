@@ -1,70 +1,314 @@
-use crate::core::*;
-use crate::geometry::Space;
-use crate::policies::{EnumerablePolicy, Policy};
-use rand::{
-    distributions::{Distribution, Normal},
-    rngs::ThreadRng,
-    Rng,
-    thread_rng,
-};
-use std::ops::Add;
-
-pub struct PerturbedPolicy<P, D> {
+use crate::policies::Policy;
+use rand::Rng;
+use rstat::{Distribution, ContinuousDistribution, univariate::continuous::Normal};
+use std::cell::RefCell;
+
+/// Interface for additive exploration noise processes.
+pub trait Noise<A> {
+    /// Perturb the action `a` with a sample of the noise process.
+    fn perturb<R: Rng + ?Sized>(&self, rng: &mut R, a: A) -> A;
+
+    /// Reset any internal state of the process.
+    fn reset(&mut self) {}
+}
+
+/// Interface for noise processes with a well-defined perturbation density.
+pub trait DensityNoise<A>: Noise<A> {
+    /// Return the density of the most recent perturbation mapping `base` to `a`.
+    fn density(&self, base: &A, a: &A) -> f64;
+}
+
+/// Independent and identically distributed Gaussian noise.
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug)]
+pub struct GaussianNoise {
+    pub stddev: f64,
+}
+
+impl GaussianNoise {
+    pub fn new(stddev: f64) -> Self { GaussianNoise { stddev } }
+
+    fn dist(&self) -> Normal { Normal::new(0.0, self.stddev) }
+}
+
+impl Noise<f64> for GaussianNoise {
+    fn perturb<R: Rng + ?Sized>(&self, rng: &mut R, a: f64) -> f64 {
+        a + self.dist().sample(rng)
+    }
+}
+
+impl DensityNoise<f64> for GaussianNoise {
+    fn density(&self, base: &f64, a: &f64) -> f64 { self.dist().pdf(a - base) }
+}
+
+impl Noise<Vec<f64>> for GaussianNoise {
+    fn perturb<R: Rng + ?Sized>(&self, rng: &mut R, a: Vec<f64>) -> Vec<f64> {
+        let dist = self.dist();
+
+        a.into_iter().map(|x| x + dist.sample(rng)).collect()
+    }
+}
+
+impl DensityNoise<Vec<f64>> for GaussianNoise {
+    fn density(&self, base: &Vec<f64>, a: &Vec<f64>) -> f64 {
+        let dist = self.dist();
+
+        base.iter().zip(a.iter()).map(|(b, x)| dist.pdf(x - b)).product()
+    }
+}
+
+/// Temporally correlated Ornstein–Uhlenbeck noise.
+///
+/// The process follows the Euler–Maruyama discretisation of `dx = θ(μ - x)dt
+/// + σ dW`, advancing by one step each time an action is perturbed, and is
+/// reset to `μ` at the end of each episode. Since each perturbation depends on
+/// the history of the process, its density is that of the Gaussian transition
+/// from the state preceding the most recent step.
+///
+/// # References
+/// - Uhlenbeck, G. E., Ornstein, L. S. (1930). On the theory of the Brownian
+/// motion. Physical Review, 36(5), 823–841.
+/// - Lillicrap, T. P., et al. (2016). Continuous control with deep
+/// reinforcement learning. In Proceedings of the 4th International Conference
+/// on Learning Representations.
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug)]
+pub struct OrnsteinUhlenbeck {
+    pub theta: f64,
+    pub sigma: f64,
+    pub mu: f64,
+    pub dt: f64,
+
+    state: RefCell<Vec<f64>>,
+    previous: RefCell<Vec<f64>>,
+}
+
+impl OrnsteinUhlenbeck {
+    pub fn new(theta: f64, sigma: f64) -> Self {
+        OrnsteinUhlenbeck {
+            theta,
+            sigma,
+            mu: 0.0,
+            dt: 1.0,
+
+            state: RefCell::new(vec![]),
+            previous: RefCell::new(vec![]),
+        }
+    }
+
+    /// Set the long-run mean of the process.
+    pub fn with_mean(mut self, mu: f64) -> Self {
+        self.mu = mu;
+        self
+    }
+
+    /// Set the time step of the discretisation.
+    pub fn with_dt(mut self, dt: f64) -> Self {
+        self.dt = dt;
+        self
+    }
+
+    /// Return the current state of the process.
+    pub fn state(&self) -> Vec<f64> { self.state.borrow().clone() }
+
+    // Advance the process by one step and return the new state.
+    fn step<R: Rng + ?Sized>(&self, rng: &mut R, n_dims: usize) -> Vec<f64> {
+        let standard = Normal::new(0.0, 1.0);
+        let mut state = self.state.borrow_mut();
+
+        if state.len() != n_dims {
+            *state = vec![self.mu; n_dims];
+        }
+
+        self.previous.replace(state.clone());
+
+        for x in state.iter_mut() {
+            *x += self.theta * (self.mu - *x) * self.dt
+                + self.sigma * self.dt.sqrt() * standard.sample(rng);
+        }
+
+        state.clone()
+    }
+
+    // Return the density of the transition into `x` from the previous state in
+    // dimension `i`; the process starts at `μ` if it has not yet been stepped.
+    fn transition_density(&self, i: usize, x: f64) -> f64 {
+        let prev = self.previous.borrow().get(i).cloned().unwrap_or(self.mu);
+        let mean = prev + self.theta * (self.mu - prev) * self.dt;
+
+        Normal::new(mean, self.sigma * self.dt.sqrt()).pdf(x)
+    }
+}
+
+impl Noise<f64> for OrnsteinUhlenbeck {
+    fn perturb<R: Rng + ?Sized>(&self, rng: &mut R, a: f64) -> f64 { a + self.step(rng, 1)[0] }
+
+    fn reset(&mut self) {
+        self.state.get_mut().clear();
+        self.previous.get_mut().clear();
+    }
+}
+
+impl DensityNoise<f64> for OrnsteinUhlenbeck {
+    fn density(&self, base: &f64, a: &f64) -> f64 { self.transition_density(0, a - base) }
+}
+
+impl Noise<Vec<f64>> for OrnsteinUhlenbeck {
+    fn perturb<R: Rng + ?Sized>(&self, rng: &mut R, a: Vec<f64>) -> Vec<f64> {
+        let noise = self.step(rng, a.len());
+
+        a.into_iter().zip(noise.into_iter()).map(|(x, n)| x + n).collect()
+    }
+
+    fn reset(&mut self) {
+        self.state.get_mut().clear();
+        self.previous.get_mut().clear();
+    }
+}
+
+impl DensityNoise<Vec<f64>> for OrnsteinUhlenbeck {
+    fn density(&self, base: &Vec<f64>, a: &Vec<f64>) -> f64 {
+        base.iter().zip(a.iter()).enumerate()
+            .map(|(i, (b, x))| self.transition_density(i, x - b))
+            .product()
+    }
+}
+
+/// Policy wrapper that adds exploration noise to the actions of a base policy.
+///
+/// Typically used as the behaviour policy for off-policy continuous control,
+/// with a deterministic target policy as the base. The most probable action is
+/// that of the base policy, and the probability of an action is given by the
+/// noise density around it; the latter is only well-defined for deterministic
+/// base policies, and the wrapper is only a `Policy` for noise processes that
+/// implement `DensityNoise`.
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug)]
+pub struct PerturbedPolicy<P, N> {
     pub base_policy: P,
-    pub noise_dist: D,
+    pub noise: N,
 }
 
-impl<P, D> PerturbedPolicy<P, D> {
-    pub fn new(base_policy: P, noise_dist: D) -> Self {
+impl<P, N> PerturbedPolicy<P, N> {
+    pub fn new(base_policy: P, noise: N) -> Self {
         PerturbedPolicy {
             base_policy,
-            noise_dist,
+            noise,
         }
     }
 }
 
-impl<P> PerturbedPolicy<P, Normal> {
-    pub fn normal(base_policy: P, std_dev: f64) -> Self {
-        PerturbedPolicy::new(base_policy, Normal::new(0.0, std_dev))
+impl<P> PerturbedPolicy<P, GaussianNoise> {
+    pub fn normal(base_policy: P, stddev: f64) -> Self {
+        PerturbedPolicy::new(base_policy, GaussianNoise::new(stddev))
     }
 }
 
-impl<P: Algorithm, D> Algorithm for PerturbedPolicy<P, D> {
-    fn handle_terminal(&mut self) {
-        self.base_policy.handle_terminal();
+impl<P> PerturbedPolicy<P, OrnsteinUhlenbeck> {
+    pub fn ornstein_uhlenbeck(base_policy: P, theta: f64, sigma: f64) -> Self {
+        PerturbedPolicy::new(base_policy, OrnsteinUhlenbeck::new(theta, sigma))
     }
 }
 
-impl<S, P, D> Policy<S> for PerturbedPolicy<P, D>
+impl<S, P, N> Policy<S> for PerturbedPolicy<P, N>
 where
     P: Policy<S>,
-    D: Distribution<P::Action>,
-    P::Action: Add<P::Action, Output = P::Action>,
+    N: DensityNoise<P::Action>,
 {
     type Action = P::Action;
 
-    fn sample(&self, rng: &mut impl Rng, s: &S) -> P::Action {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R, s: &S) -> P::Action {
         let base_action = self.base_policy.sample(rng, s);
-        let perturbation = self.noise_dist.sample(rng);
 
-        base_action + perturbation
+        self.noise.perturb(rng, base_action)
+    }
+
+    fn mpa(&self, s: &S) -> P::Action { self.base_policy.mpa(s) }
+
+    fn probability(&self, s: &S, a: &P::Action) -> f64 {
+        self.noise.density(&self.base_policy.mpa(s), a)
     }
 
-    fn probability(&self, _: &S, _: &P::Action) -> f64 {
-        unimplemented!()
+    fn handle_terminal(&mut self) {
+        self.base_policy.handle_terminal();
+        self.noise.reset();
     }
 }
 
-impl<S, P, D> EnumerablePolicy<S> for PerturbedPolicy<P, D>
-where
-    P: EnumerablePolicy<S>,
-    D: Distribution<P::Action>,
-{
-    fn n_actions(&self) -> usize {
-        self.base_policy.n_actions()
+#[cfg(test)]
+mod tests {
+    use crate::policies::Policy;
+    use rand::thread_rng;
+    use rstat::{ContinuousDistribution, univariate::continuous::Normal};
+    use super::{Noise, OrnsteinUhlenbeck, PerturbedPolicy};
+
+    struct Fixed(f64);
+
+    impl Policy<()> for Fixed {
+        type Action = f64;
+
+        fn mpa(&self, _: &()) -> f64 { self.0 }
+
+        fn probability(&self, _: &(), a: &f64) -> f64 { if *a == self.0 { 1.0 } else { 0.0 } }
+    }
+
+    #[test]
+    fn test_gaussian() {
+        let mut rng = thread_rng();
+        let p = PerturbedPolicy::normal(Fixed(1.0), 0.1);
+
+        let mean = (0..10000).map(|_| p.sample(&mut rng, &())).sum::<f64>() / 10000.0;
+
+        assert!((mean - 1.0).abs() < 0.01);
+        assert_eq!(p.mpa(&()), 1.0);
+        assert!(p.probability(&(), &1.0) > p.probability(&(), &1.1));
     }
 
-    fn probabilities(&self, _: &S) -> Vector<f64> {
-        unimplemented!()
+    #[test]
+    fn test_ou_reset() {
+        let mut rng = thread_rng();
+        let mut p = PerturbedPolicy::ornstein_uhlenbeck(Fixed(0.0), 0.15, 0.2);
+
+        p.sample(&mut rng, &());
+        p.sample(&mut rng, &());
+
+        assert_eq!(p.noise.state().len(), 1);
+
+        Policy::<()>::handle_terminal(&mut p);
+
+        assert!(p.noise.state().is_empty());
+    }
+
+    #[test]
+    fn test_ou_density() {
+        let mut rng = thread_rng();
+        let p = PerturbedPolicy::ornstein_uhlenbeck(Fixed(1.0), 0.15, 0.2);
+
+        // The first step starts from the mean of the process.
+        let a = p.sample(&mut rng, &());
+
+        assert!((p.probability(&(), &a) - Normal::new(0.0, 0.2).pdf(a - 1.0)).abs() < 1e-9);
+
+        // Subsequent steps are centred on the mean-reverted previous state.
+        let x = p.noise.state()[0];
+        let b = p.sample(&mut rng, &());
+        let expected = Normal::new(x * 0.85, 0.2).pdf(b - 1.0);
+
+        assert!((p.probability(&(), &b) - expected).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_ou_correlation() {
+        let mut rng = thread_rng();
+        let ou = OrnsteinUhlenbeck::new(0.15, 0.2).with_mean(2.0);
+
+        let xs: Vec<f64> = (0..10000).map(|_| ou.perturb(&mut rng, 0.0)).collect();
+        let mean = xs.iter().sum::<f64>() / 10000.0;
+
+        let lag1 = xs.windows(2).map(|w| (w[0] - mean) * (w[1] - mean)).sum::<f64>();
+        let lag0 = xs.iter().map(|x| (x - mean) * (x - mean)).sum::<f64>();
+
+        assert!((mean - 2.0).abs() < 0.2);
+        assert!(lag1 / lag0 > 0.7);
     }
 }
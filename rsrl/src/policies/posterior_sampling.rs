@@ -0,0 +1,119 @@
+use crate::{
+    fa::linear::BayesianStateActionFunction,
+    policies::{EnumerablePolicy, Policy},
+    utils::argmaxima,
+};
+use rand::{thread_rng, Rng};
+
+/// Thompson sampling over the posterior of a Bayesian action-value function.
+///
+/// Each call to `sample` draws a value for every action from its posterior
+/// marginal and acts greedily with respect to the draws. Since the selection
+/// probabilities are not available in closed form, `probabilities` returns a
+/// Monte-Carlo estimate using `n_samples` posterior draws.
+///
+/// # References
+/// - Thompson, W. R. (1933). On the likelihood that one unknown probability
+/// exceeds another in view of the evidence of two samples. Biometrika,
+/// 25(3/4), 285–294.
+/// - Dearden, R., Friedman, N., Russell, S. (1998). Bayesian Q-learning. In
+/// Proceedings of the 15th National Conference on Artificial Intelligence, pp.
+/// 761–768.
+#[derive(Clone, Debug)]
+pub struct PosteriorSampling<Q> {
+    pub q_func: Q,
+    pub n_samples: usize,
+}
+
+impl<Q> PosteriorSampling<Q> {
+    pub fn new(q_func: Q) -> Self {
+        PosteriorSampling {
+            q_func,
+            n_samples: 1000,
+        }
+    }
+}
+
+impl<S, Q: BayesianStateActionFunction<S>> Policy<S> for PosteriorSampling<Q> {
+    type Action = usize;
+
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R, s: &S) -> usize {
+        let (_, maxima) = argmaxima(&self.q_func.sample_all(rng, s));
+
+        maxima[rng.gen_range(0, maxima.len())]
+    }
+
+    fn mpa(&self, s: &S) -> usize { argmaxima(&self.q_func.evaluate_all(s)).1[0] }
+
+    fn probability(&self, s: &S, a: &usize) -> f64 { self.probabilities(s)[*a] }
+}
+
+impl<S, Q: BayesianStateActionFunction<S>> EnumerablePolicy<S> for PosteriorSampling<Q> {
+    fn n_actions(&self) -> usize { self.q_func.n_actions() }
+
+    fn probabilities(&self, s: &S) -> Vec<f64> {
+        let mut rng = thread_rng();
+        let mut ps = vec![0.0; self.q_func.n_actions()];
+        let z = 1.0 / self.n_samples as f64;
+
+        for _ in 0..self.n_samples {
+            ps[self.sample(&mut rng, s)] += z;
+        }
+
+        ps
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fa::{
+        EnumerableStateActionFunction, StateActionFunction,
+        linear::BayesianStateActionFunction,
+    };
+    use rand::thread_rng;
+    use super::{EnumerablePolicy, Policy, PosteriorSampling};
+
+    struct MockPosterior(Vec<f64>, Vec<f64>);
+
+    impl StateActionFunction<(), usize> for MockPosterior {
+        type Output = f64;
+
+        fn evaluate(&self, _: &(), a: &usize) -> f64 { self.0[*a] }
+
+        fn update(&mut self, _: &(), _: &usize, _: f64) {}
+    }
+
+    impl EnumerableStateActionFunction<()> for MockPosterior {
+        fn n_actions(&self) -> usize { self.0.len() }
+
+        fn evaluate_all(&self, _: &()) -> Vec<f64> { self.0.clone() }
+
+        fn update_all(&mut self, _: &(), _: Vec<f64>) {}
+    }
+
+    impl BayesianStateActionFunction<()> for MockPosterior {
+        fn evaluate_variances(&self, _: &()) -> Vec<f64> { self.1.clone() }
+    }
+
+    #[test]
+    fn test_certain() {
+        let p = PosteriorSampling::new(MockPosterior(vec![0.0, 1.0, 0.5], vec![0.0; 3]));
+        let mut rng = thread_rng();
+
+        for _ in 0..100 {
+            assert_eq!(p.sample(&mut rng, &()), 1);
+        }
+
+        assert_eq!(p.probabilities(&()), vec![0.0, 1.0, 0.0]);
+    }
+
+    #[test]
+    fn test_uncertain() {
+        let p = PosteriorSampling::new(MockPosterior(vec![0.0, 0.5], vec![1.0, 1.0]));
+        let ps = p.probabilities(&());
+
+        assert_eq!(p.mpa(&()), 1);
+        assert!(ps[0] > 0.25 && ps[0] < 0.45);
+        assert!((ps[0] + ps[1] - 1.0).abs() < 1e-7);
+    }
+}
@@ -14,6 +14,22 @@ impl<S, T: Policy<S>> Policy<S> for Shared<T> {
     fn probability(&self, state: &S, a: &Self::Action) -> f64 {
         self.borrow().probability(state, a)
     }
+
+    fn log_probability(&self, state: &S, a: &Self::Action) -> f64 {
+        self.borrow().log_probability(state, a)
+    }
+
+    fn handle_terminal(&mut self) { self.borrow_mut().handle_terminal() }
+}
+
+impl<S, T: Entropy<S>> Entropy<S> for Shared<T> {
+    fn entropy(&self, state: &S) -> f64 { self.borrow().entropy(state) }
+}
+
+impl<S, T: Divergence<S>> Divergence<S> for Shared<T> {
+    fn kl_divergence(&self, other: &Self, state: &S) -> f64 {
+        self.borrow().kl_divergence(&other.borrow(), state)
+    }
 }
 
 impl<S, T: EnumerablePolicy<S>> EnumerablePolicy<S> for Shared<T> {
@@ -22,6 +38,10 @@ impl<S, T: EnumerablePolicy<S>> EnumerablePolicy<S> for Shared<T> {
     fn probabilities(&self, state: &S) -> Vec<f64> { self.borrow().probabilities(state) }
 }
 
+impl<S, T: DeterministicPolicy<S>> DeterministicPolicy<S> for Shared<T> {
+    fn jacobian(&self, state: &S) -> Array2<f64> { self.borrow().jacobian(state) }
+}
+
 impl<S, T: DifferentiablePolicy<S>> DifferentiablePolicy<S> for Shared<T> {
     fn update(&mut self, state: &S, a: &Self::Action, error: f64) {
         self.borrow_mut().update(state, a, error)
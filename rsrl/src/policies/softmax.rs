@@ -4,8 +4,12 @@ use crate::{
         DifferentiableStateActionFunction, EnumerableStateActionFunction,
     },
     policies::{
+        entropy_from_probs,
+        kl_from_probs,
         sample_probs_with_rng,
         DifferentiablePolicy,
+        Divergence,
+        Entropy,
         EnumerablePolicy,
         Policy
     },
@@ -31,15 +35,23 @@ fn softmax<C: FromIterator<f64>>(values: &[f64], tau: f64, c: f64) -> C {
     ps.into_iter().map(|v| (v / z).min(f64::MAX)).collect()
 }
 
-fn softmax_stable<C: FromIterator<f64>>(values: &[f64], tau: f64) -> C {
+pub(super) fn softmax_stable<C: FromIterator<f64>>(values: &[f64], tau: f64) -> C {
     let max_v = values.into_iter().fold(f64::NAN, |acc, &v| f64::max(acc, v));
 
     softmax(values, tau, max_v)
 }
 
+fn log_softmax(values: &[f64], tau: f64, a: usize) -> f64 {
+    let max_v = values.into_iter().fold(f64::NAN, |acc, &v| f64::max(acc, v));
+    let z = values.into_iter().fold(0.0, |acc, v| acc + ((v - max_v) / tau).exp());
+
+    (values[a] - max_v) / tau - z.ln()
+}
+
 pub type Gibbs<F> = Softmax<F>;
 
-#[derive(Parameterised)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, Parameterised)]
 pub struct Softmax<F> {
     #[weights] fa: F,
 
@@ -89,6 +101,20 @@ impl<S, F: EnumerableStateActionFunction<S>> Policy<S> for Softmax<F> {
     }
 
     fn probability(&self, s: &S, a: &usize) -> f64 { self.probabilities(s)[*a] }
+
+    fn log_probability(&self, s: &S, a: &usize) -> f64 {
+        log_softmax(&self.fa.evaluate_all(s), self.tau, *a)
+    }
+}
+
+impl<S, F: EnumerableStateActionFunction<S>> Entropy<S> for Softmax<F> {
+    fn entropy(&self, s: &S) -> f64 { entropy_from_probs(&self.probabilities(s)) }
+}
+
+impl<S, F: EnumerableStateActionFunction<S>> Divergence<S> for Softmax<F> {
+    fn kl_divergence(&self, other: &Self, s: &S) -> f64 {
+        kl_from_probs(&self.probabilities(s), &other.probabilities(s))
+    }
 }
 
 impl<S, F: EnumerableStateActionFunction<S>> EnumerablePolicy<S> for Softmax<F> {
@@ -174,6 +200,31 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_entropy() {
+        let p = Softmax::new(MockQ::new_shared(None), 1.0);
+
+        assert!((p.entropy(&vec![0.0, 0.0]) - 2.0f64.ln()).abs() < 1e-7);
+        assert!(p.entropy(&vec![0.0, 10.0]) < 1e-3);
+    }
+
+    #[test]
+    fn test_log_probability() {
+        let p = Softmax::new(MockQ::new_shared(None), 1.0);
+
+        assert!((p.log_probability(&vec![0.0, 1.0], &1) - (E / (1.0 + E)).ln()).abs() < 1e-7);
+        assert!((p.log_probability(&vec![0.0, 1000.0], &0) + 1000.0).abs() < 1e-7);
+    }
+
+    #[test]
+    fn test_kl_divergence() {
+        let p = Softmax::new(MockQ::new_shared(Some(vec![0.0, 0.0])), 1.0);
+        let q = Softmax::new(MockQ::new_shared(Some(vec![0.0, 3.0f64.ln()])), 1.0);
+
+        assert!(p.kl_divergence(&p, &vec![]).abs() < 1e-7);
+        assert!((p.kl_divergence(&q, &vec![]) - 0.5 * (4.0f64 / 3.0).ln()).abs() < 1e-7);
+    }
+
     #[test]
     fn test_probabilities_2() {
         let fa = LFA::vector(Polynomial::new(1, 1).with_constant(), SGD(1.0), 3);
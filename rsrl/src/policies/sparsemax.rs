@@ -0,0 +1,120 @@
+use crate::{
+    fa::EnumerableStateActionFunction,
+    policies::{
+        entropy_from_probs,
+        sample_probs_with_rng,
+        Entropy,
+        EnumerablePolicy,
+        Policy,
+    },
+    utils::argmaxima,
+};
+use rand::Rng;
+
+/// Compute the Euclidean projection of `values / tau` onto the probability
+/// simplex.
+pub fn sparsemax(values: &[f64], tau: f64) -> Vec<f64> {
+    let zs: Vec<f64> = values.iter().map(|v| v / tau).collect();
+
+    // NaN values are ordered last, such that they never enter the support:
+    let mut sorted: Vec<f64> = zs.iter()
+        .map(|&z| if z.is_nan() { ::std::f64::NEG_INFINITY } else { z })
+        .collect();
+    sorted.sort_by(|a, b| b.partial_cmp(a).unwrap());
+
+    let (threshold, _) = sorted.iter().enumerate().fold((0.0, 0.0), |(t, cumsum), (k, &z)| {
+        let cumsum = cumsum + z;
+
+        if 1.0 + (k + 1) as f64 * z > cumsum {
+            ((cumsum - 1.0) / (k + 1) as f64, cumsum)
+        } else {
+            (t, cumsum)
+        }
+    });
+
+    zs.into_iter().map(|z| (z - threshold).max(0.0)).collect()
+}
+
+/// Sparsemax policy over action-values.
+///
+/// Like `Softmax`, but with the probabilities given by the projection of the
+/// scaled action-values onto the simplex. Actions with values sufficiently
+/// far below the maximum are assigned exactly zero probability.
+///
+/// # References
+/// - Martins, A., & Astudillo, R. (2016). From softmax to sparsemax: A sparse
+/// model of attention and multi-label classification. In Proceedings of the
+/// 33rd International Conference on Machine Learning (pp. 1614-1623).
+/// - Lee, K., Choi, S., & Oh, S. (2018). Sparse Markov decision processes
+/// with causal sparse Tsallis entropy regularization for reinforcement
+/// learning. IEEE Robotics and Automation Letters, 3(3), 1466-1473.
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug)]
+pub struct Sparsemax<Q> {
+    q_func: Q,
+
+    pub tau: f64,
+}
+
+impl<Q> Sparsemax<Q> {
+    pub fn new(q_func: Q, tau: f64) -> Self {
+        if tau <= 0.0 {
+            panic!("Tau parameter in Sparsemax must be positive.");
+        }
+
+        Sparsemax { q_func, tau, }
+    }
+}
+
+impl<S, Q: EnumerableStateActionFunction<S>> Policy<S> for Sparsemax<Q> {
+    type Action = usize;
+
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R, s: &S) -> usize {
+        sample_probs_with_rng(rng, &self.probabilities(s))
+    }
+
+    fn mpa(&self, s: &S) -> usize { argmaxima(&self.q_func.evaluate_all(s)).1[0] }
+
+    fn probability(&self, s: &S, a: &usize) -> f64 { self.probabilities(s)[*a] }
+}
+
+impl<S, Q: EnumerableStateActionFunction<S>> Entropy<S> for Sparsemax<Q> {
+    fn entropy(&self, s: &S) -> f64 { entropy_from_probs(&self.probabilities(s)) }
+}
+
+impl<S, Q: EnumerableStateActionFunction<S>> EnumerablePolicy<S> for Sparsemax<Q> {
+    fn n_actions(&self) -> usize { self.q_func.n_actions() }
+
+    fn probabilities(&self, s: &S) -> Vec<f64> {
+        sparsemax(&self.q_func.evaluate_all(s), self.tau)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::compare_floats;
+    use super::sparsemax;
+
+    #[test]
+    fn test_sparse() {
+        assert!(compare_floats(sparsemax(&[0.0, 2.0, 0.5], 1.0), &[0.0, 1.0, 0.0], 1e-7));
+        assert!(compare_floats(sparsemax(&[0.0, 1.0, 0.5], 1.0), &[0.0, 0.75, 0.25], 1e-7));
+    }
+
+    #[test]
+    fn test_dense() {
+        assert!(compare_floats(sparsemax(&[1.0, 1.0], 1.0), &[0.5, 0.5], 1e-7));
+        assert!(compare_floats(
+            sparsemax(&[0.0, 0.2, 0.4], 1.0),
+            &[0.4 / 3.0, 1.0 / 3.0, 1.6 / 3.0],
+            1e-7
+        ));
+    }
+
+    #[test]
+    fn test_nan() {
+        let prs = sparsemax(&[::std::f64::NAN, 0.0, 1.0, 0.5], 1.0);
+
+        assert!(compare_floats(prs, &[0.0, 0.0, 0.75, 0.25], 1e-7));
+    }
+}
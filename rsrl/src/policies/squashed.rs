@@ -0,0 +1,201 @@
+use crate::{
+    fa::{Parameterised, Weights, WeightsView, WeightsViewMut},
+    policies::{interval_bounds, DifferentiablePolicy, Policy},
+    spaces::real::Interval,
+};
+use ndarray::{Array2, ArrayView2};
+use rand::Rng;
+use std::f64::consts::LN_2;
+
+const MAX_TANH: f64 = 1.0 - 1e-6;
+
+/// Tanh-squashed policy for actions bounded in an interval.
+///
+/// Samples `u` from an unbounded scalar policy (typically a `Gaussian`) and
+/// maps it onto the interval `[lb, ub]` via `a = lb + (ub - lb)(tanh(u) + 1) / 2`.
+/// The density includes the change-of-variables correction, `π(a | s) = p(u |
+/// s) / |da/du|`, and since the Jacobian does not depend on the weights, the
+/// score function is that of the underlying policy evaluated at `u`. Unlike
+/// clipping, this leaves the policy gradient unbiased.
+///
+/// # References
+/// - Haarnoja, T., Zhou, A., Abbeel, P., Levine, S. (2018). Soft actor-critic:
+/// Off-policy maximum entropy deep reinforcement learning with a stochastic
+/// actor. In Proceedings of the 35th International Conference on Machine
+/// Learning, pp. 1861–1870.
+#[derive(Parameterised)]
+pub struct Squashed<P> {
+    #[weights] pub policy: P,
+
+    lb: f64,
+    ub: f64,
+}
+
+impl<P> Squashed<P> {
+    pub fn new(policy: P, action_space: Interval) -> Self {
+        let (lb, ub) = interval_bounds(&action_space);
+
+        Squashed { policy, lb, ub, }
+    }
+
+    /// Map an unbounded action onto the interval.
+    pub fn squash(&self, u: f64) -> f64 {
+        self.lb + (self.ub - self.lb) * (u.tanh() + 1.0) / 2.0
+    }
+
+    /// Map a bounded action back onto the real line.
+    pub fn unsquash(&self, a: f64) -> f64 {
+        let y = 2.0 * (a - self.lb) / (self.ub - self.lb) - 1.0;
+
+        y.max(-MAX_TANH).min(MAX_TANH).atanh()
+    }
+
+    // Return |da/du| at the pre-image `u`.
+    fn jacobian(&self, u: f64) -> f64 {
+        let t = u.tanh().max(-MAX_TANH).min(MAX_TANH);
+
+        (self.ub - self.lb) / 2.0 * (1.0 - t * t)
+    }
+
+    // Return ln |da/du| at the pre-image `u`, using the identity `ln(1 -
+    // tanh²(u)) = 2 (ln 2 - |u| - ln(1 + exp(-2|u|)))` to avoid cancellation.
+    fn log_jacobian(&self, u: f64) -> f64 {
+        let u = u.abs();
+
+        ((self.ub - self.lb) / 2.0).ln() + 2.0 * (LN_2 - u - (-2.0 * u).exp().ln_1p())
+    }
+}
+
+impl<S, P: Policy<S, Action = f64>> Policy<S> for Squashed<P> {
+    type Action = f64;
+
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R, s: &S) -> f64 {
+        self.squash(self.policy.sample(rng, s))
+    }
+
+    fn mpa(&self, s: &S) -> f64 { self.squash(self.policy.mpa(s)) }
+
+    fn probability(&self, s: &S, a: &f64) -> f64 {
+        let u = self.unsquash(*a);
+
+        self.policy.probability(s, &u) / self.jacobian(u)
+    }
+
+    fn log_probability(&self, s: &S, a: &f64) -> f64 {
+        let u = self.unsquash(*a);
+
+        self.policy.log_probability(s, &u) - self.log_jacobian(u)
+    }
+
+    fn handle_terminal(&mut self) { self.policy.handle_terminal() }
+}
+
+impl<S, P: DifferentiablePolicy<S, Action = f64>> DifferentiablePolicy<S> for Squashed<P> {
+    fn update(&mut self, s: &S, a: &f64, error: f64) {
+        let u = self.unsquash(*a);
+
+        self.policy.update(s, &u, error)
+    }
+
+    fn update_grad(&mut self, grad: &ArrayView2<f64>) { self.policy.update_grad(grad) }
+
+    fn update_grad_scaled(&mut self, grad: &ArrayView2<f64>, factor: f64) {
+        self.policy.update_grad_scaled(grad, factor)
+    }
+
+    fn grad_log(&self, s: &S, a: &f64) -> Array2<f64> {
+        self.policy.grad_log(s, &self.unsquash(*a))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        fa::{Parameterised, linear::{LFA, basis::{Polynomial, Projector}, optim::SGD}},
+        policies::{DifferentiablePolicy, Policy, gaussian::{self, Gaussian}},
+        spaces::real::Interval,
+    };
+    use ndarray::Array2;
+    use super::Squashed;
+
+    macro_rules! make_policy {
+        () => {{
+            let mut p = Squashed::new(Gaussian::new(
+                gaussian::mean::Scalar(
+                    LFA::scalar(Polynomial::new(1, 1).with_constant(), SGD(1.0))
+                ),
+                gaussian::stddev::Constant(0.8),
+            ), Interval::bounded(-2.0, 1.0));
+
+            p.update_grad(&Array2::from_elem(p.weights_dim(), 0.2).view());
+            p
+        }};
+    }
+
+    #[test]
+    fn test_squash_roundtrip() {
+        let p = Squashed::new((), Interval::bounded(-2.0, 1.0));
+
+        assert_eq!(p.squash(0.0), -0.5);
+        assert!(p.squash(100.0) <= 1.0);
+        assert!(p.squash(-100.0) >= -2.0);
+
+        for &u in [-3.0, -0.5, 0.0, 0.7, 2.0].iter() {
+            assert!((p.unsquash(p.squash(u)) - u).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_density() {
+        const N: usize = 20000;
+
+        let p = make_policy!();
+        let s = vec![0.5];
+
+        let da = 3.0 / N as f64;
+        let total: f64 = (0..N).map(|i| {
+            let a = -2.0 + (i as f64 + 0.5) * da;
+            let pr = p.probability(&s, &a);
+
+            assert!((p.log_probability(&s, &a) - pr.ln()).abs() < 1e-8);
+
+            pr * da
+        }).sum();
+
+        assert!((total - 1.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_grad_log() {
+        const EPS: f64 = 1e-6;
+
+        let mut p = make_policy!();
+
+        let s = vec![0.5];
+        let a = 0.4;
+
+        let grad = p.grad_log(&s, &a);
+        let (n_rows, n_cols) = grad.dim();
+
+        // Gaussian has no weight views, so the weights are perturbed through
+        // update_grad:
+        for i in 0..n_rows {
+            for j in 0..n_cols {
+                let mut e = Array2::zeros((n_rows, n_cols));
+                e[[i, j]] = EPS;
+
+                p.update_grad(&e.view());
+                let lp_plus = p.log_probability(&s, &a);
+
+                p.update_grad_scaled(&e.view(), -2.0);
+                let lp_minus = p.log_probability(&s, &a);
+
+                p.update_grad(&e.view());
+
+                let fd = (lp_plus - lp_minus) / (2.0 * EPS);
+
+                assert!((grad[[i, j]] - fd).abs() < 1e-5, "∂/∂w[{}, {}]", i, j);
+            }
+        }
+    }
+}
@@ -0,0 +1,298 @@
+extern crate special_fun;
+
+use crate::{
+    fa::{
+        StateFunction, DifferentiableStateFunction,
+        Parameterised, Stacked, Weights, WeightsView, WeightsViewMut,
+    },
+    policies::{interval_bounds, DifferentiablePolicy, Entropy, Policy},
+    spaces::real::Interval,
+};
+use ndarray::{Array2, ArrayView2, Axis};
+use rand::Rng;
+use std::{
+    cell::Ref,
+    f64::consts::{E, PI, SQRT_2},
+    ops::AddAssign,
+};
+
+const MIN_STDDEV: f64 = 0.1;
+const MIN_MASS: f64 = 1e-12;
+
+#[inline]
+fn normal_pdf(x: f64) -> f64 { (-0.5 * x * x).exp() / (2.0 * PI).sqrt() }
+
+#[inline]
+fn normal_cdf(x: f64) -> f64 {
+    use special_fun::FloatSpecial;
+
+    0.5 * (-x / SQRT_2).erfc()
+}
+
+// Invert the standard normal CDF on [lo, hi] by bisection.
+fn normal_cdf_inv(p: f64, mut lo: f64, mut hi: f64) -> f64 {
+    for _ in 0..60 {
+        let mid = 0.5 * (lo + hi);
+
+        if normal_cdf(mid) < p { lo = mid; } else { hi = mid; }
+    }
+
+    0.5 * (lo + hi)
+}
+
+/// Truncated Gaussian policy for actions bounded in an interval.
+///
+/// The mean and standard deviation of the untruncated normal are given by two
+/// state functions, and the density is renormalised by the probability mass
+/// inside `[lb, ub]`, `Z = Φ(β) - Φ(α)`. The score function accounts for the
+/// dependence of `Z` on both parameters, so, unlike clipping a `Gaussian`, the
+/// policy gradient is unbiased. The weights are stacked row-wise, `[mean;
+/// stddev]`.
+///
+/// # References
+/// - Johnson, N. L., Kotz, S., Balakrishnan, N. (1994). Continuous Univariate
+/// Distributions, Vol. 1 (2nd ed.). Wiley, Section 10.1.
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug)]
+pub struct TruncatedGaussian<M, S = M> {
+    params: Stacked<(M, S)>,
+
+    lb: f64,
+    ub: f64,
+}
+
+impl<M: Parameterised, S: Parameterised> TruncatedGaussian<M, S> {
+    pub fn new(mean: M, stddev: S, action_space: Interval) -> Self {
+        let (lb, ub) = interval_bounds(&action_space);
+
+        TruncatedGaussian {
+            params: Stacked::new((mean, stddev), Axis(0)),
+
+            lb,
+            ub,
+        }
+    }
+
+    /// Return a reference to the function approximator for the mean.
+    pub fn mean(&self) -> Ref<M> { Ref::map(self.params.get(), |p| &p.0) }
+
+    /// Return a reference to the function approximator for the stddev.
+    pub fn stddev(&self) -> Ref<S> { Ref::map(self.params.get(), |p| &p.1) }
+
+    #[inline]
+    pub fn compute_mean<I>(&self, input: &I) -> f64 where M: StateFunction<I, Output = f64> {
+        self.mean().evaluate(input)
+    }
+
+    #[inline]
+    pub fn compute_stddev<I>(&self, input: &I) -> f64 where S: StateFunction<I, Output = f64> {
+        self.stddev().evaluate(input).max(MIN_STDDEV)
+    }
+
+    // Return the standardised bounds, α and β, and the normaliser, Z.
+    fn standardise(&self, mean: f64, stddev: f64) -> (f64, f64, f64) {
+        let alpha = (self.lb - mean) / stddev;
+        let beta = (self.ub - mean) / stddev;
+
+        (alpha, beta, (normal_cdf(beta) - normal_cdf(alpha)).max(MIN_MASS))
+    }
+
+    // Partial derivatives of ln π(a | s) wrt the mean and stddev.
+    fn gl_partial(&self, mean: f64, stddev: f64, a: f64) -> [f64; 2] {
+        let (alpha, beta, z) = self.standardise(mean, stddev);
+        let (pdf_a, pdf_b) = (normal_pdf(alpha), normal_pdf(beta));
+
+        let diff = a - mean;
+        let var = stddev * stddev;
+        let sz = stddev * z;
+
+        [
+            diff / var - (pdf_a - pdf_b) / sz,
+            diff * diff / var / stddev - 1.0 / stddev - (alpha * pdf_a - beta * pdf_b) / sz,
+        ]
+    }
+}
+
+impl<I, M, S> Policy<I> for TruncatedGaussian<M, S>
+where
+    M: StateFunction<I, Output = f64> + Parameterised,
+    S: StateFunction<I, Output = f64> + Parameterised,
+{
+    type Action = f64;
+
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R, input: &I) -> f64 {
+        let mean = self.compute_mean(input);
+        let stddev = self.compute_stddev(input);
+        let (alpha, beta, _) = self.standardise(mean, stddev);
+
+        let (p_lo, p_hi) = (normal_cdf(alpha), normal_cdf(beta));
+        let p = p_lo + rng.gen::<f64>() * (p_hi - p_lo);
+        let x = mean + stddev * normal_cdf_inv(p, alpha, beta);
+
+        x.max(self.lb).min(self.ub)
+    }
+
+    fn mpa(&self, input: &I) -> f64 { self.compute_mean(input).max(self.lb).min(self.ub) }
+
+    fn probability(&self, input: &I, a: &f64) -> f64 {
+        if *a < self.lb || *a > self.ub {
+            return 0.0;
+        }
+
+        let mean = self.compute_mean(input);
+        let stddev = self.compute_stddev(input);
+        let (_, _, z) = self.standardise(mean, stddev);
+
+        normal_pdf((a - mean) / stddev) / (stddev * z)
+    }
+}
+
+impl<I, M, S> Entropy<I> for TruncatedGaussian<M, S>
+where
+    M: StateFunction<I, Output = f64> + Parameterised,
+    S: StateFunction<I, Output = f64> + Parameterised,
+{
+    fn entropy(&self, input: &I) -> f64 {
+        let mean = self.compute_mean(input);
+        let stddev = self.compute_stddev(input);
+        let (alpha, beta, z) = self.standardise(mean, stddev);
+
+        (stddev * z * (2.0 * PI * E).sqrt()).ln()
+            + (alpha * normal_pdf(alpha) - beta * normal_pdf(beta)) / (2.0 * z)
+    }
+}
+
+impl<M: Parameterised, S: Parameterised> Parameterised for TruncatedGaussian<M, S> {
+    fn weights(&self) -> Weights { self.params.weights() }
+
+    fn weights_view(&self) -> WeightsView { self.params.weights_view() }
+
+    fn weights_view_mut(&mut self) -> WeightsViewMut { self.params.weights_view_mut() }
+
+    fn weights_dim(&self) -> [usize; 2] { self.params.weights_dim() }
+}
+
+impl<I, M, S> DifferentiablePolicy<I> for TruncatedGaussian<M, S>
+where
+    M: DifferentiableStateFunction<I, Output = f64> + Parameterised,
+    S: DifferentiableStateFunction<I, Output = f64> + Parameterised,
+{
+    fn update(&mut self, input: &I, a: &f64, error: f64) {
+        let mean = self.compute_mean(input);
+        let stddev = self.compute_stddev(input);
+
+        let [gl_mean, gl_stddev] = self.gl_partial(mean, stddev, *a);
+
+        self.params.modify(|p| {
+            p.0.update(input, gl_mean * error);
+            p.1.update(input, gl_stddev * error);
+        })
+    }
+
+    fn update_grad(&mut self, grad: &ArrayView2<f64>) {
+        self.params.weights_view_mut().add_assign(grad);
+    }
+
+    fn update_grad_scaled(&mut self, grad: &ArrayView2<f64>, factor: f64) {
+        self.params.weights_view_mut().scaled_add(factor, grad);
+    }
+
+    fn grad_log(&self, input: &I, a: &f64) -> Array2<f64> {
+        let mean = self.compute_mean(input);
+        let stddev = self.compute_stddev(input);
+
+        let [gl_mean, gl_stddev] = self.gl_partial(mean, stddev, *a);
+
+        let p = self.params.get();
+        let grad_mean: Array2<f64> = p.0.grad(input).into();
+        let grad_stddev: Array2<f64> = p.1.grad(input).into();
+
+        stack![Axis(0), gl_mean * grad_mean, gl_stddev * grad_stddev]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        fa::{Parameterised, linear::{LFA, basis::{Polynomial, Projector}, optim::SGD}},
+        policies::{DifferentiablePolicy, Policy},
+        spaces::real::Interval,
+    };
+    use super::{normal_cdf, normal_cdf_inv, TruncatedGaussian};
+
+    macro_rules! make_policy {
+        () => {{
+            let basis = Polynomial::new(1, 1).with_constant();
+            let n = basis.n_features();
+
+            let mut p = TruncatedGaussian::new(
+                LFA::scalar(basis.clone(), SGD(1.0)),
+                LFA::scalar(basis, SGD(1.0)),
+                Interval::bounded(-1.0, 1.0),
+            );
+
+            p.weights_view_mut().slice_mut(s![..n, ..]).fill(0.2);
+            p.weights_view_mut().slice_mut(s![n.., ..]).fill(0.5);
+            p
+        }};
+    }
+
+    #[test]
+    fn test_normal_cdf() {
+        assert!((normal_cdf(0.0) - 0.5).abs() < 1e-10);
+        assert!((normal_cdf(1.96) - 0.9750021).abs() < 1e-6);
+        assert!((normal_cdf(-1.0) + normal_cdf(1.0) - 1.0).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_normal_cdf_inv() {
+        for &x in [-2.0, -0.3, 0.0, 1.5].iter() {
+            assert!((normal_cdf_inv(normal_cdf(x), -5.0, 5.0) - x).abs() < 1e-8);
+        }
+    }
+
+    #[test]
+    fn test_density() {
+        const N: usize = 2000;
+
+        let p = make_policy!();
+        let s = vec![0.5];
+
+        let da = 2.0 / N as f64;
+        let total: f64 = (0..N).map(|i| {
+            p.probability(&s, &(-1.0 + (i as f64 + 0.5) * da)) * da
+        }).sum();
+
+        assert!((total - 1.0).abs() < 1e-5);
+        assert_eq!(p.probability(&s, &1.5), 0.0);
+    }
+
+    #[test]
+    fn test_grad_log() {
+        const EPS: f64 = 1e-6;
+
+        let mut p = make_policy!();
+
+        let s = vec![0.5];
+        let a = 0.3;
+
+        let grad = p.grad_log(&s, &a);
+        let (n_rows, n_cols) = grad.dim();
+
+        for i in 0..n_rows {
+            for j in 0..n_cols {
+                p.weights_view_mut()[[i, j]] += EPS;
+                let lp_plus = p.log_probability(&s, &a);
+
+                p.weights_view_mut()[[i, j]] -= 2.0 * EPS;
+                let lp_minus = p.log_probability(&s, &a);
+
+                p.weights_view_mut()[[i, j]] += EPS;
+
+                let fd = (lp_plus - lp_minus) / (2.0 * EPS);
+
+                assert!((grad[[i, j]] - fd).abs() < 1e-5, "∂/∂w[{}, {}]", i, j);
+            }
+        }
+    }
+}
@@ -0,0 +1,137 @@
+use crate::{
+    fa::EnumerableStateActionFunction,
+    policies::{EnumerablePolicy, Policy, VisitCounter},
+    utils::argmaxima,
+};
+use rand::Rng;
+
+/// Upper confidence bound exploration over state-action visit counts.
+///
+/// Selects the action maximising `Q(s, a) + c sqrt(ln(N(s) + 1) / N(s, a))`,
+/// with unvisited actions taking priority. The policy only reads from the
+/// counter; counts are typically maintained by wrapping the agent in a
+/// `control::exploration::CountBased` learner that shares the same counter.
+///
+/// # References
+/// - Auer, P., Cesa-Bianchi, N., Fischer, P. (2002). Finite-time analysis of
+/// the multiarmed bandit problem. Machine Learning, 47(2–3), 235–256.
+/// - Kocsis, L., Szepesvári, C. (2006). Bandit based Monte-Carlo planning. In
+/// European Conference on Machine Learning, pp. 282–293.
+#[derive(Clone, Debug)]
+pub struct UCB<Q, C> {
+    pub q_func: Q,
+    pub counter: C,
+
+    pub c: f64,
+}
+
+impl<Q, C> UCB<Q, C> {
+    pub fn new(q_func: Q, counter: C, c: f64) -> Self {
+        UCB {
+            q_func,
+            counter,
+
+            c,
+        }
+    }
+
+    /// Return the upper confidence index of each action in state `s`.
+    pub fn indices<S>(&self, s: &S) -> Vec<f64>
+    where
+        Q: EnumerableStateActionFunction<S>,
+        C: VisitCounter<S>,
+    {
+        let log_n = (self.counter.state_count(s) as f64 + 1.0).ln();
+
+        self.q_func.evaluate_all(s).into_iter().enumerate().map(|(a, q)| {
+            match self.counter.count(s, a) {
+                0 => ::std::f64::MAX,
+                n => q + self.c * (log_n / n as f64).sqrt(),
+            }
+        }).collect()
+    }
+}
+
+impl<S, Q, C> Policy<S> for UCB<Q, C>
+where
+    Q: EnumerableStateActionFunction<S>,
+    C: VisitCounter<S>,
+{
+    type Action = usize;
+
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R, s: &S) -> usize {
+        let (_, maxima) = argmaxima(&self.indices(s));
+
+        maxima[rng.gen_range(0, maxima.len())]
+    }
+
+    fn mpa(&self, s: &S) -> usize { argmaxima(&self.indices(s)).1[0] }
+
+    fn probability(&self, s: &S, a: &usize) -> f64 { self.probabilities(s)[*a] }
+}
+
+impl<S, Q, C> EnumerablePolicy<S> for UCB<Q, C>
+where
+    Q: EnumerableStateActionFunction<S>,
+    C: VisitCounter<S>,
+{
+    fn n_actions(&self) -> usize { self.q_func.n_actions() }
+
+    fn probabilities(&self, s: &S) -> Vec<f64> {
+        let indices = self.indices(s);
+        let mut ps = vec![0.0; indices.len()];
+
+        let (_, maxima) = argmaxima(&indices);
+
+        let p = 1.0 / maxima.len() as f64;
+        for i in maxima {
+            ps[i] = p;
+        }
+
+        ps
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        fa::mocking::MockQ,
+        policies::{HashedCounter, VisitCounter},
+        utils::compare_floats,
+    };
+    use super::{EnumerablePolicy, Policy, UCB};
+
+    #[test]
+    fn test_unvisited_first() {
+        let q = MockQ::new_shared(Some(vec![1.0, 0.0, 0.0].into()));
+        let counter = HashedCounter::new(3, |_: &Vec<f64>| 0u64);
+        let mut p = UCB::new(q, counter, 1.0);
+
+        p.counter.increment(&vec![], 0);
+
+        assert!(compare_floats(p.probabilities(&vec![]), &[0.0, 0.5, 0.5], 1e-7));
+
+        p.counter.increment(&vec![], 1);
+        p.counter.increment(&vec![], 2);
+
+        assert_eq!(p.mpa(&vec![]), 0);
+    }
+
+    #[test]
+    fn test_bonus() {
+        let q = MockQ::new_shared(Some(vec![1.0, 0.9].into()));
+        let counter = HashedCounter::new(2, |_: &Vec<f64>| 0u64);
+        let mut p = UCB::new(q, counter, 1.0);
+
+        for _ in 0..100 {
+            p.counter.increment(&vec![], 0);
+        }
+
+        p.counter.increment(&vec![], 1);
+
+        let indices = p.indices(&vec![]);
+
+        assert!(indices[1] > indices[0]);
+        assert!((indices[1] - 0.9 - (102.0f64).ln().sqrt()).abs() < 1e-7);
+    }
+}
@@ -19,6 +19,15 @@ impl<S, A, T: ActionValuePredictor<S, A>> ActionValuePredictor<S, A> for Shared<
     fn predict_q(&self, s: &S, a: &A) -> f64 { self.borrow().predict_q(s, a) }
 }
 
+pub trait AverageRewardPredictor {
+    /// Return the estimated average reward rate of the policy.
+    fn predict_rho(&self) -> f64;
+}
+
+impl<T: AverageRewardPredictor> AverageRewardPredictor for Shared<T> {
+    fn predict_rho(&self) -> f64 { self.borrow().predict_rho() }
+}
+
 pub mod gtd;
 pub mod lstd;
 pub mod mc;
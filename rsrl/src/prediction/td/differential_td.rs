@@ -0,0 +1,69 @@
+use crate::{
+    OnlineLearner,
+    domains::Transition,
+    fa::{Weights, WeightsView, WeightsViewMut, Parameterised, StateFunction},
+    prediction::{AverageRewardPredictor, ValuePredictor},
+};
+
+/// Differential semi-gradient TD(0) for the average reward setting.
+///
+/// Learns the differential value function, `v(s)`, along with an estimate of
+/// the average reward rate, `rho`, using the differential TD error
+/// `r - rho + v(s') - v(s)`. Terminal transitions, which should not occur in a
+/// continuing task, bootstrap from zero.
+///
+/// # References
+/// - Sutton, R. S. and Barto, A. G. (2018). Reinforcement Learning: An
+/// Introduction (2nd ed.). MIT Press, Section 10.3.
+#[derive(Clone, Debug, Serialize, Deserialize, Parameterised)]
+pub struct DifferentialTD<V> {
+    #[weights] pub v_func: V,
+
+    pub alpha: f64,
+    pub beta: f64,
+
+    pub rho: f64,
+}
+
+impl<V> DifferentialTD<V> {
+    pub fn new(v_func: V, alpha: f64, beta: f64) -> Self {
+        DifferentialTD {
+            v_func,
+
+            alpha,
+            beta,
+
+            rho: 0.0,
+        }
+    }
+}
+
+impl<S, A, V> OnlineLearner<S, A> for DifferentialTD<V>
+where
+    V: StateFunction<S, Output = f64>
+{
+    fn handle_transition(&mut self, t: &Transition<S, A>) {
+        let s = t.from.state();
+        let v = self.v_func.evaluate(s);
+
+        let td_error = if t.terminated() {
+            t.reward - self.rho - v
+        } else {
+            t.reward - self.rho + self.v_func.evaluate(t.to.state()) - v
+        };
+
+        self.rho += self.beta * td_error;
+        self.v_func.update(s, self.alpha * td_error);
+    }
+}
+
+impl<S, V> ValuePredictor<S> for DifferentialTD<V>
+where
+    V: StateFunction<S, Output = f64>
+{
+    fn predict_v(&self, s: &S) -> f64 { self.v_func.evaluate(s) }
+}
+
+impl<V> AverageRewardPredictor for DifferentialTD<V> {
+    fn predict_rho(&self) -> f64 { self.rho }
+}
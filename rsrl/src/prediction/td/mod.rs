@@ -1,5 +1,6 @@
 import_all!(td);
 import_all!(td_lambda);
+import_all!(differential_td);
 
 // TODO:
 // n-step TD - Sutton & Barto
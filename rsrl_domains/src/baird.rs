@@ -0,0 +1,195 @@
+use crate::{Domain, FiniteMDP, Observation, Transition, spaces::discrete::Ordinal};
+use ndarray::{Array1, Array2, Array3};
+use rand::{thread_rng, Rng, rngs::ThreadRng};
+
+const N_STATES: usize = 7;
+const N_FEATURES: usize = 8;
+
+/// Index of the action leading uniformly to one of the six upper states.
+pub const BAIRD_DASHED: usize = 0;
+
+/// Index of the action leading deterministically to the lower state.
+pub const BAIRD_SOLID: usize = 1;
+
+/// Baird's counterexample for off-policy learning with function approximation.
+///
+/// The continuing task comprises six upper states (`0..6`) and a single lower
+/// state (`6`). The dashed action moves to one of the upper states uniformly at
+/// random, and the solid action moves to the lower state; all rewards are zero,
+/// so the true value function is identically zero. Under the standard
+/// behaviour policy (dashed with probability 6/7) and target policy (always
+/// solid), off-policy semi-gradient TD diverges with the standard eight
+/// features, despite the true values being representable.
+///
+/// # References
+/// - Baird, L. (1995). Residual algorithms: Reinforcement learning with
+/// function approximation. In Proceedings of the 12th International
+/// Conference on Machine Learning, pp. 30–37.
+/// - Sutton, R. S. and Barto, A. G. (2018). Reinforcement Learning: An
+/// Introduction (2nd ed.). MIT Press, Example 11.1.
+#[derive(Debug)]
+pub struct BairdCounterexample {
+    loc: usize,
+
+    rng: ThreadRng,
+}
+
+impl BairdCounterexample {
+    /// Standard discount factor of the task.
+    pub const GAMMA: f64 = 0.99;
+
+    pub fn new() -> BairdCounterexample {
+        let mut rng = thread_rng();
+
+        BairdCounterexample {
+            loc: rng.gen_range(0, N_STATES),
+
+            rng,
+        }
+    }
+
+    /// Feature vector of state `s`.
+    pub fn features(s: usize) -> Array1<f64> {
+        let mut phi = Array1::zeros(N_FEATURES);
+
+        if s < N_STATES - 1 {
+            phi[s] = 2.0;
+            phi[N_FEATURES - 1] = 1.0;
+        } else {
+            phi[N_FEATURES - 2] = 1.0;
+            phi[N_FEATURES - 1] = 2.0;
+        }
+
+        phi
+    }
+
+    /// Stack the feature vectors of all states into a matrix.
+    pub fn feature_matrix() -> Array2<f64> {
+        let mut phi = Array2::zeros((N_STATES, N_FEATURES));
+
+        for (s, mut row) in phi.outer_iter_mut().enumerate() {
+            row.assign(&BairdCounterexample::features(s));
+        }
+
+        phi
+    }
+
+    /// Standard initial weights, `(1, 1, 1, 1, 1, 1, 10, 1)`.
+    pub fn initial_weights() -> Array1<f64> {
+        let mut w = Array1::ones(N_FEATURES);
+        w[N_FEATURES - 2] = 10.0;
+
+        w
+    }
+
+    /// Return the true value of every state, which is zero under any policy.
+    pub fn true_values() -> Array1<f64> { Array1::zeros(N_STATES) }
+
+    /// Behaviour policy matrix, `[S, A]`.
+    pub fn behaviour_policy() -> Array2<f64> {
+        Array2::from_shape_fn((N_STATES, 2), |(_, a)| {
+            if a == BAIRD_DASHED { 6.0 / 7.0 } else { 1.0 / 7.0 }
+        })
+    }
+
+    /// Target policy matrix, `[S, A]`.
+    pub fn target_policy() -> Array2<f64> {
+        Array2::from_shape_fn((N_STATES, 2), |(_, a)| if a == BAIRD_SOLID { 1.0 } else { 0.0 })
+    }
+
+    /// Enumerate the dynamics of the domain into an explicit `FiniteMDP`.
+    pub fn to_mdp(gamma: f64) -> FiniteMDP {
+        let mut transitions = Array3::zeros((N_STATES, 2, N_STATES));
+
+        for s in 0..N_STATES {
+            for ns in 0..(N_STATES - 1) {
+                transitions[[s, BAIRD_DASHED, ns]] = 1.0 / (N_STATES - 1) as f64;
+            }
+
+            transitions[[s, BAIRD_SOLID, N_STATES - 1]] = 1.0;
+        }
+
+        FiniteMDP::new(transitions, Array2::zeros((N_STATES, 2)), gamma)
+    }
+}
+
+impl Default for BairdCounterexample {
+    fn default() -> BairdCounterexample { BairdCounterexample::new() }
+}
+
+impl Domain for BairdCounterexample {
+    type StateSpace = Ordinal;
+    type ActionSpace = Ordinal;
+
+    fn emit(&self) -> Observation<usize> { Observation::Full(self.loc) }
+
+    fn step(&mut self, action: usize) -> Transition<usize, usize> {
+        let from = self.emit();
+
+        self.loc = match action {
+            BAIRD_DASHED => self.rng.gen_range(0, N_STATES - 1),
+            BAIRD_SOLID => N_STATES - 1,
+            _ => panic!("Unknown action {}!", action),
+        };
+
+        Transition {
+            from,
+            action,
+            reward: 0.0,
+            to: self.emit(),
+        }
+    }
+
+    fn state_space(&self) -> Self::StateSpace { Ordinal::new(N_STATES) }
+
+    fn action_space(&self) -> Ordinal { Ordinal::new(2) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BAIRD_DASHED, BAIRD_SOLID, BairdCounterexample, Domain};
+
+    #[test]
+    fn test_dynamics() {
+        let mut domain = BairdCounterexample::default();
+
+        for _ in 0..100 {
+            let t = domain.step(BAIRD_DASHED);
+
+            assert!(*t.to.state() < 6);
+            assert_eq!(t.reward, 0.0);
+            assert!(!t.terminated());
+        }
+
+        assert_eq!(*domain.step(BAIRD_SOLID).to.state(), 6);
+    }
+
+    #[test]
+    fn test_features() {
+        let phi = BairdCounterexample::feature_matrix();
+
+        assert_eq!(phi.row(0).to_vec(), vec![2.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 1.0]);
+        assert_eq!(phi.row(6).to_vec(), vec![0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 1.0, 2.0]);
+    }
+
+    #[test]
+    fn test_semi_gradient_dp_diverges() {
+        let mdp = BairdCounterexample::to_mdp(BairdCounterexample::GAMMA);
+        let phi = BairdCounterexample::feature_matrix();
+        let p_pi = mdp.policy_transitions(&BairdCounterexample::target_policy());
+
+        let mut w = BairdCounterexample::initial_weights();
+        let w0_norm = w.dot(&w).sqrt();
+
+        // Expected off-policy semi-gradient TD(0) update under the uniform
+        // state distribution:
+        for _ in 0..1000 {
+            let v = phi.dot(&w);
+            let delta = mdp.gamma * p_pi.dot(&v) - &v;
+
+            w.scaled_add(0.01 / 7.0, &phi.t().dot(&delta));
+        }
+
+        assert!(w.dot(&w).sqrt() > 10.0 * w0_norm);
+    }
+}
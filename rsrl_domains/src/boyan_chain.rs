@@ -0,0 +1,180 @@
+use crate::{Domain, FiniteMDP, Observation, Transition, spaces::discrete::Ordinal};
+use ndarray::{Array1, Array2, Array3};
+use rand::{thread_rng, Rng, rngs::ThreadRng};
+
+const N_STATES: usize = 13;
+const N_FEATURES: usize = 4;
+
+/// Boyan's chain prediction task.
+///
+/// Episodes start in state 12 and move down the chain by one or two states
+/// with equal probability, receiving a reward of -3 per step, until reaching
+/// the terminal state 0. From state 1 the only transition is to state 0, with a
+/// reward of -2. The standard representation comprises four features which
+/// linearly interpolate between states 12, 8, 4 and 0; the true (undiscounted)
+/// value function, `v(s) = -2s`, is exactly representable with weights
+/// `(-24, -16, -8, 0)`.
+///
+/// # References
+/// - Boyan, J. A. (2002). Technical update: Least-squares temporal difference
+/// learning. Machine Learning, 49(2–3), 233–246.
+#[derive(Debug)]
+pub struct BoyanChain {
+    loc: usize,
+
+    rng: ThreadRng,
+}
+
+impl BoyanChain {
+    pub fn new() -> BoyanChain {
+        BoyanChain {
+            loc: N_STATES - 1,
+
+            rng: thread_rng(),
+        }
+    }
+
+    /// Interpolated feature vector of state `s`.
+    pub fn features(s: usize) -> Array1<f64> {
+        let mut phi = Array1::zeros(N_FEATURES);
+
+        let m = s / 4;
+        let r = (s % 4) as f64 / 4.0;
+
+        phi[N_FEATURES - 1 - m] = 1.0 - r;
+
+        if r > 0.0 {
+            phi[N_FEATURES - 2 - m] = r;
+        }
+
+        phi
+    }
+
+    /// Stack the feature vectors of all states into a matrix.
+    pub fn feature_matrix() -> Array2<f64> {
+        let mut phi = Array2::zeros((N_STATES, N_FEATURES));
+
+        for (s, mut row) in phi.outer_iter_mut().enumerate() {
+            row.assign(&BoyanChain::features(s));
+        }
+
+        phi
+    }
+
+    /// Weights under which the linear predictor recovers the true values.
+    pub fn true_weights() -> Array1<f64> { array![-24.0, -16.0, -8.0, 0.0] }
+
+    /// Return the true (undiscounted) value of every state.
+    pub fn true_values() -> Array1<f64> { Array1::from_shape_fn(N_STATES, |s| -2.0 * s as f64) }
+
+    /// Enumerate the dynamics of the domain into an explicit `FiniteMDP`.
+    pub fn to_mdp(gamma: f64) -> FiniteMDP {
+        let mut transitions = Array3::zeros((N_STATES, 1, N_STATES));
+        let mut rewards = Array2::zeros((N_STATES, 1));
+
+        transitions[[0, 0, 0]] = 1.0;
+        transitions[[1, 0, 0]] = 1.0;
+        rewards[[1, 0]] = -2.0;
+
+        for s in 2..N_STATES {
+            transitions[[s, 0, s - 1]] = 0.5;
+            transitions[[s, 0, s - 2]] = 0.5;
+            rewards[[s, 0]] = -3.0;
+        }
+
+        let mut terminals = vec![false; N_STATES];
+        terminals[0] = true;
+
+        let mut initial = Array1::zeros(N_STATES);
+        initial[N_STATES - 1] = 1.0;
+
+        FiniteMDP::new(transitions, rewards, gamma)
+            .with_terminals(terminals)
+            .with_initial(initial)
+    }
+}
+
+impl Default for BoyanChain {
+    fn default() -> BoyanChain { BoyanChain::new() }
+}
+
+impl Domain for BoyanChain {
+    type StateSpace = Ordinal;
+    type ActionSpace = Ordinal;
+
+    fn emit(&self) -> Observation<usize> {
+        if self.loc == 0 {
+            Observation::Terminal(self.loc)
+        } else {
+            Observation::Full(self.loc)
+        }
+    }
+
+    fn step(&mut self, action: usize) -> Transition<usize, usize> {
+        let from = self.emit();
+
+        let reward = match self.loc {
+            0 => 0.0,
+            1 => {
+                self.loc = 0;
+
+                -2.0
+            },
+            _ => {
+                self.loc -= if self.rng.gen_bool(0.5) { 1 } else { 2 };
+
+                -3.0
+            },
+        };
+
+        Transition {
+            from,
+            action,
+            reward,
+            to: self.emit(),
+        }
+    }
+
+    fn state_space(&self) -> Self::StateSpace { Ordinal::new(N_STATES) }
+
+    fn action_space(&self) -> Ordinal { Ordinal::new(1) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BoyanChain, Domain};
+
+    #[test]
+    fn test_rollout() {
+        let ts = BoyanChain::default().rollout(|_| 0);
+        let last = ts.last().unwrap();
+
+        assert_eq!(*ts[0].from.state(), 12);
+        assert!(last.terminated());
+        assert!(ts.len() >= 6 && ts.len() <= 12);
+    }
+
+    #[test]
+    fn test_features() {
+        assert_eq!(BoyanChain::features(12).to_vec(), vec![1.0, 0.0, 0.0, 0.0]);
+        assert_eq!(BoyanChain::features(10).to_vec(), vec![0.5, 0.5, 0.0, 0.0]);
+        assert_eq!(BoyanChain::features(5).to_vec(), vec![0.0, 0.25, 0.75, 0.0]);
+        assert_eq!(BoyanChain::features(0).to_vec(), vec![0.0, 0.0, 0.0, 1.0]);
+    }
+
+    #[test]
+    fn test_true_values() {
+        let v = BoyanChain::true_values();
+        let v_hat = BoyanChain::feature_matrix().dot(&BoyanChain::true_weights());
+
+        for s in 0..13 {
+            assert!((v[s] - v_hat[s]).abs() < 1e-7);
+        }
+
+        let q = BoyanChain::to_mdp(1.0).backup(v.view());
+
+        for s in 0..13 {
+            assert!((q[[s, 0]] - v[s]).abs() < 1e-7);
+        }
+    }
+}
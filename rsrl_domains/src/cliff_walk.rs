@@ -1,6 +1,6 @@
 use crate::spaces::{TwoSpace, discrete::Ordinal};
-use ndarray::Array2;
-use super::{Domain, Observation, Transition, grid_world::{GridWorld, Motion}};
+use ndarray::{Array1, Array2};
+use super::{Domain, FiniteMDP, Observation, Transition, grid_world::{GridWorld, Motion}};
 
 const ALL_ACTIONS: [Motion; 4] = [
     Motion::North(1),
@@ -23,6 +23,34 @@ impl CliffWalk {
             loc: [0; 2],
         }
     }
+
+    fn is_terminal(&self, loc: [usize; 2]) -> bool { loc[0] > 0 && loc[1] == 0 }
+
+    /// Enumerate the dynamics of the domain into an explicit `FiniteMDP`.
+    ///
+    /// Location `[x, y]` is mapped to state index `x + y * width`, and all
+    /// probability mass in the initial distribution is placed on `[0, 0]`.
+    pub fn to_mdp(&self, gamma: f64) -> FiniteMDP {
+        let width = self.gw.width();
+        let mdp = FiniteMDP::from_grid_world(
+            &self.gw,
+            &ALL_ACTIONS,
+            |_, _, nloc| if !self.is_terminal(nloc) {
+                0.0
+            } else if nloc[0] == width - 1 {
+                50.0
+            } else {
+                -50.0
+            },
+            |loc| self.is_terminal(loc),
+            gamma,
+        );
+
+        let mut initial = Array1::zeros(mdp.n_states());
+        initial[0] = 1.0;
+
+        mdp.with_initial(initial)
+    }
 }
 
 impl Default for CliffWalk {
@@ -34,7 +62,7 @@ impl Domain for CliffWalk {
     type ActionSpace = Ordinal;
 
     fn emit(&self) -> Observation<[usize; 2]> {
-        if self.loc[0] > 0 && self.loc[1] == 0 {
+        if self.is_terminal(self.loc) {
             Observation::Terminal(self.loc)
         } else {
             Observation::Full(self.loc)
@@ -119,6 +147,29 @@ mod tests {
         assert!(t.reward.is_sign_positive());
     }
 
+    #[test]
+    fn test_to_mdp() {
+        let cw = CliffWalk::default();
+        let mdp = cw.to_mdp(0.9);
+
+        assert_eq!(mdp.n_states(), 60);
+        assert_eq!(mdp.n_actions(), 4);
+        assert_eq!(mdp.initial[0], 1.0);
+
+        assert!(!mdp.terminals[0]);
+        assert!(mdp.terminals[1]);
+        assert!(mdp.terminals[11]);
+        assert!(!mdp.terminals[12]);
+
+        // Stepping east from the start drops into the cliff:
+        assert_eq!(mdp.transitions[[0, 1, 1]], 1.0);
+        assert_eq!(mdp.rewards[[0, 1]], -50.0);
+
+        // Stepping south from above the goal reaches it:
+        assert_eq!(mdp.transitions[[23, 2, 11]], 1.0);
+        assert_eq!(mdp.rewards[[23, 2]], 50.0);
+    }
+
     #[test]
     fn test_safe() {
         let mut cw = CliffWalk::default();
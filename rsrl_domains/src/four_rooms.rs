@@ -0,0 +1,187 @@
+use crate::spaces::{TwoSpace, discrete::Ordinal};
+use super::{ActionMask, Domain, Observation, Transition, grid_world::{GridWorld, Motion}};
+
+const ALL_ACTIONS: [Motion; 4] = [
+    Motion::North(1),
+    Motion::East(1),
+    Motion::South(1),
+    Motion::West(1),
+];
+
+const LAYOUT: &str = "\
+1 1 1 1 1 1 1 1 1 1 1 1 1
+1 0 0 0 0 0 1 0 0 0 0 0 1
+1 0 0 0 0 0 1 0 0 0 0 0 1
+1 0 0 0 0 0 0 0 0 0 0 0 1
+1 0 0 0 0 0 1 0 0 0 0 0 1
+1 0 0 0 0 0 1 0 0 0 0 0 1
+1 1 0 1 1 1 1 0 0 0 0 0 1
+1 0 0 0 0 0 1 1 1 0 1 1 1
+1 0 0 0 0 0 1 0 0 0 0 0 1
+1 0 0 0 0 0 1 0 0 0 0 0 1
+1 0 0 0 0 0 0 0 0 0 0 0 1
+1 0 0 0 0 0 1 0 0 0 0 0 1
+1 1 1 1 1 1 1 1 1 1 1 1 1";
+
+/// Locations of the four hallways connecting the rooms.
+pub const FOUR_ROOMS_HALLWAYS: [[usize; 2]; 4] = [[3, 6], [6, 2], [7, 9], [10, 6]];
+
+/// The four-rooms navigation task.
+///
+/// A 13x13 grid, bounded by walls and divided into four rooms connected by
+/// single-cell hallways. Moving into a wall leaves the agent in place, and
+/// reaching the goal ends the episode with a reward of one; all other
+/// transitions yield zero reward.
+///
+/// # References
+/// - Sutton, R. S., Precup, D., & Singh, S. (1999). Between MDPs and
+/// semi-MDPs: A framework for temporal abstraction in reinforcement learning.
+/// Artificial Intelligence, 112(1-2), 181-211.
+pub struct FourRooms {
+    gw: GridWorld<u8>,
+    loc: [usize; 2],
+    goal: [usize; 2],
+}
+
+impl FourRooms {
+    pub fn new(start: [usize; 2], goal: [usize; 2]) -> FourRooms {
+        let gw = GridWorld::from_str(LAYOUT);
+
+        if gw.get(start) != Some(&0) || gw.get(goal) != Some(&0) {
+            panic!("Start and goal locations must be empty cells.");
+        }
+
+        FourRooms { gw, loc: start, goal, }
+    }
+
+    /// Return true if `loc` is a wall.
+    pub fn is_wall(&self, loc: [usize; 2]) -> bool { self.gw.get(loc) != Some(&0) }
+
+    /// Return the index of the room containing `loc`, or `None` for hallways
+    /// and walls.
+    pub fn room(&self, loc: [usize; 2]) -> Option<usize> {
+        if self.is_wall(loc) || FOUR_ROOMS_HALLWAYS.contains(&loc) {
+            None
+        } else {
+            let lower = if loc[0] < 6 || (loc[0] == 6 && loc[1] > 6) { 0 } else { 2 };
+            let right = if loc[1] < 6 { 0 } else { 1 };
+
+            Some(lower + right)
+        }
+    }
+}
+
+impl Default for FourRooms {
+    fn default() -> FourRooms { FourRooms::new([1, 1], [11, 11]) }
+}
+
+impl Domain for FourRooms {
+    type StateSpace = TwoSpace<Ordinal>;
+    type ActionSpace = Ordinal;
+
+    fn emit(&self) -> Observation<[usize; 2]> {
+        if self.loc == self.goal {
+            Observation::Terminal(self.loc)
+        } else {
+            Observation::Full(self.loc)
+        }
+    }
+
+    fn step(&mut self, action: usize) -> Transition<[usize; 2], usize> {
+        let from = self.emit();
+        let nloc = self.gw.perform_motion(self.loc, ALL_ACTIONS[action]);
+
+        if !self.is_wall(nloc) {
+            self.loc = nloc;
+        }
+
+        let to = self.emit();
+
+        Transition {
+            from,
+            action,
+            reward: if to.is_terminal() { 1.0 } else { 0.0 },
+            to,
+        }
+    }
+
+    fn state_space(&self) -> Self::StateSpace {
+        TwoSpace::new([
+            Ordinal::new(self.gw.width()),
+            Ordinal::new(self.gw.height()),
+        ])
+    }
+
+    fn action_space(&self) -> Ordinal { Ordinal::new(4) }
+}
+
+impl ActionMask<[usize; 2]> for FourRooms {
+    /// Flag each action as legal if it does not move the agent into a wall.
+    fn legal_actions(&self, loc: &[usize; 2]) -> Vec<bool> {
+        ALL_ACTIONS.iter().map(|&m| !self.is_wall(self.gw.perform_motion(*loc, m))).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ActionMask, Domain, FourRooms, FOUR_ROOMS_HALLWAYS};
+
+    #[test]
+    fn test_walls() {
+        let mut fr = FourRooms::default();
+
+        // Moving south or west from the start runs into the outer walls:
+        fr.step(2);
+        fr.step(3);
+
+        assert_eq!(*fr.emit().state(), [1, 1]);
+    }
+
+    #[test]
+    fn test_rooms() {
+        let fr = FourRooms::default();
+
+        assert_eq!(fr.room([1, 1]), Some(0));
+        assert_eq!(fr.room([1, 11]), Some(1));
+        assert_eq!(fr.room([11, 1]), Some(2));
+        assert_eq!(fr.room([11, 11]), Some(3));
+        assert_eq!(fr.room([6, 11]), Some(1));
+
+        for h in FOUR_ROOMS_HALLWAYS.iter() {
+            assert!(!fr.is_wall(*h));
+            assert_eq!(fr.room(*h), None);
+        }
+    }
+
+    #[test]
+    fn test_goal() {
+        let mut fr = FourRooms::new([11, 10], [11, 11]);
+        let t = fr.step(0);
+
+        assert!(t.terminated());
+        assert_eq!(t.reward, 1.0);
+    }
+
+    #[test]
+    fn test_action_mask() {
+        let fr = FourRooms::default();
+
+        assert_eq!(fr.legal_actions(&[1, 1]), vec![true, true, false, false]);
+
+        // Each hallway is bounded by walls on two sides, and the mask agrees
+        // with the dynamics:
+        for h in FOUR_ROOMS_HALLWAYS.iter() {
+            let legal = fr.legal_actions(h);
+
+            assert_eq!(legal.iter().filter(|&&l| l).count(), 2);
+
+            for (a, &l) in legal.iter().enumerate() {
+                let mut fr = FourRooms::new(*h, [11, 11]);
+
+                fr.step(a);
+
+                assert_eq!(*fr.emit().state() != *h, l);
+            }
+        }
+    }
+}
@@ -1,4 +1,5 @@
 #![allow(dead_code)]
+use crate::ActionMask;
 use ndarray::Array2;
 use std::{
     cmp,
@@ -170,8 +171,18 @@ impl<T> GridWorld<T> {
     }
 }
 
+impl<T> ActionMask<[usize; 2]> for GridWorld<T> {
+    /// Flag each of the four cardinal unit motions as legal if it stays within
+    /// the bounds of the grid.
+    fn legal_actions(&self, loc: &[usize; 2]) -> Vec<bool> {
+        (0..4).map(|i| self.valid_motion(*loc, Motion::from_usize(i, 1))).collect()
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use crate::ActionMask;
+    use ndarray::Array2;
     use super::{GridWorld, Motion};
 
     #[test]
@@ -352,4 +363,13 @@ mod tests {
             gw.move_west(loc, 3)
         );
     }
+
+    #[test]
+    fn test_legal_actions() {
+        let gw = GridWorld::new(Array2::from_elem((3, 3), ()));
+
+        assert_eq!(gw.legal_actions(&[0, 0]), vec![true, true, false, false]);
+        assert_eq!(gw.legal_actions(&[1, 1]), vec![true; 4]);
+        assert_eq!(gw.legal_actions(&[2, 2]), vec![false, false, true, true]);
+    }
 }
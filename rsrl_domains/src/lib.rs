@@ -221,9 +221,25 @@ pub trait Domain {
     fn action_space(&self) -> Self::ActionSpace;
 }
 
+/// An interface for state-dependent sets of legal actions over an enumerable
+/// action space.
+pub trait ActionMask<S: ?Sized> {
+    /// Return a flag for each action indicating whether it is legal in `state`.
+    fn legal_actions(&self, state: &S) -> Vec<bool>;
+}
+
+impl<S: ?Sized, F: Fn(&S) -> Vec<bool>> ActionMask<S> for F {
+    fn legal_actions(&self, state: &S) -> Vec<bool> { self(state) }
+}
+
 mod consts;
 mod macros;
+
 mod grid_world;
+pub use self::grid_world::*;
+
+mod mdp;
+pub use self::mdp::*;
 
 mod ode;
 use self::ode::*;
@@ -240,12 +256,24 @@ pub use self::acrobot::*;
 mod hiv;
 pub use self::hiv::*;
 
+mod four_rooms;
+pub use self::four_rooms::*;
+
 mod cliff_walk;
 pub use self::cliff_walk::*;
 
 mod roulette;
 pub use self::roulette::*;
 
+mod random_walk;
+pub use self::random_walk::*;
+
+mod boyan_chain;
+pub use self::boyan_chain::*;
+
+mod baird;
+pub use self::baird::*;
+
 #[cfg(feature = "openai")]
 mod openai;
 #[cfg(feature = "openai")]
@@ -0,0 +1,258 @@
+use crate::grid_world::{GridWorld, Motion};
+use ndarray::{Array1, Array2, Array3, ArrayView1, Axis};
+
+/// Explicit representation of a finite Markov decision process.
+///
+/// States flagged as terminal are treated as absorbing with zero value; any
+/// probability mass leading into them therefore contributes only the immediate
+/// reward.
+#[derive(Clone, Debug)]
+pub struct FiniteMDP {
+    /// Transition tensor with entries `P[s, a, s'] = Pr(s' | s, a)`.
+    pub transitions: Array3<f64>,
+
+    /// Expected immediate reward, `R[s, a]`.
+    pub rewards: Array2<f64>,
+
+    /// Indicator of whether each state is terminal.
+    pub terminals: Vec<bool>,
+
+    /// Distribution over initial states.
+    pub initial: Array1<f64>,
+
+    /// Discount factor.
+    pub gamma: f64,
+}
+
+impl FiniteMDP {
+    pub fn new(transitions: Array3<f64>, rewards: Array2<f64>, gamma: f64) -> FiniteMDP {
+        let (n_states, n_actions, n_next) = transitions.dim();
+
+        if n_states != n_next {
+            panic!("Transition tensor must have shape [S, A, S].");
+        }
+
+        if rewards.dim() != (n_states, n_actions) {
+            panic!("Reward matrix must have shape [S, A].");
+        }
+
+        for s in 0..n_states {
+            for a in 0..n_actions {
+                let z = transitions.slice(s![s, a, ..]).sum();
+
+                if (z - 1.0).abs() > 1e-7 {
+                    panic!("Transition probabilities for ({}, {}) sum to {}, not 1.", s, a, z);
+                }
+            }
+        }
+
+        FiniteMDP {
+            transitions,
+            rewards,
+            terminals: vec![false; n_states],
+            initial: Array1::from_elem(n_states, 1.0 / n_states as f64),
+            gamma,
+        }
+    }
+
+    /// Construct the MDP induced by deterministic motions on a `GridWorld`.
+    ///
+    /// State `[x, y]` is mapped to index `x + y * width`; terminal states
+    /// self-transition with zero reward.
+    pub fn from_grid_world<T>(
+        gw: &GridWorld<T>,
+        motions: &[Motion],
+        reward_fn: impl Fn([usize; 2], usize, [usize; 2]) -> f64,
+        terminal_fn: impl Fn([usize; 2]) -> bool,
+        gamma: f64,
+    ) -> FiniteMDP
+    {
+        let width = gw.width();
+        let n_states = width * gw.height();
+        let n_actions = motions.len();
+
+        let mut transitions = Array3::zeros((n_states, n_actions, n_states));
+        let mut rewards = Array2::zeros((n_states, n_actions));
+        let mut terminals = vec![false; n_states];
+
+        for y in 0..gw.height() {
+            for x in 0..width {
+                let loc = [x, y];
+                let s = x + y * width;
+
+                terminals[s] = terminal_fn(loc);
+
+                for (a, &motion) in motions.iter().enumerate() {
+                    if terminals[s] {
+                        transitions[[s, a, s]] = 1.0;
+                    } else {
+                        let nloc = gw.perform_motion(loc, motion);
+
+                        transitions[[s, a, nloc[0] + nloc[1] * width]] = 1.0;
+                        rewards[[s, a]] = reward_fn(loc, a, nloc);
+                    }
+                }
+            }
+        }
+
+        FiniteMDP::new(transitions, rewards, gamma).with_terminals(terminals)
+    }
+
+    /// Replace the terminal state indicators.
+    pub fn with_terminals(mut self, terminals: Vec<bool>) -> FiniteMDP {
+        if terminals.len() != self.n_states() {
+            panic!("Expected {} terminal indicators, got {}.", self.n_states(), terminals.len());
+        }
+
+        self.terminals = terminals;
+        self
+    }
+
+    /// Replace the initial state distribution.
+    pub fn with_initial(mut self, initial: Array1<f64>) -> FiniteMDP {
+        if initial.len() != self.n_states() {
+            panic!("Expected an initial distribution over {} states.", self.n_states());
+        }
+
+        self.initial = initial;
+        self
+    }
+
+    pub fn n_states(&self) -> usize { self.transitions.len_of(Axis(0)) }
+
+    pub fn n_actions(&self) -> usize { self.transitions.len_of(Axis(1)) }
+
+    /// Return the one-hot policy matrix, `[S, A]`, for a deterministic policy.
+    pub fn policy_matrix(&self, actions: &[usize]) -> Array2<f64> {
+        let mut pi = Array2::zeros((self.n_states(), self.n_actions()));
+
+        for (s, &a) in actions.iter().enumerate() {
+            pi[[s, a]] = 1.0;
+        }
+
+        pi
+    }
+
+    /// Return the state-to-state transition matrix induced by the policy `pi`,
+    /// with rows of terminal states set to zero.
+    pub fn policy_transitions(&self, pi: &Array2<f64>) -> Array2<f64> {
+        let n_states = self.n_states();
+        let mut p_pi = Array2::zeros((n_states, n_states));
+
+        for s in (0..n_states).filter(|&s| !self.terminals[s]) {
+            let mut row = p_pi.row_mut(s);
+
+            for a in 0..self.n_actions() {
+                row.scaled_add(pi[[s, a]], &self.transitions.slice(s![s, a, ..]));
+            }
+        }
+
+        p_pi
+    }
+
+    /// Return the expected reward in each state under the policy `pi`.
+    pub fn policy_rewards(&self, pi: &Array2<f64>) -> Array1<f64> {
+        let mut r_pi = (&self.rewards * pi).sum_axis(Axis(1));
+
+        for s in (0..self.n_states()).filter(|&s| self.terminals[s]) {
+            r_pi[s] = 0.0;
+        }
+
+        r_pi
+    }
+
+    /// Apply the Bellman backup to a state-value vector, returning the
+    /// corresponding action-values, `[S, A]`.
+    pub fn backup(&self, v: ArrayView1<f64>) -> Array2<f64> {
+        let mut v = v.to_owned();
+
+        for s in (0..self.n_states()).filter(|&s| self.terminals[s]) {
+            v[s] = 0.0;
+        }
+
+        let mut q = self.rewards.clone();
+
+        for s in 0..self.n_states() {
+            if self.terminals[s] {
+                q.row_mut(s).fill(0.0);
+
+                continue;
+            }
+
+            for a in 0..self.n_actions() {
+                q[[s, a]] += self.gamma * self.transitions.slice(s![s, a, ..]).dot(&v);
+            }
+        }
+
+        q
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::grid_world::{GridWorld, Motion};
+    use ndarray::Array2;
+    use super::FiniteMDP;
+
+    fn corridor() -> FiniteMDP {
+        let gw = GridWorld::new(Array2::from_elem((1, 3), ()));
+
+        FiniteMDP::from_grid_world(
+            &gw,
+            &[Motion::West(1), Motion::East(1)],
+            |_, _, nloc| if nloc[0] == 2 { 1.0 } else { 0.0 },
+            |loc| loc[0] == 2,
+            0.5,
+        )
+    }
+
+    #[test]
+    fn test_from_grid_world() {
+        let mdp = corridor();
+
+        assert_eq!(mdp.n_states(), 3);
+        assert_eq!(mdp.n_actions(), 2);
+        assert_eq!(mdp.terminals, vec![false, false, true]);
+
+        assert_eq!(mdp.transitions[[0, 0, 0]], 1.0);
+        assert_eq!(mdp.transitions[[0, 1, 1]], 1.0);
+        assert_eq!(mdp.transitions[[1, 1, 2]], 1.0);
+        assert_eq!(mdp.rewards[[1, 1]], 1.0);
+        assert_eq!(mdp.rewards[[0, 1]], 0.0);
+    }
+
+    #[test]
+    fn test_policy_matrices() {
+        let mdp = corridor();
+        let pi = mdp.policy_matrix(&[1, 1, 1]);
+
+        let p_pi = mdp.policy_transitions(&pi);
+        let r_pi = mdp.policy_rewards(&pi);
+
+        assert_eq!(p_pi[[0, 1]], 1.0);
+        assert_eq!(p_pi[[1, 2]], 1.0);
+        assert_eq!(p_pi.row(2).sum(), 0.0);
+        assert_eq!(r_pi.to_vec(), vec![0.0, 1.0, 0.0]);
+    }
+
+    #[test]
+    fn test_backup() {
+        let mdp = corridor();
+        let q = mdp.backup(array![1.0, 2.0, 100.0].view());
+
+        assert!((q[[0, 0]] - 0.5).abs() < 1e-7);
+        assert!((q[[0, 1]] - 1.0).abs() < 1e-7);
+        assert!((q[[1, 1]] - 1.0).abs() < 1e-7);
+        assert_eq!(q[[2, 0]], 0.0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_invalid_transitions() {
+        FiniteMDP::new(
+            ndarray::Array3::zeros((2, 1, 2)),
+            Array2::zeros((2, 1)),
+            0.9,
+        );
+    }
+}
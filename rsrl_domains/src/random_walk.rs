@@ -0,0 +1,222 @@
+use crate::{Domain, FiniteMDP, Observation, Transition, spaces::discrete::Ordinal};
+use ndarray::{Array1, Array2, Array3};
+use rand::{thread_rng, Rng, rngs::ThreadRng};
+
+/// Bounded random walk prediction task.
+///
+/// The chain comprises `n_states` non-terminal states, indexed `1..=n_states`,
+/// flanked by terminal states `0` and `n_states + 1`. Each episode starts in
+/// the centre of the chain and moves left or right with equal probability,
+/// yielding a reward of -1 on reaching the left terminal and +1 on reaching the
+/// right. The single action available to the agent has no effect.
+///
+/// # References
+/// - Sutton, R. S. and Barto, A. G. (2018). Reinforcement Learning: An
+/// Introduction (2nd ed.). MIT Press, Example 7.1.
+#[derive(Debug)]
+pub struct RandomWalk {
+    n_states: usize,
+    loc: usize,
+
+    rng: ThreadRng,
+}
+
+impl RandomWalk {
+    pub fn new(n_states: usize) -> RandomWalk {
+        if n_states == 0 {
+            panic!("RandomWalk requires at least one non-terminal state.");
+        }
+
+        RandomWalk {
+            n_states,
+            loc: (n_states + 1) / 2,
+
+            rng: thread_rng(),
+        }
+    }
+
+    fn is_terminal(&self, s: usize) -> bool { s == 0 || s == self.n_states + 1 }
+
+    /// One-hot features over the non-terminal states; terminal states map to
+    /// the zero vector.
+    pub fn tabular_features(&self, s: usize) -> Array1<f64> {
+        let mut phi = Array1::zeros(self.n_states);
+
+        if !self.is_terminal(s) {
+            phi[s - 1] = 1.0;
+        }
+
+        phi
+    }
+
+    /// Inverted features, with every feature active except that of the
+    /// current state, normalised to unit length.
+    pub fn inverted_features(&self, s: usize) -> Array1<f64> {
+        if self.is_terminal(s) {
+            return Array1::zeros(self.n_states);
+        }
+
+        let z = ((self.n_states - 1) as f64).sqrt().max(1.0);
+
+        (1.0 - self.tabular_features(s)) / z
+    }
+
+    /// State aggregation features, grouping adjacent non-terminal states into
+    /// `n_groups` (approximately) equally sized bins.
+    pub fn aggregated_features(&self, s: usize, n_groups: usize) -> Array1<f64> {
+        let mut phi = Array1::zeros(n_groups);
+
+        if !self.is_terminal(s) {
+            phi[(s - 1) * n_groups / self.n_states] = 1.0;
+        }
+
+        phi
+    }
+
+    /// Stack the feature vectors of all `n_states + 2` states into a matrix.
+    pub fn feature_matrix(&self, features: impl Fn(usize) -> Array1<f64>) -> Array2<f64> {
+        let rows: Vec<Array1<f64>> = (0..self.n_states + 2).map(features).collect();
+        let mut phi = Array2::zeros((rows.len(), rows[0].len()));
+
+        for (mut row, f) in phi.outer_iter_mut().zip(rows.iter()) {
+            row.assign(f);
+        }
+
+        phi
+    }
+
+    /// Return the true (undiscounted) value of every state.
+    pub fn true_values(&self) -> Array1<f64> {
+        let z = (self.n_states + 1) as f64;
+
+        Array1::from_shape_fn(self.n_states + 2, |s| if self.is_terminal(s) {
+            0.0
+        } else {
+            2.0 * s as f64 / z - 1.0
+        })
+    }
+
+    /// Enumerate the dynamics of the domain into an explicit `FiniteMDP`.
+    pub fn to_mdp(&self, gamma: f64) -> FiniteMDP {
+        let n = self.n_states + 2;
+
+        let mut transitions = Array3::zeros((n, 1, n));
+        let mut rewards = Array2::zeros((n, 1));
+
+        for s in 0..n {
+            if self.is_terminal(s) {
+                transitions[[s, 0, s]] = 1.0;
+            } else {
+                transitions[[s, 0, s - 1]] = 0.5;
+                transitions[[s, 0, s + 1]] = 0.5;
+
+                if s == 1 { rewards[[s, 0]] -= 0.5; }
+                if s == self.n_states { rewards[[s, 0]] += 0.5; }
+            }
+        }
+
+        let mut initial = Array1::zeros(n);
+        initial[(self.n_states + 1) / 2] = 1.0;
+
+        FiniteMDP::new(transitions, rewards, gamma)
+            .with_terminals((0..n).map(|s| self.is_terminal(s)).collect())
+            .with_initial(initial)
+    }
+}
+
+impl Default for RandomWalk {
+    fn default() -> RandomWalk { RandomWalk::new(19) }
+}
+
+impl Domain for RandomWalk {
+    type StateSpace = Ordinal;
+    type ActionSpace = Ordinal;
+
+    fn emit(&self) -> Observation<usize> {
+        if self.is_terminal(self.loc) {
+            Observation::Terminal(self.loc)
+        } else {
+            Observation::Full(self.loc)
+        }
+    }
+
+    fn step(&mut self, action: usize) -> Transition<usize, usize> {
+        let from = self.emit();
+
+        if !self.is_terminal(self.loc) {
+            if self.rng.gen_bool(0.5) {
+                self.loc += 1;
+            } else {
+                self.loc -= 1;
+            }
+        }
+
+        let to = self.emit();
+
+        Transition {
+            from,
+            action,
+            reward: match to {
+                Observation::Terminal(0) => -1.0,
+                Observation::Terminal(_) => 1.0,
+                _ => 0.0,
+            },
+            to,
+        }
+    }
+
+    fn state_space(&self) -> Self::StateSpace { Ordinal::new(self.n_states + 2) }
+
+    fn action_space(&self) -> Ordinal { Ordinal::new(1) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Domain, RandomWalk};
+
+    #[test]
+    fn test_initial_state() {
+        let rw = RandomWalk::default();
+
+        assert_eq!(*rw.emit().state(), 10);
+        assert!(!rw.emit().is_terminal());
+    }
+
+    #[test]
+    fn test_rollout_terminates() {
+        let ts = RandomWalk::new(5).rollout(|_| 0);
+        let last = ts.last().unwrap();
+
+        assert!(last.terminated());
+        assert_eq!(last.reward.abs(), 1.0);
+        assert!(ts[..ts.len() - 1].iter().all(|t| t.reward == 0.0));
+    }
+
+    #[test]
+    fn test_features() {
+        let rw = RandomWalk::new(5);
+
+        assert_eq!(rw.tabular_features(3).to_vec(), vec![0.0, 0.0, 1.0, 0.0, 0.0]);
+        assert_eq!(rw.tabular_features(0).to_vec(), vec![0.0; 5]);
+        assert_eq!(rw.inverted_features(3).to_vec(), vec![0.5, 0.5, 0.0, 0.5, 0.5]);
+        assert_eq!(rw.aggregated_features(2, 2).to_vec(), vec![1.0, 0.0]);
+        assert_eq!(rw.aggregated_features(4, 2).to_vec(), vec![0.0, 1.0]);
+        assert_eq!(rw.feature_matrix(|s| rw.tabular_features(s)).dim(), (7, 5));
+    }
+
+    #[test]
+    fn test_true_values() {
+        let rw = RandomWalk::default();
+        let mdp = rw.to_mdp(1.0);
+        let v = rw.true_values();
+
+        assert!((v[1] + 0.9).abs() < 1e-7);
+        assert!((v[19] - 0.9).abs() < 1e-7);
+
+        let q = mdp.backup(v.view());
+
+        for s in 0..21 {
+            assert!((q[[s, 0]] - v[s]).abs() < 1e-7);
+        }
+    }
+}
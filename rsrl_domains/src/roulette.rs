@@ -1,5 +1,5 @@
 use rand::{thread_rng, Rng, rngs::ThreadRng};
-use crate::{Domain, Observation, Transition, spaces::{real::Reals, discrete::Ordinal}};
+use crate::{ActionMask, Domain, Observation, Transition, spaces::{real::Reals, discrete::Ordinal}};
 
 #[derive(Debug)]
 pub struct Roulette {
@@ -24,7 +24,7 @@ impl Roulette {
     }
 
     pub fn update_state(&mut self, action: usize) {
-        if action == 156 {
+        if action == ROULETTE_QUIT {
             self.active = false;
 
             return;
@@ -223,6 +223,19 @@ impl Roulette {
     }
 }
 
+/// Index of the action which leaves the table.
+pub const ROULETTE_QUIT: usize = 156;
+
+impl ActionMask<f64> for Roulette {
+    /// Every bet is legal only if the current wealth covers the stake; quitting
+    /// is always legal.
+    fn legal_actions(&self, wealth: &f64) -> Vec<bool> {
+        let can_bet = *wealth >= self.bet_size;
+
+        (0..=ROULETTE_QUIT).map(|a| a == ROULETTE_QUIT || can_bet).collect()
+    }
+}
+
 impl Default for Roulette {
     fn default() -> Roulette { Roulette::new(1.0, 1.0) }
 }